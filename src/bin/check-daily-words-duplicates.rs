@@ -1,13 +1,9 @@
-use std::collections::HashSet;
+use sanuli::tooling;
 
 const WORDS: &str = include_str!("../../daily-words.txt");
 
 fn main() {
-    let mut words = HashSet::new();
-
-    for (line, word) in WORDS.lines().enumerate() {
-        if !words.insert(word) {
-            println!("{word} on line {line} is a duplicate");
-        }
+    for (line, word) in tooling::duplicates(WORDS) {
+        println!("{word} on line {line} is a duplicate");
     }
 }
@@ -2,6 +2,8 @@ use std::fs;
 use std::collections::HashSet;
 use rand::prelude::SliceRandom;
 
+use sanuli::tooling;
+
 const WORDS: &str = include_str!("../../full-words.txt");
 
 fn get_random_word(word_list: &[String]) -> String {
@@ -11,16 +13,8 @@ fn get_random_word(word_list: &[String]) -> String {
         .clone()
 }
 
-fn parse_words(words: &str, word_length: usize) -> Vec<String> {
-    words
-        .lines()
-        .filter(|word| word.chars().count() == word_length)
-        .map(|word| word.chars().collect())
-        .collect()
-}
-
 fn main() {
-    let word_list = parse_words(WORDS, 5);
+    let word_list = tooling::parse_words(WORDS, 5);
 
     let mut output: HashSet<String> = HashSet::new();
     while output.len() < 1000 {
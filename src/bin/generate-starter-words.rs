@@ -0,0 +1,30 @@
+use sanuli::manager::{self, WordLanguage, WordList};
+use sanuli::tooling;
+
+const WORD_LENGTHS: [usize; 2] = [5, 6];
+const STARTER_COUNT: usize = 3;
+
+// Prints the table of src/starters.rs
+fn main() {
+    let word_lists = manager::parse_all_words();
+
+    for (word_language, name) in [
+        (WordLanguage::Finnish, "Finnish"),
+        (WordLanguage::Swedish, "Swedish"),
+    ] {
+        for word_length in WORD_LENGTHS {
+            let words = word_lists
+                .get(&(word_language, WordList::Common, word_length))
+                .map(|words| words.iter().cloned().collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            let starters = tooling::starter_words(&words, STARTER_COUNT)
+                .iter()
+                .map(|word| format!("\"{word}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            println!("    (WordLanguage::{name}, {word_length}, &[{starters}]),");
+        }
+    }
+}
@@ -1,41 +1,21 @@
 use std::fs;
 
-const ALLOWED_KEYS: [char; 28] = [
-    'Q', 'W', 'E', 'R', 'T', 'Y', 'U', 'I', 'O', 'P', 'A', 'S', 'D', 'F', 'G', 'H', 'J', 'K',
-    'L', 'Ö', 'Ä', 'Z', 'X', 'C', 'V', 'B', 'N', 'M',
-];
-
-fn parse_word_list(data: String) -> Vec<String> {
-    let parts = data.split("<kotus-sanalista>\n").collect::<Vec<&str>>();
-    let words = parts[1].split("</kotus-sanalista>").collect::<Vec<&str>>();
-
-    let mut word_list = Vec::new();
-
-    for line in words[0].lines() {
-        let (word, _): (String, String) = serde_scan::scan!("<st><s>{}</s>{}" <- line).unwrap();
-
-        let count = word.chars().count();
-
-        if (count == 6 || count == 5)
-            && word
-                .to_uppercase()
-                .chars()
-                .all(|c| ALLOWED_KEYS.contains(&c))
-        {
-            word_list.push(word.to_uppercase());
-        }
-    }
-
-    word_list
-}
+use sanuli::game;
+use sanuli::manager::WordLanguage;
+use sanuli::tooling;
 
+// Usage: parse-kotus-word-list <word list file> [allowed characters, Finnish letters by default]
 fn main() {
-    let filename = std::env::args()
-        .nth(1)
-        .expect("No path to word list file given");
+    let mut args = std::env::args().skip(1);
+    let filename = args.next().expect("No path to word list file given");
     let data = fs::read_to_string(filename).expect("Unable to read word list file");
 
-    let word_list = parse_word_list(data);
+    let allowed_characters = match args.next() {
+        Some(characters) => characters.to_uppercase().chars().collect(),
+        None => game::alphabet(WordLanguage::Finnish).collect::<Vec<_>>(),
+    };
+
+    let word_list = tooling::parse_kotus_word_list(&data, &allowed_characters);
 
     let output_data = word_list.join("\n");
     fs::write("full-words-generated.txt", output_data).expect("Unable to write file");
@@ -0,0 +1,251 @@
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use serde::{Deserialize, Serialize};
+
+use sanuli::game::{self, Game, Letter, DEFAULT_MAX_GUESSES, DEFAULT_WORD_LENGTH};
+use sanuli::manager::{self, GameMode, KeyState, TileState, WordLanguage, WordList};
+use sanuli::sanuli::Sanuli;
+
+const USAGE: &str =
+    "Usage: sanuli-cli [--language fi|sv] [--length 5|6] [--list easy|common|full] [--streak-file PATH]";
+const STREAK_FILE: &str = ".sanuli-cli.json";
+
+const RESET: &str = "\x1b[0m";
+
+struct Options {
+    word_language: WordLanguage,
+    word_length: usize,
+    word_list: WordList,
+    streak_file: PathBuf,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Streak {
+    current: usize,
+    best: usize,
+    played: usize,
+    won: usize,
+}
+
+fn default_streak_file() -> PathBuf {
+    match env::var_os("HOME") {
+        Some(home) => PathBuf::from(home).join(STREAK_FILE),
+        None => PathBuf::from(STREAK_FILE),
+    }
+}
+
+fn parse_word_list(value: &str) -> Option<WordList> {
+    match value {
+        "easy" => Some(WordList::Easy),
+        "common" => Some(WordList::Common),
+        "full" => Some(WordList::Full),
+        _ => None,
+    }
+}
+
+fn parse_options() -> Result<Options, String> {
+    let mut options = Options {
+        word_language: WordLanguage::default(),
+        word_length: DEFAULT_WORD_LENGTH,
+        word_list: WordList::default(),
+        streak_file: default_streak_file(),
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {arg}"));
+        match arg.as_str() {
+            "--language" => {
+                let value = value()?;
+                options.word_language =
+                    WordLanguage::from_code(&value).ok_or(format!("Invalid language: {value}"))?;
+            }
+            "--length" => {
+                let value = value()?;
+                options.word_length = value
+                    .parse()
+                    .map_err(|_| format!("Invalid word length: {value}"))?;
+            }
+            "--list" => {
+                let value = value()?;
+                options.word_list =
+                    parse_word_list(&value).ok_or(format!("Invalid word list: {value}"))?;
+            }
+            "--streak-file" => options.streak_file = PathBuf::from(value()?),
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                process::exit(0);
+            }
+            _ => return Err(format!("Unknown argument: {arg}")),
+        }
+    }
+
+    Ok(options)
+}
+
+fn load_streak(path: &Path) -> Streak {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_streak(path: &Path, streak: &Streak) {
+    let contents = serde_json::to_string_pretty(streak).unwrap();
+    if let Err(err) = fs::write(path, contents) {
+        eprintln!("Unable to save streak to {}: {err}", path.display());
+    }
+}
+
+fn tile_color(state: &TileState) -> &'static str {
+    match state {
+        TileState::Correct => "\x1b[1;30;42m",
+        TileState::Present => "\x1b[1;30;43m",
+        TileState::Absent => "\x1b[1;37;100m",
+        TileState::Unknown => "\x1b[1m",
+    }
+}
+
+fn print_board(game: &Sanuli) {
+    let board = &game.boards()[0];
+    let submitted = if board.is_guessing {
+        board.current_guess
+    } else {
+        board.current_guess + 1
+    };
+
+    for guess in board.guesses.iter().take(submitted) {
+        let row = guess
+            .iter()
+            .map(|(character, state)| format!("{} {character} {RESET}", tile_color(state)))
+            .collect::<String>();
+        println!("{row}");
+    }
+}
+
+fn print_keyboard(game: &Sanuli) {
+    for row in game::keyboard_layout(game.word_language()) {
+        let keys = row
+            .iter()
+            .map(|key| match game.keyboard_state().get(key) {
+                Some(KeyState::Single(state)) => format!("{}{key}{RESET}", tile_color(state)),
+                _ => key.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        println!("{keys}");
+    }
+}
+
+fn read_guess(game: &mut Sanuli, line: &str) -> Result<(), String> {
+    let guess = line.trim().to_uppercase().chars().collect::<Vec<_>>();
+
+    if let Some(character) = guess.iter().find(|c| Letter::try_from(**c).is_err()) {
+        return Err(format!("Kirjain ei kelpaa: {character}"));
+    }
+    if guess.len() != game.word_length() {
+        return Err(format!(
+            "Sanassa pitää olla {} kirjainta!",
+            game.word_length()
+        ));
+    }
+
+    for character in guess {
+        game.push_character(character);
+    }
+
+    let current_guess = game.boards()[0].current_guess;
+    game.submit_guess();
+
+    if game.is_guessing() && game.boards()[0].current_guess == current_guess {
+        let message = game.message();
+        for _ in 0..game.word_length() {
+            game.pop_character();
+        }
+        return Err(message);
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let options = parse_options().unwrap_or_else(|err| {
+        eprintln!("{err}\n{USAGE}");
+        process::exit(1);
+    });
+
+    let word_lists = manager::parse_all_words();
+    if !word_lists.contains_key(&(
+        options.word_language,
+        options.word_list,
+        options.word_length,
+    )) {
+        eprintln!(
+            "No words of length {} on the chosen list",
+            options.word_length
+        );
+        process::exit(1);
+    }
+
+    let mut game = Sanuli::new(
+        options.word_language,
+        GameMode::Classic,
+        options.word_list,
+        options.word_length,
+        DEFAULT_MAX_GUESSES,
+        false,
+        word_lists,
+    );
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    while game.is_guessing() {
+        print!(
+            "Arvaus {}/{}: ",
+            game.boards()[0].current_guess + 1,
+            game.max_guesses()
+        );
+        io::stdout().flush().unwrap();
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            _ => {
+                println!();
+                return;
+            }
+        };
+
+        match read_guess(&mut game, &line) {
+            Ok(()) => {
+                print_board(&game);
+                if game.is_guessing() {
+                    print_keyboard(&game);
+                }
+            }
+            Err(message) => println!("{message}"),
+        }
+    }
+
+    println!("{}", game.message());
+
+    let mut streak = load_streak(&options.streak_file);
+    streak.played += 1;
+    if game.is_winner() {
+        streak.won += 1;
+        streak.current += 1;
+        streak.best = streak.best.max(streak.current);
+    } else {
+        streak.current = 0;
+    }
+    save_streak(&options.streak_file, &streak);
+
+    println!(
+        "Putki: {} (paras {}), voitettu {}/{}",
+        streak.current, streak.best, streak.won, streak.played
+    );
+}
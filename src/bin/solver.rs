@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::env;
+use std::io::{self, BufRead};
+use std::process;
+
+use sanuli::game::{KnownStates, DEFAULT_WORD_LENGTH};
+use sanuli::manager::{self, WordLanguage, WordList};
+use sanuli::solver;
+
+const USAGE: &str =
+    "Usage: solver [--length N] [--rank frequency|information] [--limit N] < guesses
+
+Each line of input is a guess and its colors, c = correct, p = present, a = absent:
+KOIRA cppaa";
+
+const DEFAULT_LIMIT: usize = 10;
+
+enum Rank {
+    Frequency,
+    Information,
+}
+
+struct Options {
+    word_length: Option<usize>,
+    rank: Rank,
+    limit: usize,
+}
+
+fn parse_options() -> Result<Options, String> {
+    let mut options = Options {
+        word_length: None,
+        rank: Rank::Frequency,
+        limit: DEFAULT_LIMIT,
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {arg}"));
+        match arg.as_str() {
+            "--length" => {
+                let value = value()?;
+                let word_length = value
+                    .parse()
+                    .map_err(|_| format!("Invalid word length: {value}"))?;
+                options.word_length = Some(word_length);
+            }
+            "--rank" => {
+                options.rank = match value()?.as_str() {
+                    "frequency" => Rank::Frequency,
+                    "information" => Rank::Information,
+                    other => return Err(format!("Invalid ranking: {other}")),
+                }
+            }
+            "--limit" => {
+                let value = value()?;
+                options.limit = value
+                    .parse()
+                    .map_err(|_| format!("Invalid limit: {value}"))?;
+            }
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                process::exit(0);
+            }
+            _ => return Err(format!("Unknown argument: {arg}")),
+        }
+    }
+
+    Ok(options)
+}
+
+fn exit_with(message: String) -> ! {
+    eprintln!("{message}\n{USAGE}");
+    process::exit(1);
+}
+
+fn main() {
+    let options = parse_options().unwrap_or_else(|err| exit_with(err));
+
+    let mut rows = Vec::new();
+    for (line_number, line) in io::stdin().lock().lines().enumerate() {
+        let line = line.unwrap_or_else(|err| exit_with(err.to_string()));
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+        match parts.as_slice() {
+            [] => continue,
+            [guess, tiles] => match solver::parse_feedback(guess, tiles) {
+                Some(row) => rows.push(row),
+                None => exit_with(format!("Invalid feedback on line {}", line_number + 1)),
+            },
+            _ => exit_with(format!("Invalid line {}: {line}", line_number + 1)),
+        }
+    }
+
+    let word_length = options
+        .word_length
+        .or_else(|| rows.first().map(|row| row.len()))
+        .unwrap_or(DEFAULT_WORD_LENGTH);
+
+    if rows.iter().any(|row| row.len() != word_length) {
+        exit_with(format!("Every guess should have {word_length} letters"));
+    }
+
+    let word_lists = manager::parse_all_words();
+    let words = match word_lists.get(&(WordLanguage::Finnish, WordList::Full, word_length)) {
+        Some(words) => words,
+        None => exit_with(format!("No words of length {word_length}")),
+    };
+
+    let mut states = KnownStates::default();
+    let mut counts = HashMap::new();
+    for row in &rows {
+        solver::learn(&mut states, &mut counts, row);
+    }
+
+    let candidates = solver::candidates(words, &states, &counts);
+
+    println!("{} candidates", candidates.len());
+    for word in candidates.iter().take(options.limit) {
+        println!("  {}", word.iter().collect::<String>());
+    }
+    if candidates.len() > options.limit {
+        println!("  ...");
+    }
+
+    if candidates.len() < 2 {
+        return;
+    }
+
+    let suggestions = match options.rank {
+        Rank::Frequency => solver::rank_by_letter_frequency(&candidates),
+        Rank::Information => solver::rank_by_expected_information(&candidates),
+    };
+
+    println!("Suggestions");
+    for (word, score) in suggestions.iter().take(options.limit) {
+        println!("  {} {score:.2}", word.iter().collect::<String>());
+    }
+}
@@ -0,0 +1,52 @@
+use yew::prelude::*;
+
+use sanuli::game::GuessAnalysis;
+use sanuli::strings::{self, Language, Text};
+
+#[derive(Properties, PartialEq)]
+pub struct AnalysisProps {
+    pub rows: Vec<GuessAnalysis>,
+    pub language: Language,
+    pub on_close: Callback<MouseEvent>,
+}
+
+/// The words left before and after each guess of a finished game, the weak guesses flagged.
+#[function_component(Analysis)]
+pub fn analysis(props: &AnalysisProps) -> Html {
+    let text = |text: Text| text.get(props.language);
+    let count = |count: usize| strings::format_count(props.language, count);
+
+    html! {
+        <div class="analysis">
+            <label class="label">{text(Text::AnalysisTitle)}</label>
+            <table class="analysis-table">
+                <tr>
+                    <th>{text(Text::AnalysisGuess)}</th>
+                    <th>{text(Text::AnalysisBefore)}</th>
+                    <th>{text(Text::AnalysisAfter)}</th>
+                </tr>
+                {
+                    props.rows.iter().map(|row| {
+                        html! {
+                            <tr class={classes!(row.is_weak().then_some("analysis-weak"))}>
+                                <td>{ &row.guess }</td>
+                                <td>{ count(row.before) }</td>
+                                <td>
+                                    { count(row.after) }
+                                    {
+                                        if row.is_weak() {
+                                            html! { <span class="analysis-note">{text(Text::AnalysisWeak)}</span> }
+                                        } else {
+                                            html! {}
+                                        }
+                                    }
+                                </td>
+                            </tr>
+                        }
+                    }).collect::<Html>()
+                }
+            </table>
+            <button class="replay-button" onmousedown={props.on_close.clone()}>{ "✕" }</button>
+        </div>
+    }
+}
@@ -0,0 +1,57 @@
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub message: String,
+    pub action: String,
+    pub on_action_cb: Callback<MouseEvent>,
+    // A second choice next to the action, like continuing where the player left off
+    #[prop_or_default]
+    pub secondary_action: Option<(String, Callback<MouseEvent>)>,
+    #[prop_or_default]
+    pub on_dismiss_cb: Option<Callback<MouseEvent>>,
+}
+
+#[function_component(Banner)]
+pub fn banner(props: &Props) -> Html {
+    let on_action_cb = props.on_action_cb.clone();
+    let onclick = Callback::from(move |e: MouseEvent| {
+        e.prevent_default();
+        on_action_cb.emit(e);
+    });
+
+    html! {
+        <div class="banner">
+            { &props.message }
+            <a class="link" href={"javascript:void(0)"} {onclick}>{ &props.action }</a>
+            {
+                if let Some((action, on_action_cb)) = props.secondary_action.clone() {
+                    let onclick = Callback::from(move |e: MouseEvent| {
+                        e.prevent_default();
+                        on_action_cb.emit(e);
+                    });
+
+                    html! {
+                        <a class="link" href={"javascript:void(0)"} {onclick}>{ action }</a>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+            {
+                if let Some(on_dismiss_cb) = props.on_dismiss_cb.clone() {
+                    let onclick = Callback::from(move |e: MouseEvent| {
+                        e.prevent_default();
+                        on_dismiss_cb.emit(e);
+                    });
+
+                    html! {
+                        <a class="banner-dismiss" href={"javascript:void(0)"} {onclick}>{ "×" }</a>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+        </div>
+    }
+}
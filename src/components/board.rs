@@ -1,6 +1,44 @@
+use gloo_timers::callback::Timeout;
 use yew::prelude::*;
 
-use crate::manager::TileState;
+use sanuli::game::{SharedGuesses, TileMarkers, DEFAULT_MAX_GUESSES, EMPTY};
+
+use crate::manager::{TileMarker, TileState};
+
+// Holding a finger on a tile this long marks it, the same as a right click does
+const LONG_PRESS_MS: u32 = 500;
+
+/// Boards up to the default height use the large fixed width layout, taller boards
+/// shrink their tiles to fit the container and scroll once they run out of room.
+pub fn board_class(max_guesses: usize) -> &'static str {
+    if max_guesses > DEFAULT_MAX_GUESSES {
+        "board-compact"
+    } else {
+        "board"
+    }
+}
+
+pub fn board_style(max_guesses: usize, word_length: usize) -> String {
+    let rows = format!("grid-template-rows: repeat({max_guesses}, 1fr);");
+
+    if max_guesses > DEFAULT_MAX_GUESSES {
+        // 2rem tiles for five letters and 0.4rem smaller for each additional letter
+        let tenths = 20usize
+            .saturating_sub(word_length.saturating_sub(5) * 4)
+            .max(10);
+        format!(
+            "{rows} --tile-size: {}rem; --tile-font-size: {}rem;",
+            tenths as f64 / 10.0,
+            tenths as f64 / 20.0
+        )
+    } else {
+        format!("{rows} height: calc({max_guesses} * var(--board-row-height));")
+    }
+}
+
+pub fn row_style(word_length: usize) -> String {
+    format!("grid-template-columns: repeat({word_length}, 1fr);")
+}
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
@@ -8,15 +46,32 @@ pub struct Props {
     pub is_reset: bool,
     pub is_hidden: bool,
 
-    pub guesses: Vec<Vec<(char, TileState)>>,
-    pub previous_guesses: Vec<Vec<(char, TileState)>>,
+    pub guesses: SharedGuesses,
+    pub new_information: Vec<Vec<bool>>,
+    pub highlight_new_information: bool,
+    pub previous_guesses: SharedGuesses,
     pub current_guess: usize,
     pub max_guesses: usize,
     pub word_length: usize,
+    pub show_glyphs: bool,
+    #[prop_or_default]
+    pub markers: TileMarkers,
+    // Called with the row and column of a tile the player marks, boards without it can't be marked
+    #[prop_or_default]
+    pub on_mark: Option<Callback<(usize, usize)>>,
 }
 
 #[function_component(Board)]
 pub fn board(props: &Props) -> Html {
+    // The rows get the same callback on every render, so that typing still re-renders only one row
+    let on_mark = use_mut_ref(|| props.on_mark.clone());
+    *on_mark.borrow_mut() = props.on_mark.clone();
+    let on_mark_tile = use_callback((), move |tile: (usize, usize), _| {
+        if let Some(on_mark) = on_mark.borrow().as_ref() {
+            on_mark.emit(tile);
+        }
+    });
+
     html! {
         <>
             {
@@ -24,8 +79,10 @@ pub fn board(props: &Props) -> Html {
                     html! {
                         <PreviousBoard
                             guesses={props.previous_guesses.clone()}
+                            is_hidden={props.is_hidden}
                             max_guesses={props.max_guesses}
                             word_length={props.word_length}
+                            show_glyphs={props.show_glyphs}
                         />
                     }
                 } else {
@@ -35,36 +92,31 @@ pub fn board(props: &Props) -> Html {
             <div class={classes!(
                 props.is_reset.then(|| "slide-in"),
                 props.is_reset.then(|| format!("slide-in-{}", props.previous_guesses.len())),
-                format!("board-{}", props.max_guesses))}>{
+                board_class(props.max_guesses))}
+                style={board_style(props.max_guesses, props.word_length)}>{
                     props.guesses.iter().enumerate().map(|(row, guess)| {
-                        let is_current_row = row == props.current_guess && props.is_guessing;
+                        let new_information = if props.highlight_new_information && !props.is_hidden {
+                            props.new_information.get(row).cloned().unwrap_or_default()
+                        } else {
+                            Vec::new()
+                        };
+
+                        let markers = (0..props.word_length)
+                            .map(|column| props.markers.get(&(row, column)).copied())
+                            .collect::<Vec<_>>();
 
                         html! {
-                            <div class={format!("row-{}", props.word_length)}>
-                                {
-                                    (0..props.word_length).map(|tile_index| {
-                                        let (character, tile_state) = guess
-                                            .get(tile_index)
-                                            .unwrap_or(&(' ', TileState::Unknown));
-
-                                        html! {
-                                            <div class={classes!(
-                                                "tile",
-                                                tile_state.to_string(),
-                                                is_current_row.then(|| Some("current"))
-                                            )}>
-                                                {
-                                                    if props.is_hidden {
-                                                        ' '
-                                                    } else {
-                                                        *character
-                                                    }
-                                                }
-                                            </div>
-                                        }
-                                    }).collect::<Html>()
-                                }
-                            </div>
+                            <BoardRow
+                                guess={guess.clone()}
+                                new_information={new_information}
+                                is_current={row == props.current_guess && props.is_guessing}
+                                is_hidden={props.is_hidden}
+                                word_length={props.word_length}
+                                show_glyphs={props.show_glyphs}
+                                row={row}
+                                markers={markers}
+                                on_mark={props.on_mark.as_ref().map(|_| on_mark_tile.clone())}
+                            />
                         }
                     }).collect::<Html>()
                 }
@@ -73,34 +125,158 @@ pub fn board(props: &Props) -> Html {
     }
 }
 
+#[derive(Properties, PartialEq)]
+pub struct BoardRowProps {
+    pub guess: Vec<(char, TileState)>,
+    // Tiles to highlight, empty when nothing is highlighted
+    pub new_information: Vec<bool>,
+    pub is_current: bool,
+    pub is_hidden: bool,
+    pub word_length: usize,
+    pub show_glyphs: bool,
+    #[prop_or_default]
+    pub row: usize,
+    // Marker of each tile, empty when nothing is marked
+    #[prop_or_default]
+    pub markers: Vec<Option<TileMarker>>,
+    #[prop_or_default]
+    pub on_mark: Option<Callback<(usize, usize)>>,
+}
+
+// A row of its own so that typing re-renders only the row whose props changed
+#[function_component(BoardRow)]
+pub fn board_row(props: &BoardRowProps) -> Html {
+    // One press at a time, so the row shares the timer of its tiles
+    let press_timeout = use_mut_ref(|| None::<Timeout>);
+    let is_touch = use_mut_ref(|| false);
+
+    html! {
+        <div class="row" style={row_style(props.word_length)}>
+            {
+                (0..props.word_length).map(|tile_index| {
+                    let (character, tile_state) = props.guess
+                        .get(tile_index)
+                        .unwrap_or(&(EMPTY, TileState::Unknown));
+                    let is_new_information = props.new_information
+                        .get(tile_index)
+                        .copied()
+                        .unwrap_or(false);
+
+                    let glyph = if props.show_glyphs && !props.is_hidden {
+                        tile_state.glyph()
+                    } else {
+                        None
+                    };
+                    let marker = props.markers
+                        .get(tile_index)
+                        .copied()
+                        .flatten()
+                        .filter(|_| !props.is_hidden);
+
+                    let tile = (props.row, tile_index);
+                    let (onpointerdown, onpointerup, oncontextmenu) = match props.on_mark.clone() {
+                        Some(on_mark) => {
+                            let onpointerdown = {
+                                let on_mark = on_mark.clone();
+                                let press_timeout = press_timeout.clone();
+                                let is_touch = is_touch.clone();
+                                Callback::from(move |e: PointerEvent| {
+                                    // A mouse marks with the right button instead
+                                    *is_touch.borrow_mut() = e.pointer_type() != "mouse";
+                                    if *is_touch.borrow() {
+                                        let on_mark = on_mark.clone();
+                                        *press_timeout.borrow_mut() = Some(Timeout::new(LONG_PRESS_MS, move || {
+                                            on_mark.emit(tile);
+                                        }));
+                                    }
+                                })
+                            };
+                            // Dropping the timeout cancels it, letting go early or sliding away isn't a long press
+                            let onpointerup = {
+                                let press_timeout = press_timeout.clone();
+                                Callback::from(move |_: PointerEvent| {
+                                    press_timeout.borrow_mut().take();
+                                })
+                            };
+                            // Some phones open the context menu on a long press, it was already handled by the timer
+                            let oncontextmenu = {
+                                let is_touch = is_touch.clone();
+                                Callback::from(move |e: MouseEvent| {
+                                    e.prevent_default();
+                                    if !*is_touch.borrow() {
+                                        on_mark.emit(tile);
+                                    }
+                                })
+                            };
+                            (Some(onpointerdown), Some(onpointerup), Some(oncontextmenu))
+                        }
+                        None => (None, None, None),
+                    };
+
+                    html! {
+                        <div class={classes!(
+                            "tile",
+                            tile_state.to_string(),
+                            props.is_current.then(|| Some("current")),
+                            is_new_information.then_some("new-information")
+                        )}
+                            onpointerdown={onpointerdown}
+                            onpointerup={onpointerup.clone()}
+                            onpointerleave={onpointerup.clone()}
+                            onpointercancel={onpointerup}
+                            oncontextmenu={oncontextmenu}>
+                            {
+                                if props.is_hidden {
+                                    ' '
+                                } else {
+                                    *character
+                                }
+                            }
+                            {
+                                match glyph {
+                                    Some(glyph) => html! { <span class="glyph">{ glyph }</span> },
+                                    None => html! {},
+                                }
+                            }
+                            {
+                                match marker {
+                                    Some(TileMarker::Star) => html! { <span class="marker star">{ "★" }</span> },
+                                    Some(TileMarker::Strike) => html! { <span class="marker strike" /> },
+                                    None => html! {},
+                                }
+                            }
+                        </div>
+                    }
+                }).collect::<Html>()
+            }
+        </div>
+    }
+}
+
 #[derive(Properties, PartialEq)]
 pub struct PreviousBoardProps {
-    pub guesses: Vec<Vec<(char, TileState)>>,
+    pub is_hidden: bool,
+    pub guesses: SharedGuesses,
     pub max_guesses: usize,
     pub word_length: usize,
+    pub show_glyphs: bool,
 }
 
 #[function_component(PreviousBoard)]
 pub fn previous_board(props: &PreviousBoardProps) -> Html {
     html! {
-        <div class={classes!("slide-out", format!("slide-out-{}", props.guesses.len()), format!("board-{}", props.max_guesses))}>
+        <div class={classes!("slide-out", format!("slide-out-{}", props.guesses.len()), board_class(props.max_guesses))}
+            style={board_style(props.max_guesses, props.word_length)}>
             { props.guesses.iter().map(|guess| {
                 html! {
-                    <div class={format!("row-{}", props.word_length)}>
-                        {
-                            (0..props.word_length).map(|tile_index| {
-                                let (character, tile_state) = guess
-                                    .get(tile_index)
-                                    .unwrap_or(&(' ', TileState::Unknown));
-
-                                html! {
-                                    <div class={classes!("tile", tile_state.to_string())}>
-                                        { character }
-                                    </div>
-                                }
-                            }).collect::<Html>()
-                        }
-                    </div>
+                    <BoardRow
+                        guess={guess.clone()}
+                        new_information={Vec::new()}
+                        is_current={false}
+                        is_hidden={props.is_hidden}
+                        word_length={props.word_length}
+                        show_glyphs={props.show_glyphs}
+                    />
                 }
             }).collect::<Html>() }
         </div>
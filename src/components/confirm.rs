@@ -0,0 +1,70 @@
+use gloo_timers::callback::Timeout;
+use yew::prelude::*;
+
+use sanuli::strings::{Language, Text};
+
+// How long the control waits for the second press before reverting
+const CONFIRM_TIMEOUT_MS: u32 = 3000;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ConfirmLinkProps {
+    pub label: String,
+    pub language: Language,
+    pub onconfirm: Callback<()>,
+}
+
+/// A link that has to be pressed twice within a short window before it
+/// emits `onconfirm`. Intended for destructive actions like resetting a game.
+#[function_component(ConfirmLink)]
+pub fn confirm_link(props: &ConfirmLinkProps) -> Html {
+    let is_armed = use_state(|| false);
+    let timeout = use_mut_ref(|| None::<Timeout>);
+
+    let onclick = {
+        let is_armed = is_armed.clone();
+        let timeout = timeout.clone();
+        let onconfirm = props.onconfirm.clone();
+
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+
+            // Dropping the timeout cancels it
+            timeout.borrow_mut().take();
+
+            if *is_armed {
+                is_armed.set(false);
+                onconfirm.emit(());
+            } else {
+                is_armed.set(true);
+
+                let is_armed = is_armed.clone();
+                *timeout.borrow_mut() = Some(Timeout::new(CONFIRM_TIMEOUT_MS, move || {
+                    is_armed.set(false);
+                }));
+            }
+        })
+    };
+
+    // Clicking anywhere else moves the focus away and cancels the confirmation
+    let onblur = {
+        let is_armed = is_armed.clone();
+        let timeout = timeout.clone();
+
+        Callback::from(move |_: FocusEvent| {
+            timeout.borrow_mut().take();
+            is_armed.set(false);
+        })
+    };
+
+    html! {
+        <a class="link" href={"javascript:void(0)"} tabindex="0" {onclick} {onblur}>
+            {
+                if *is_armed {
+                    Text::AreYouSure.get(props.language).to_owned()
+                } else {
+                    props.label.clone()
+                }
+            }
+        </a>
+    }
+}
@@ -0,0 +1,70 @@
+use std::rc::Rc;
+
+use gloo_timers::callback::Interval;
+use yew::prelude::*;
+
+use sanuli::demo;
+use sanuli::strings::{Language, Text};
+
+use crate::components::board::BoardRow;
+
+// Time between typing two letters of the demo game
+const DEMO_STEP_MS: u32 = 400;
+// Steps the finished demo board stays up before it is played again
+const DEMO_PAUSE_STEPS: usize = 8;
+
+struct DemoStep(usize);
+
+impl Reducible for DemoStep {
+    type Action = ();
+
+    fn reduce(self: Rc<Self>, _: ()) -> Rc<Self> {
+        Rc::new(DemoStep((self.0 + 1) % (demo::steps() + DEMO_PAUSE_STEPS)))
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct DemoProps {
+    pub language: Language,
+    pub on_dismiss: Callback<MouseEvent>,
+}
+
+/// A miniature board playing the scripted demo game over and over.
+#[function_component(Demo)]
+pub fn demo(props: &DemoProps) -> Html {
+    let step = use_reducer(|| DemoStep(0));
+
+    {
+        let dispatcher = step.dispatcher();
+        use_effect_with((), move |_| {
+            let interval = Interval::new(DEMO_STEP_MS, move || dispatcher.dispatch(()));
+            move || drop(interval)
+        });
+    }
+
+    let word_length = demo::word_length();
+
+    html! {
+        <div class="demo-overlay">
+            <div class="demo-board">
+                {
+                    demo::board(step.0).into_iter().map(|guess| {
+                        html! {
+                            <BoardRow
+                                guess={guess}
+                                new_information={Vec::new()}
+                                is_current={false}
+                                is_hidden={false}
+                                word_length={word_length}
+                                show_glyphs={false}
+                            />
+                        }
+                    }).collect::<Html>()
+                }
+            </div>
+            <button class="demo-button" onmousedown={props.on_dismiss.clone()}>
+                { Text::PlayYourself.get(props.language) }
+            </button>
+        </div>
+    }
+}
@@ -1,10 +1,15 @@
 use yew::prelude::*;
 
+use sanuli::strings::{Language, Text};
+
 #[derive(Properties, Clone, PartialEq)]
 pub struct Props {
     pub on_toggle_menu_cb: Callback<MouseEvent>,
     pub on_toggle_help_cb: Callback<MouseEvent>,
     pub title: String,
+    pub is_daily_pending: bool,
+    pub is_hint_assisted: bool,
+    pub language: Language,
 }
 
 #[function_component(Header)]
@@ -28,8 +33,26 @@ pub fn header(props: &Props) -> Html {
     html! {
         <header>
             <nav onclick={onclick_help} class="title-icon">{"?"}</nav>
-                <h1 class="title">{&props.title}</h1>
-            <nav onclick={onclick_menu} class="title-icon">{"≡"}</nav>
+                <h1 class="title">
+                    {&props.title}
+                    {
+                        if props.is_hint_assisted {
+                            html! { <span class="hint-indicator">{Text::HintsUsed.get(props.language)}</span> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </h1>
+            <nav onclick={onclick_menu} class="title-icon">
+                {"≡"}
+                {
+                    if props.is_daily_pending {
+                        html! { <span class="badge" /> }
+                    } else {
+                        html! {}
+                    }
+                }
+            </nav>
         </header>
     }
 }
@@ -1,32 +1,93 @@
 use std::collections::HashMap;
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{AddEventListenerOptions, TouchEvent};
 use yew::prelude::*;
 
-use crate::manager::{GameMode, KeyState, TileState};
+use sanuli::game;
+use sanuli::strings::{self, Language, Text};
+
+use crate::manager::{GameMode, KeyState, KeyboardLayout, TileState, WordLanguage, WordList};
 use crate::Msg;
 
 use crate::components::message::Message;
+use crate::components::starter_words::StarterWords;
+use crate::components::unused_letters::UnusedLetters;
+
+// Half-key spacers before and after each QWERTY row, staggering the rows like a physical keyboard
+const QWERTY_SPACERS: [(usize, usize); 3] = [(0, 0), (1, 0), (3, 2)];
+// Every letter in alphabetical order, the ABC layout picks the letters of the word language from it
+const ALPHABETICAL_ORDER: [char; 29] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S',
+    'T', 'U', 'V', 'W', 'X', 'Y', 'Z', 'Å', 'Ä', 'Ö',
+];
+const ALPHABETICAL_ROWS: usize = 3;
+const COMPACT_ROWS: usize = 2;
+
+// The letters of the word language on the rows of the layout. The rows can be of different lengths,
+// backspace always ends the first row and the submit button the last one.
+fn keyboard_rows(layout: KeyboardLayout, word_language: WordLanguage) -> Vec<Vec<char>> {
+    let letters = game::alphabet(word_language).collect::<Vec<_>>();
+
+    match layout {
+        KeyboardLayout::Qwerty => game::keyboard_layout(word_language)
+            .iter()
+            .map(|row| row.to_vec())
+            .collect(),
+        KeyboardLayout::Alphabetical => {
+            let letters = ALPHABETICAL_ORDER
+                .iter()
+                .filter(|letter| letters.contains(letter))
+                .copied()
+                .collect::<Vec<_>>();
+            split_rows(&letters, ALPHABETICAL_ROWS)
+        }
+        KeyboardLayout::Compact => split_rows(&letters, COMPACT_ROWS),
+    }
+}
 
-const KEYBOARD_0: [char; 10] = ['Q', 'W', 'E', 'R', 'T', 'Y', 'U', 'I', 'O', 'P'];
-const KEYBOARD_1: [char; 11] = ['A', 'S', 'D', 'F', 'G', 'H', 'J', 'K', 'L', 'Ö', 'Ä'];
-const KEYBOARD_2: [char; 7] = ['Z', 'X', 'C', 'V', 'B', 'N', 'M'];
+// As even rows as possible, the first ones get the extra letters
+fn split_rows(letters: &[char], rows: usize) -> Vec<Vec<char>> {
+    letters
+        .chunks(letters.len().div_ceil(rows))
+        .map(|row| row.to_vec())
+        .collect()
+}
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
     pub callback: Callback<Msg>,
 
     pub is_unknown: bool,
+    pub is_warning: bool,
+    // Words that still fit the guesses, shown in place of an empty message
+    pub remaining_candidates: Option<usize>,
     pub is_winner: bool,
     pub is_guessing: bool,
     pub is_hidden: bool,
+    pub is_spectating: bool,
+    pub can_share_progress: bool,
+    pub has_analysis: bool,
+    // Offered with the beginner help until the first letter of the word is typed
+    pub starter_words: &'static [&'static str],
+    pub show_unused_letters: bool,
+    pub show_glyphs: bool,
 
     pub is_emojis_copied: bool,
+    pub is_counts_copied: bool,
     pub is_link_copied: bool,
 
     pub game_mode: GameMode,
+    pub language: Language,
+    pub word_language: WordLanguage,
+    pub keyboard_layout: KeyboardLayout,
+    pub detailed_key_colors: bool,
 
     pub message: String,
     pub word: String,
+    pub word_source: Option<WordList>,
     pub last_guess: String,
+    pub board_results: Vec<(String, bool)>,
+    pub daily_percentile: Option<usize>,
 
     pub keyboard: HashMap<char, KeyState>,
 }
@@ -34,146 +95,176 @@ pub struct Props {
 #[function_component(Keyboard)]
 pub fn keyboard(props: &Props) -> Html {
     let callback = props.callback.clone();
-    let onbackspace = Callback::from(move |e: MouseEvent| {
-        e.prevent_default();
-        callback.emit(Msg::Backspace);
-    });
+    let onbackspace = Callback::from(move |_| callback.emit(Msg::Backspace));
+    let rows = keyboard_rows(props.keyboard_layout, props.word_language);
+    let last_row = rows.len() - 1;
+    let spacers = match props.keyboard_layout {
+        KeyboardLayout::Qwerty => &QWERTY_SPACERS[..],
+        _ => &[],
+    };
+
+    let submit_button = if props.is_guessing {
+        let callback = props.callback.clone();
+        let onpress = Callback::from(move |_| callback.emit(Msg::Guess));
+
+        html! {
+            <PressButton class={classes!("keyboard-button", "keyboard-button-submit")} onpress={onpress}>
+                { Text::GuessButton.get(props.language) }
+            </PressButton>
+        }
+    } else if matches!(props.game_mode, GameMode::DailyWord(_) | GameMode::Shared) {
+        let callback = props.callback.clone();
+        let onpress = Callback::from(move |_| callback.emit(Msg::ChangePreviousGameMode));
+
+        html! {
+            <PressButton class={classes!("keyboard-button", "keyboard-button-submit", "correct")} onpress={onpress}>
+                { Text::BackButton.get(props.language) }
+            </PressButton>
+        }
+    } else {
+        let callback = props.callback.clone();
+        let onpress = Callback::from(move |_| callback.emit(Msg::NextWord));
+
+        html! {
+            <PressButton class={classes!("keyboard-button", "keyboard-button-submit", "correct")} onpress={onpress}>
+                { Text::NewButton.get(props.language) }
+            </PressButton>
+        }
+    };
 
     html! {
         <div class="keyboard">
             {
                 if props.message.is_empty() && !props.is_hidden {
-                    html! {}
+                    match props.remaining_candidates {
+                        Some(count) => html! {
+                            <div class="message message-analysis">
+                                { strings::msg_remaining_candidates(props.language, count) }
+                            </div>
+                        },
+                        None => html! {},
+                    }
                 } else {
                     html! {
                         <Message
                             message={props.message.clone()}
                             is_unknown={props.is_unknown}
+                            is_warning={props.is_warning}
                             is_winner={props.is_winner}
                             is_guessing={props.is_guessing}
                             is_hidden={props.is_hidden}
+                            is_spectating={props.is_spectating}
+                            can_share_progress={props.can_share_progress}
+                            has_analysis={props.has_analysis}
                             is_emojis_copied={props.is_emojis_copied}
+                            is_counts_copied={props.is_counts_copied}
                             is_link_copied={props.is_link_copied}
                             last_guess={props.last_guess.clone()}
+                            board_results={props.board_results.clone()}
+                            daily_percentile={props.daily_percentile}
                             word={props.word.clone()}
+                            word_source={props.word_source}
                             game_mode={props.game_mode}
+                            language={props.language}
+                            word_language={props.word_language}
                             callback={props.callback.clone()}
                         />
                     }
                 }
             }
 
-            <div class="keyboard-row">
-                {
-                    KEYBOARD_0.iter().map(|key| {
-                        let callback = props.callback.clone();
-                        let onkeypress = Callback::from(move |e: MouseEvent| {
-                            e.prevent_default();
-                            callback.emit(Msg::KeyPress(*key));
-                        });
-
-                        let key_state = props.keyboard.get(key).unwrap_or(&KeyState::Single(TileState::Unknown));
-
-                        html! {
-                            <KeyboardButton character={*key} is_hidden={props.is_hidden} onkeypress={onkeypress} key_state={*key_state}/>
-                        }
-                    }).collect::<Html>()
-                }
-                <button data-nosnippet="" class={classes!("keyboard-button", "keyboard-button-backspace")} onmousedown={onbackspace}>
-                    { "⌫" }
-                </button>
-            </div>
-            <div class="keyboard-row">
-                <div class="spacer" />
-                {
-                    KEYBOARD_1.iter().map(|key| {
-                        let callback = props.callback.clone();
-                        let onkeypress = Callback::from(move |e: MouseEvent| {
-                            e.prevent_default();
-                            callback.emit(Msg::KeyPress(*key));
-                        });
-
-                        let key_state = props.keyboard.get(key).unwrap_or(&KeyState::Single(TileState::Unknown));
-
-                        html! {
-                            <KeyboardButton character={*key} is_hidden={props.is_hidden} onkeypress={onkeypress} key_state={*key_state}/>
-                        }
-                    }).collect::<Html>()
+            {
+                if !props.starter_words.is_empty() && !props.is_hidden {
+                    html! { <StarterWords callback={props.callback.clone()} words={props.starter_words} /> }
+                } else {
+                    html! {}
                 }
-            </div>
-            <div class="keyboard-row">
-                <div class="spacer" />
-                <div class="spacer" />
-                <div class="spacer" />
-                {
-                    KEYBOARD_2.iter().map(|key| {
-                        let callback = props.callback.clone();
-                        let onkeypress = Callback::from(move |e: MouseEvent| {
-                            e.prevent_default();
-                            callback.emit(Msg::KeyPress(*key));
-                        });
-
-                        let key_state = props.keyboard.get(key).unwrap_or(&KeyState::Single(TileState::Unknown));
-
-                        html! {
-                            <KeyboardButton character={*key} is_hidden={props.is_hidden} onkeypress={onkeypress} key_state={*key_state}/>
-                        }
-                    }).collect::<Html>()
+            }
+
+            {
+                if props.show_unused_letters && !props.is_hidden {
+                    html! { <UnusedLetters keyboard={props.keyboard.clone()} word_language={props.word_language} /> }
+                } else {
+                    html! {}
                 }
-                {
-                    if props.is_guessing {
-                        let callback = props.callback.clone();
-                        let onmousedown = Callback::from(move |e: MouseEvent| {
-                            e.prevent_default();
-                            callback.emit(Msg::Guess);
-                        });
-
-                        html! {
-                            <button data-nosnippet="" class={classes!("keyboard-button", "keyboard-button-submit")}
-                                onmousedown={onmousedown}>
-                                { "ARVAA" }
-                            </button>
-                        }
-                    } else if matches!(props.game_mode, GameMode::DailyWord(_) | GameMode::Shared) {
-                        let callback = props.callback.clone();
-                        let onmousedown = Callback::from(move |e: MouseEvent| {
-                            e.prevent_default();
-                            callback.emit(Msg::ChangePreviousGameMode);
-                        });
-
-                        html! {
-                            <button data-nosnippet="" class={classes!("keyboard-button", "keyboard-button-submit", "correct")}
-                                onmousedown={onmousedown}>
-                                { "TAKAISIN" }
-                            </button>
-                        }
-                    } else {
-                        let callback = props.callback.clone();
-                        let onmousedown = Callback::from(move |e: MouseEvent| {
-                            e.prevent_default();
-                            callback.emit(Msg::NextWord);
-                        });
-
-                        html! {
-                            <button data-nosnippet="" class={classes!("keyboard-button", "keyboard-button-submit", "correct")}
-                                onmousedown={onmousedown}>
-                                { "UUSI?" }
-                            </button>
-                        }
+            }
+
+            {
+                rows.iter().enumerate().map(|(index, row)| {
+                    let (leading, trailing) = spacers.get(index).copied().unwrap_or_default();
+
+                    html! {
+                        <div class="keyboard-row">
+                            { (0..leading).map(|_| html! { <div class="spacer" /> }).collect::<Html>() }
+                            {
+                                row.iter().map(|key| {
+                                    let key = *key;
+                                    let callback = props.callback.clone();
+                                    let onkeypress = Callback::from(move |_| callback.emit(Msg::KeyPress(key)));
+
+                                    let key_state = props.keyboard.get(&key).unwrap_or(&KeyState::Single(TileState::Unknown));
+
+                                    html! {
+                                        <KeyboardButton character={key} is_hidden={props.is_hidden} show_glyphs={props.show_glyphs}
+                                            is_detailed={props.detailed_key_colors} onkeypress={onkeypress} key_state={*key_state}/>
+                                    }
+                                }).collect::<Html>()
+                            }
+                            {
+                                if index == 0 {
+                                    html! {
+                                        <PressButton class={classes!("keyboard-button", "keyboard-button-backspace")} onpress={onbackspace.clone()}>
+                                            { "⌫" }
+                                        </PressButton>
+                                    }
+                                } else {
+                                    html! {}
+                                }
+                            }
+                            { if index == last_row { submit_button.clone() } else { html! {} } }
+                            { (0..trailing).map(|_| html! { <div class="spacer" /> }).collect::<Html>() }
+                        </div>
                     }
-                }
-                <div class="spacer" />
-                <div class="spacer" />
-            </div>
+                }).collect::<Html>()
+            }
         </div>
     }
 }
 
+// Clockwise order of the boards around the key, starting from the top. Two boards are side by
+// side and four in a 2x2 grid, so the segments match where the boards are on screen.
+fn segment_order(count: usize) -> Vec<usize> {
+    match count {
+        2 => vec![1, 0],
+        4 => vec![1, 3, 2, 0],
+        _ => (0..count).collect(),
+    }
+}
+
+fn segments_background(states: &[TileState]) -> String {
+    let degrees = 360.0 / states.len() as f64;
+    let stops = segment_order(states.len())
+        .into_iter()
+        .enumerate()
+        .map(|(segment, board)| {
+            let start = segment as f64 * degrees;
+            let end = start + degrees;
+            format!("var(--{state}) {start}deg, var(--{state}) {end}deg", state = states[board])
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("background: conic-gradient({stops});")
+}
+
 #[derive(Properties, PartialEq)]
 pub struct KeyboardButtonProps {
-    pub onkeypress: Callback<MouseEvent>,
+    pub onkeypress: Callback<()>,
     pub character: char,
     pub is_hidden: bool,
+    pub show_glyphs: bool,
+    // Keys of several boards are split by board, otherwise colored by the best state
+    pub is_detailed: bool,
     pub key_state: KeyState,
 }
 
@@ -182,26 +273,44 @@ pub fn keyboard_button(props: &KeyboardButtonProps) -> Html {
     if !props.is_hidden {
         match props.key_state {
             KeyState::Single(state) => {
+                let glyph = props.show_glyphs.then(|| state.glyph()).flatten();
+
+                html! {
+                    <PressButton class={classes!("keyboard-button", state.to_string())} onpress={props.onkeypress.clone()}>
+                        { props.character }
+                        {
+                            match glyph {
+                                Some(glyph) => html! { <span class="glyph">{ glyph }</span> },
+                                None => html! {},
+                            }
+                        }
+                    </PressButton>
+                }
+            }
+            KeyState::Multiple { .. } if !props.is_detailed => {
+                let best = props.key_state.best();
+
                 html! {
-                    <button data-nosnippet="" class={classes!("keyboard-button", state.to_string())} onmousedown={props.onkeypress.clone()}>
+                    <PressButton class={classes!("keyboard-button", best.to_string())} onpress={props.onkeypress.clone()}>
                         { props.character }
-                    </button>
+                        <span class="key-dots">
+                            {
+                                props.key_state.states().iter().map(|state| {
+                                    html! { <span class={classes!("key-dot", state.to_string())} /> }
+                                }).collect::<Html>()
+                            }
+                        </span>
+                    </PressButton>
                 }
             }
-            KeyState::Quadruple(states) => {
-                let background = format!(
-                    "background: conic-gradient(var(--{top_right}) 0deg, var(--{top_right}) 90deg, var(--{bottom_right}) 90deg, var(--{bottom_right}) 180deg, var(--{bottom_left}) 180deg, var(--{bottom_left}) 270deg, var(--{top_left}) 270deg, var(--{top_left}) 360deg);",
-                    top_left=states[0],
-                    top_right=states[1],
-                    bottom_left=states[2],
-                    bottom_right=states[3],
-                );
+            KeyState::Multiple { .. } => {
+                let background = segments_background(props.key_state.states());
 
                 html! {
-                    <button data-nosnippet="" class={"keyboard-button"} style={background.clone()}
-                        onmousedown={props.onkeypress.clone()}>
+                    <PressButton class={"keyboard-button"} style={background.clone()}
+                        onpress={props.onkeypress.clone()}>
                         { props.character }
-                    </button>
+                    </PressButton>
                 }
             }
         }
@@ -213,3 +322,66 @@ pub fn keyboard_button(props: &KeyboardButtonProps) -> Html {
         }
     }
 }
+
+#[derive(Properties, PartialEq)]
+pub struct PressButtonProps {
+    pub class: Classes,
+    #[prop_or_default]
+    pub style: Option<String>,
+    pub onpress: Callback<()>,
+    pub children: Html,
+}
+
+/// A key of the on-screen keyboard, pressed as soon as it is touched or clicked. Neither the
+/// touch nor the click is left to the browser, so tapping keys never scrolls or zooms the page.
+#[function_component(PressButton)]
+pub fn press_button(props: &PressButtonProps) -> Html {
+    let node = use_node_ref();
+    let onpress = use_mut_ref(|| props.onpress.clone());
+    *onpress.borrow_mut() = props.onpress.clone();
+
+    {
+        let onpress = onpress.clone();
+        use_effect_with(node.clone(), move |node| {
+            // Yew listens to touchstart passively, which makes the browser ignore prevent_default.
+            // Preventing it also stops the emulated mousedown, so a tap presses the key only once.
+            let listener = node.cast::<web_sys::Element>().map(|element| {
+                let listener = Closure::<dyn Fn(TouchEvent)>::wrap(Box::new(move |e: TouchEvent| {
+                    e.prevent_default();
+                    onpress.borrow().emit(());
+                }));
+
+                let options = AddEventListenerOptions::new();
+                options.set_passive(false);
+                element
+                    .add_event_listener_with_callback_and_add_event_listener_options(
+                        "touchstart",
+                        listener.as_ref().unchecked_ref(),
+                        &options,
+                    )
+                    .unwrap();
+
+                (element, listener)
+            });
+
+            move || {
+                if let Some((element, listener)) = listener {
+                    let _ = element
+                        .remove_event_listener_with_callback("touchstart", listener.as_ref().unchecked_ref());
+                }
+            }
+        });
+    }
+
+    let onmousedown = Callback::from(move |e: MouseEvent| {
+        e.prevent_default();
+        onpress.borrow().emit(());
+    });
+
+    html! {
+        <button data-nosnippet="" ref={node} class={props.class.clone()} style={props.style.clone()}
+            onmousedown={onmousedown}>
+            { props.children.clone() }
+        </button>
+    }
+}
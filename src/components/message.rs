@@ -1,33 +1,58 @@
 use yew::prelude::*;
 
-use crate::manager::GameMode;
+use sanuli::config::{self, Suggestion};
+use sanuli::strings::{self, Language, Text};
+
+use crate::components::confirm::ConfirmLink;
+use crate::manager::{GameMode, WordLanguage, WordList};
 use crate::Msg as GameMsg;
 
-const FORMS_LINK_TEMPLATE_ADD: &str = "https://docs.google.com/forms/d/e/1FAIpQLSfH8gs4sq-Ynn8iGOvlc99J_zOG2rJEC4m8V0kCgF_en3RHFQ/viewform?usp=pp_url&entry.461337706=Lis%C3%A4yst%C3%A4&entry.560255602=";
-const FORMS_LINK_TEMPLATE_DEL: &str = "https://docs.google.com/forms/d/e/1FAIpQLSfH8gs4sq-Ynn8iGOvlc99J_zOG2rJEC4m8V0kCgF_en3RHFQ/viewform?usp=pp_url&entry.461337706=Poistoa&entry.560255602=";
 const DICTIONARY_LINK_TEMPLATE: &str = "https://www.kielitoimistonsanakirja.fi/#/";
+const SWEDISH_DICTIONARY_LINK_TEMPLATE: &str = "https://svenska.se/tre/?sok=";
+
+pub fn dictionary_link(word_language: WordLanguage, word: &str) -> String {
+    let word = word.to_lowercase();
+    match word_language {
+        WordLanguage::Finnish => format!("{}{}?searchMode=all", DICTIONARY_LINK_TEMPLATE, word),
+        WordLanguage::Swedish => format!("{}{}", SWEDISH_DICTIONARY_LINK_TEMPLATE, word),
+    }
+}
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct MessageProps {
     pub message: String,
     pub is_unknown: bool,
+    // The message asks to confirm a guess going against known information
+    pub is_warning: bool,
     pub is_winner: bool,
     pub is_guessing: bool,
     pub is_hidden: bool,
+    pub is_spectating: bool,
+    pub can_share_progress: bool,
+    pub has_analysis: bool,
 
     pub is_emojis_copied: bool,
+    pub is_counts_copied: bool,
     pub is_link_copied: bool,
 
     pub word: String,
+    pub word_source: Option<WordList>,
     pub last_guess: String,
+    // The word of each board and whether it was found
+    pub board_results: Vec<(String, bool)>,
+    pub daily_percentile: Option<usize>,
     pub game_mode: GameMode,
+    pub language: Language,
+    pub word_language: WordLanguage,
     pub callback: Callback<GameMsg>,
 }
 
 #[function_component(Message)]
 pub fn message(props: &MessageProps) -> Html {
+    let text = |text: Text| text.get(props.language);
+
     html! {
-        <div class="message">
+        <div class={classes!("message", props.is_warning.then_some("message-warning"))}>
             { &props.message }
             <div class="message-small">{
                 if props.is_hidden {
@@ -37,45 +62,141 @@ pub fn message(props: &MessageProps) -> Html {
                         callback.emit(GameMsg::RevealHiddenTiles);
                     });
                     let callback = props.callback.clone();
-                    let reset_game = Callback::from(move |e: MouseEvent| {
-                        e.prevent_default();
+                    let reset_game = Callback::from(move |_| {
                         callback.emit(GameMsg::ResetGame);
                     });
+                    let callback = props.callback.clone();
+                    let share_emojis = Callback::from(move |e: MouseEvent| {
+                        e.prevent_default();
+                        callback.emit(GameMsg::ShareEmojis);
+                    });
+                    let callback = props.callback.clone();
+                    let share_emoji_counts = Callback::from(move |e: MouseEvent| {
+                        e.prevent_default();
+                        callback.emit(GameMsg::ShareEmojiCounts);
+                    });
 
                     html! {
                         <>
-                            <a class="link" href={"javascript:void(0)"} onclick={reset_game}>
-                                {"Kokeile ratkaista"}
-                            </a>
-                            {" | "}
+                            {
+                                match props.game_mode {
+                                    GameMode::Shared => html! {
+                                        <>
+                                            <ConfirmLink label={text(Text::TryToSolve)} language={props.language} onconfirm={reset_game} />
+                                            {" | "}
+                                        </>
+                                    },
+                                    // The result has no letters in it, so it can be shared before revealing
+                                    GameMode::DailyWord(_) => html! {
+                                        <>
+                                            <a class="link" href={"javascript:void(0)"} onclick={share_emojis}>
+                                                {
+                                                    if !props.is_emojis_copied {
+                                                        {text(Text::CopyResult)}
+                                                    } else {
+                                                        {text(Text::Copied)}
+                                                    }
+                                                }
+                                            </a>
+                                            {" | "}
+                                            <a class="link" href={"javascript:void(0)"} onclick={share_emoji_counts}>
+                                                {
+                                                    if !props.is_counts_copied {
+                                                        {text(Text::CopyCounts)}
+                                                    } else {
+                                                        {text(Text::Copied)}
+                                                    }
+                                                }
+                                            </a>
+                                            {" | "}
+                                        </>
+                                    },
+                                    _ => html! {},
+                                }
+                            }
                             <a class="link" href={"javascript:void(0)"} onclick={reveal_hidden_tiles}>
-                                {"Paljasta"}
+                                {text(Text::Reveal)}
                             </a>
                         </>
                     }
+                } else if props.is_spectating {
+                    html! {}
+                } else if !props.is_guessing && props.game_mode == GameMode::Quadruple {
+                    html! { <BoardResults
+                            board_results={props.board_results.clone()}
+                            language={props.language}
+                            word_language={props.word_language}
+                        /> }
                 } else if !props.is_guessing {
                     html! {
                         <SubMessage
                             is_winner={props.is_winner}
                             is_emojis_copied={props.is_emojis_copied}
+                            is_counts_copied={props.is_counts_copied}
                             is_link_copied={props.is_link_copied}
+                            has_analysis={props.has_analysis}
                             word={props.word.clone()}
+                            word_source={props.word_source}
                             game_mode={props.game_mode}
+                            language={props.language}
+                            word_language={props.word_language}
                             callback={props.callback.clone()}
                         />
                     }
-                } else if props.is_guessing && props.is_unknown {
+                } else {
+                    let callback = props.callback.clone();
+                    let share_progress = Callback::from(move |e: MouseEvent| {
+                        e.prevent_default();
+                        callback.emit(GameMsg::ShareProgress);
+                    });
                     let last_guess = props.last_guess.to_lowercase();
+
                     html! {
-                        <a class="link" href={format!("{}{}", FORMS_LINK_TEMPLATE_ADD, last_guess)}
-                            target="_blank">{ "Ehdota lisäystä?" }
-                        </a>
+                        <>
+                            {
+                                if props.is_unknown {
+                                    html! {
+                                        <a class="link" href={config::suggestion_link(Suggestion::Addition, &last_guess)}
+                                            target="_blank">{ text(Text::SuggestAddition) }
+                                        </a>
+                                    }
+                                } else {
+                                    html! {}
+                                }
+                            }
+                            { if props.is_unknown && props.can_share_progress { " | " } else { "" } }
+                            {
+                                if props.can_share_progress {
+                                    html! {
+                                        <a class="link" href={"javascript:void(0)"} onclick={share_progress}>
+                                            {
+                                                if !props.is_link_copied {
+                                                    {text(Text::ShareProgress)}
+                                                } else {
+                                                    {text(Text::Copied)}
+                                                }
+                                            }
+                                        </a>
+                                    }
+                                } else {
+                                    html! {}
+                                }
+                            }
+                        </>
                     }
-                } else {
-                    html! {}
                 }
             }
             </div>
+            {
+                match props.daily_percentile {
+                    Some(percentile) if matches!(props.game_mode, GameMode::DailyWord(_)) => html! {
+                        <div class="message-small">
+                            { strings::msg_daily_percentile(props.language, percentile) }
+                        </div>
+                    },
+                    _ => html! {},
+                }
+            }
         </div>
     }
 }
@@ -84,14 +205,20 @@ pub fn message(props: &MessageProps) -> Html {
 pub struct SubMessageProps {
     pub is_winner: bool,
     pub is_emojis_copied: bool,
+    pub is_counts_copied: bool,
     pub is_link_copied: bool,
+    pub has_analysis: bool,
     pub word: String,
+    pub word_source: Option<WordList>,
     pub game_mode: GameMode,
+    pub language: Language,
+    pub word_language: WordLanguage,
     pub callback: Callback<GameMsg>,
 }
 
 #[function_component(SubMessage)]
 fn sub_message(props: &SubMessageProps) -> Html {
+    let text = |text: Text| text.get(props.language);
     let word = props.word.to_lowercase();
 
     let callback = props.callback.clone();
@@ -100,30 +227,70 @@ fn sub_message(props: &SubMessageProps) -> Html {
         callback.emit(GameMsg::ShareEmojis);
     });
     let callback = props.callback.clone();
+    let share_emoji_counts = Callback::from(move |e: MouseEvent| {
+        e.prevent_default();
+        callback.emit(GameMsg::ShareEmojiCounts);
+    });
+    let callback = props.callback.clone();
     let share_link = Callback::from(move |e: MouseEvent| {
         e.prevent_default();
         callback.emit(GameMsg::ShareLink);
     });
-
-    if props.game_mode == GameMode::Quadruple {
-        return html!{}   
-    }
+    let callback = props.callback.clone();
+    let toggle_replay = Callback::from(move |e: MouseEvent| {
+        e.prevent_default();
+        callback.emit(GameMsg::ToggleReplay);
+    });
+    let callback = props.callback.clone();
+    let toggle_analysis = Callback::from(move |e: MouseEvent| {
+        e.prevent_default();
+        callback.emit(GameMsg::ToggleAnalysis);
+    });
 
     html! {
         <>
-            <a class="link" href={format!("{}{}?searchMode=all", DICTIONARY_LINK_TEMPLATE, word)}
-                target="_blank">{ "Sanakirja" }
+            {
+                match props.word_source {
+                    Some(word_list) => html! {
+                        <>
+                            <span class="word-source">{ strings::msg_word_source(props.language, word_list) }</span>
+                            {" | "}
+                        </>
+                    },
+                    None => html! {},
+                }
+            }
+            <a class="link" href={dictionary_link(props.word_language, &word)}
+                target="_blank">{ text(Text::Dictionary) }
             </a>
             {" | "}
             <a class="link" href={"javascript:void(0)"} onclick={share_link}>
                 {
                     if !props.is_link_copied {
-                        {"Kopioi linkki"}
+                        {text(Text::CopyLink)}
                     } else {
-                        {"Kopioitu!"}
+                        {text(Text::Copied)}
                     }
                 }
             </a>
+            {" | "}
+            <a class="link" href={"javascript:void(0)"} onclick={toggle_replay}>
+                {text(Text::Replay)}
+            </a>
+            {
+                if props.has_analysis {
+                    html! {
+                        <>
+                            {" | "}
+                            <a class="link" href={"javascript:void(0)"} onclick={toggle_analysis}>
+                                {text(Text::AnalysisTitle)}
+                            </a>
+                        </>
+                    }
+                } else {
+                    html! {}
+                }
+            }
             {
                 if matches!(props.game_mode, GameMode::DailyWord(_)) {
                     html! {
@@ -132,9 +299,19 @@ fn sub_message(props: &SubMessageProps) -> Html {
                             <a class="link" href={"javascript:void(0)"} onclick={share_emojis}>
                                 {
                                     if !props.is_emojis_copied {
-                                        {"Kopioi tulos"}
+                                        {text(Text::CopyResult)}
+                                    } else {
+                                        {text(Text::Copied)}
+                                    }
+                                }
+                            </a>
+                            {" | "}
+                            <a class="link" href={"javascript:void(0)"} onclick={share_emoji_counts}>
+                                {
+                                    if !props.is_counts_copied {
+                                        {text(Text::CopyCounts)}
                                     } else {
-                                        {"Kopioitu!"}
+                                        {text(Text::Copied)}
                                     }
                                 }
                             </a>
@@ -144,9 +321,29 @@ fn sub_message(props: &SubMessageProps) -> Html {
                     html! {
                         <>
                             {" | "}
-                            <a class="link" href={format!("{}{}", FORMS_LINK_TEMPLATE_DEL, word)}
-                                target="_blank">{ "Ehdota poistoa?" }
+                            <a class="link" href={config::suggestion_link(Suggestion::Removal, &word)}
+                                target="_blank">{ text(Text::SuggestRemoval) }
                             </a>
+                            {
+                                if props.game_mode == GameMode::Relay {
+                                    html! {
+                                        <>
+                                            {" | "}
+                                            <a class="link" href={"javascript:void(0)"} onclick={share_emojis}>
+                                                {
+                                                    if !props.is_emojis_copied {
+                                                        {text(Text::CopyChain)}
+                                                    } else {
+                                                        {text(Text::Copied)}
+                                                    }
+                                                }
+                                            </a>
+                                        </>
+                                    }
+                                } else {
+                                    html! {}
+                                }
+                            }
                         </>
                     }
                 } else {
@@ -156,3 +353,54 @@ fn sub_message(props: &SubMessageProps) -> Html {
         </>
     }
 }
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct BoardResultsProps {
+    pub board_results: Vec<(String, bool)>,
+    pub language: Language,
+    pub word_language: WordLanguage,
+}
+
+#[function_component(BoardResults)]
+fn board_results(props: &BoardResultsProps) -> Html {
+    let words = |is_found: bool| {
+        props
+            .board_results
+            .iter()
+            .filter(|(_, is_winner)| *is_winner == is_found)
+            .enumerate()
+            .map(|(index, (word, _))| {
+                html! {
+                    <>
+                        { if index > 0 { ", " } else { "" } }
+                        <a class="link" href={dictionary_link(props.word_language, word)}
+                            target="_blank">{ word }
+                        </a>
+                    </>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let missed = words(false);
+    let found = words(true);
+
+    html! {
+        <>
+            {
+                if !missed.is_empty() {
+                    html! { <div>{Text::WordsMissed.get(props.language)}{ missed }</div> }
+                } else {
+                    html! {}
+                }
+            }
+            {
+                if !found.is_empty() {
+                    html! { <div>{Text::WordsFound.get(props.language)}{ found }</div> }
+                } else {
+                    html! {}
+                }
+            }
+        </>
+    }
+}
@@ -1,5 +1,13 @@
 pub mod header;
 pub mod keyboard;
 pub mod message;
+pub mod analysis;
+pub mod banner;
 pub mod board;
-pub mod modal;
\ No newline at end of file
+pub mod confirm;
+pub mod demo;
+pub mod modal;
+pub mod replay;
+pub mod starter_words;
+pub mod unused_letters;
+pub mod word_entry;
\ No newline at end of file
@@ -1,12 +1,43 @@
-use chrono::Local;
+use chrono::{Local, NaiveDate};
+use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
-use crate::manager::{GameMode, Theme, WordList};
+use sanuli::config::{self, Suggestion, CHANGELOG_URL, VERSION};
+use sanuli::game::{self, STREAK_MILESTONES};
+use sanuli::strings::{self, Language, Text};
+
+use crate::components::confirm::ConfirmLink;
+use crate::components::message::dictionary_link;
+use crate::manager::{
+    BrokenStreak, EmojiSet, GameMode, KeyboardLayout, Settings, SuspendedGame, Theme, TileState,
+    WordCount, WordLanguage, WordList,
+};
 use crate::Msg;
 
-const FORMS_LINK_TEMPLATE_ADD: &str = "https://docs.google.com/forms/d/e/1FAIpQLSfH8gs4sq-Ynn8iGOvlc99J_zOG2rJEC4m8V0kCgF_en3RHFQ/viewform?usp=pp_url&entry.461337706=Lis%C3%A4yst%C3%A4&entry.560255602=";
-const CHANGELOG_URL: &str = "https://github.com/Cadiac/sanuli/blob/master/CHANGELOG.md";
-const VERSION: &str = "v1.14";
+// Pre-scripted guesses for the help example, the hidden word is "TALVI"
+const EXAMPLE_GUESSES: [[(char, TileState); 5]; 3] = [
+    [
+        ('K', TileState::Absent),
+        ('O', TileState::Absent),
+        ('I', TileState::Present),
+        ('R', TileState::Absent),
+        ('A', TileState::Present),
+    ],
+    [
+        ('L', TileState::Present),
+        ('A', TileState::Correct),
+        ('I', TileState::Present),
+        ('T', TileState::Present),
+        ('A', TileState::Absent),
+    ],
+    [
+        ('T', TileState::Correct),
+        ('A', TileState::Correct),
+        ('L', TileState::Correct),
+        ('V', TileState::Correct),
+        ('I', TileState::Correct),
+    ],
+];
 
 macro_rules! onmousedown {
     ( $cb:ident, $msg:expr ) => {{
@@ -21,19 +52,78 @@ macro_rules! onmousedown {
 #[derive(Properties, Clone, PartialEq)]
 pub struct HelpModalProps {
     pub theme: Theme,
+    pub game_mode: GameMode,
+    pub language: Language,
+    pub word_counts: Vec<WordCount>,
     pub callback: Callback<Msg>,
 }
 
+// Help for the game modes beyond the basic game, the current mode's section is shown first
+#[derive(Clone, Copy, PartialEq)]
+enum HelpSection {
+    Daily,
+    Relay,
+    Quadruple,
+    Hotseat,
+    Practice,
+    Shared,
+}
+
+const HELP_SECTIONS: [HelpSection; 6] = [
+    HelpSection::Daily,
+    HelpSection::Relay,
+    HelpSection::Quadruple,
+    HelpSection::Hotseat,
+    HelpSection::Practice,
+    HelpSection::Shared,
+];
+
+impl HelpSection {
+    fn of(game_mode: GameMode) -> Option<Self> {
+        match game_mode {
+            GameMode::Classic => None,
+            GameMode::DailyWord(_) => Some(HelpSection::Daily),
+            GameMode::Relay => Some(HelpSection::Relay),
+            GameMode::Quadruple => Some(HelpSection::Quadruple),
+            GameMode::Hotseat => Some(HelpSection::Hotseat),
+            GameMode::Practice(_) => Some(HelpSection::Practice),
+            GameMode::Shared => Some(HelpSection::Shared),
+        }
+    }
+
+    fn content(self, language: Language) -> Html {
+        let text = match self {
+            HelpSection::Daily => Text::HelpDaily,
+            HelpSection::Relay => Text::HelpRelay,
+            HelpSection::Quadruple => Text::HelpQuadruple,
+            HelpSection::Hotseat => Text::HelpHotseat,
+            HelpSection::Practice => Text::HelpPractice,
+            HelpSection::Shared => Text::HelpShared,
+        };
+
+        html! { <p>{ text.get(language) }</p> }
+    }
+}
+
 #[function_component(HelpModal)]
 pub fn help_modal(props: &HelpModalProps) -> Html {
     let callback = props.callback.clone();
     let toggle_help = onmousedown!(callback, Msg::ToggleHelp);
+    let language = props.language;
+    let text = |text: Text| text.get(language);
+
+    let current_section = HelpSection::of(props.game_mode);
+    let is_word_list_help_visible = !matches!(
+        props.game_mode,
+        GameMode::DailyWord(_) | GameMode::Shared | GameMode::Hotseat
+    );
 
     html! {
         <div class="modal">
             <span onmousedown={toggle_help} class="modal-close">{"✖"}</span>
-            <p>{"Arvaa kätketty "}<i>{"sanuli"}</i>{" kuudella yrityksellä."}</p>
-            <p>{"Jokaisen yrityksen jälkeen arvatut kirjaimet vaihtavat väriään."}</p>
+            { current_section.map(|section| section.content(language)).unwrap_or_default() }
+            <p>{text(Text::HelpGuessStart)}<i>{"sanuli"}</i>{text(Text::HelpGuessEnd)}</p>
+            <p>{text(Text::HelpColors)}</p>
 
             <div class="row-5 example">
                 <div class={classes!("tile", "correct")}>{"K"}</div>
@@ -47,81 +137,324 @@ pub fn help_modal(props: &HelpModalProps) -> Html {
                 {
                     html! {
                         if props.theme == Theme::Colorblind {
-                            <span class="present">{"Sininen"}</span>
+                            <span class="present">{text(Text::Blue)}</span>
                         } else {
-                            <span class="present">{"Keltainen"}</span>
+                            <span class="present">{text(Text::Yellow)}</span>
                         }
                     }
                 }
-                {": kirjain löytyy kätketystä sanasta, mutta on arvauksessa väärällä paikalla."}
+                {text(Text::HelpPresent)}
             </p>
             <p>
                 {
                     html! {
                         if props.theme == Theme::Colorblind {
-                            <span class="correct">{"Oranssi"}</span>
+                            <span class="correct">{text(Text::Orange)}</span>
                         } else {
-                            <span class="correct">{"Vihreä"}</span>
+                            <span class="correct">{text(Text::Green)}</span>
                         }
                     }
                 }
-                {": kirjain on arvauksessa oikealla paikalla."}
+                {text(Text::HelpCorrect)}
             </p>
-            <p><span class="absent">{"Harmaa"}</span>{": kirjain ei löydy sanasta."}</p>
+            <p><span class="absent">{text(Text::Gray)}</span>{text(Text::HelpAbsent)}</p>
 
-            <p>
-                {"Arvattaviin sanoihin käytetyn sanulistan vaikeusasteen voi valita asetuksista. Sanulistojen pohjana on käytetty
-                Kotimaisten kielten keskuksen (Kotus) julkaiseman "}
-                <a class="link" href="https://creativecommons.org/licenses/by/3.0/deed.fi" target="_blank">{"\"CC Nimeä 3.0 Muokkaamaton\""}</a>
-                {"-lisensoidun nykysuomen sanalistan sanoja."}
-            </p>
+            <HelpExample language={language} />
 
-            <p><b>{"Tavallinen"}</b>{" lista sisältää täydestä listasta poimitut yleisimmät sanat ilman harvinaisempia laina- ja murresanoja tai muita erikoisuuksia."}</p>
-            <p><b>{"Helppo"}</b>{" lista on tavallisesta vielä hieman helpotettu versio, jossa jäljellä ovat vain yleiset arkikielen sanat ilman vanhahtavia sanoja,
-                puhekieltä tai rumia sanuleja. Näin lista sopii kaikenikäisille. \"Helppo\" kuusikirjaimisten sanulien lista on kuitenkin vielä kesken."}</p>
-            <p><b>{"Vaikea"}</b>{" lista on täysi lista pelin hyväksymiä sanoja. Tälle listalle on myös lisätty jonkin verran käyttäjien uusia ehdotuksia,
-                puhekielisyyksiä, murresanoja sekä muita erikoisuuksia, eikä poistoja ole tehty kuin vain jos sanulit eivät selvästi ole oikeita sanoja."}</p>
-            <p>
-                {"Sanulit ovat yleensä perusmuodossa, mutta eivät välttämättä täysin pelkkää kirjakieltä. Yhdyssanojakin on seassa."}
-            </p>
-            <p>
-                {"Päivän sanulit tulevat omalta listaltaan, joka on jotain tavallisen ja vaikean listan väliltä. Sanuli on aina sama kaikille pelaajille tiettynä päivänä."}
-            </p>
+            { if is_word_list_help_visible { word_list_help(language, &props.word_counts) } else { html! {} } }
+            {
+                HELP_SECTIONS
+                    .iter()
+                    .filter(|section| Some(**section) != current_section)
+                    .map(|section| section.content(language))
+                    .collect::<Html>()
+            }
             <p>
-                {"Sanuliketjussa jos arvaat sanulin, on se suoraan ensimmäinen arvaus seuraavaan peliin. Näin joudut sopeutumaan vaihtuviin alkuarvauksiin, ja peli on hieman vaikeampi."}
+                {text(Text::HelpSuggestionsStart)}
+                <a class="link" href={config::suggestion_link(Suggestion::Addition, "")}>{text(Text::HelpSuggestionsLink)}</a>
+                {text(Text::HelpSuggestionsEnd)}
             </p>
+        </div>
+    }
+}
+
+fn word_list_help(language: Language, word_counts: &[WordCount]) -> Html {
+    let text = |text: Text| text.get(language);
+
+    html! {
+        <>
             <p>
-                {"Nelulissa ratkaiset samalla kertaa neljää eri sanulia samoilla arvauksilla. Tavoite on saada kaikki neljä sanulia ratkaistua yhdeksällä arvauksella."}
+                {text(Text::HelpWordListsStart)}
+                <a class="link" href="https://creativecommons.org/licenses/by/3.0/deed.fi" target="_blank">{text(Text::HelpWordListsLicense)}</a>
+                {text(Text::HelpWordListsEnd)}
             </p>
+
+            <p><b>{text(Text::ListCommon)}</b>{text(Text::HelpCommonList)}</p>
+            <p><b>{text(Text::ListEasy)}</b>{text(Text::HelpEasyList)}</p>
+            <p><b>{text(Text::ListFull)}</b>{text(Text::HelpFullList)}</p>
             <p>
-                {"Sanulistoja muokkailen aina välillä käyttäjien ehdotusten perusteella, ja voit jättää omat ehdotuksesi sanuleihin "}
-                <a class="link" href={FORMS_LINK_TEMPLATE_ADD}>{"täällä"}</a>
-                {". Kiitos kaikille ehdotuksia jättäneille ja sanulistojen kasaamisessa auttaneille henkilöille!"}
+                {text(Text::HelpWordForms)}
             </p>
+            <ul class="word-counts">
+                {
+                    word_counts.iter().map(|word_count| {
+                        html! {
+                            <li>
+                                {format!(
+                                    "{}: {}",
+                                    strings::word_list_name(language, word_count.word_list),
+                                    strings::msg_word_list_count(language, word_count.count, word_count.word_length),
+                                )}
+                            </li>
+                        }
+                    }).collect::<Html>()
+                }
+            </ul>
+        </>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct HelpExampleProps {
+    language: Language,
+}
+
+#[function_component(HelpExample)]
+fn help_example(props: &HelpExampleProps) -> Html {
+    let revealed = use_state(|| 0);
+
+    let onmousedown = {
+        let revealed = revealed.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            if *revealed < EXAMPLE_GUESSES.len() {
+                revealed.set(*revealed + 1);
+            } else {
+                revealed.set(0);
+            }
+        })
+    };
+
+    let description = match *revealed {
+        0 => Text::HelpExampleStart,
+        1 => Text::HelpExampleFirst,
+        2 => Text::HelpExampleSecond,
+        _ => Text::HelpExampleSolved,
+    };
+
+    html! {
+        <div class="help-example">
+            <div class="board-example">
+                {
+                    EXAMPLE_GUESSES.iter().enumerate().map(|(row, guess)| {
+                        html! {
+                            <div class="row-5 example">
+                                {
+                                    guess.iter().map(|(character, tile_state)| {
+                                        if row < *revealed {
+                                            html! {
+                                                <div class={classes!("tile", tile_state.to_string())}>{ character }</div>
+                                            }
+                                        } else {
+                                            html! { <div class="tile" /> }
+                                        }
+                                    }).collect::<Html>()
+                                }
+                            </div>
+                        }
+                    }).collect::<Html>()
+                }
+            </div>
+            <p>{ description.get(props.language) }</p>
+            <button class="select" {onmousedown}>
+                {
+                    if *revealed < EXAMPLE_GUESSES.len() {
+                        Text::NextGuess.get(props.language)
+                    } else {
+                        Text::StartOver.get(props.language)
+                    }
+                }
+            </button>
         </div>
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum MenuTab {
+    #[default]
+    Settings,
+    Statistics,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum MenuKey {
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+}
+
+// The sequence number makes repeated presses of the same key distinct props
+#[derive(Clone, Copy, PartialEq)]
+pub struct MenuKeyPress {
+    pub key: MenuKey,
+    pub sequence: usize,
+}
+
+// Settings that can be chosen with the arrow keys, in the order they are shown
+#[derive(Clone, Copy, PartialEq)]
+enum OptionGroup {
+    WordLanguage,
+    WordLength,
+    WordList,
+    GameMode,
+    Theme,
+}
+
+impl OptionGroup {
+    fn visible(is_hide_settings: bool) -> Vec<Self> {
+        let mut groups = if is_hide_settings {
+            vec![
+                OptionGroup::WordLanguage,
+                OptionGroup::GameMode,
+                OptionGroup::Theme,
+            ]
+        } else {
+            vec![
+                OptionGroup::WordLanguage,
+                OptionGroup::WordLength,
+                OptionGroup::WordList,
+                OptionGroup::GameMode,
+                OptionGroup::Theme,
+            ]
+        };
+        // With only one language there is nothing to choose from
+        if WordLanguage::available().len() < 2 {
+            groups.retain(|group| *group != OptionGroup::WordLanguage);
+        }
+        groups
+    }
+
+    fn messages(self, today: NaiveDate, word_language: WordLanguage) -> Vec<Msg> {
+        match self {
+            OptionGroup::WordLanguage => WordLanguage::available()
+                .iter()
+                .map(|word_language| Msg::ChangeWordLanguage(*word_language))
+                .collect(),
+            OptionGroup::WordLength => vec![Msg::ChangeWordLength(5), Msg::ChangeWordLength(6)],
+            OptionGroup::WordList => word_language
+                .word_lists()
+                .iter()
+                .map(|word_list| Msg::ChangeWordList(*word_list))
+                .collect(),
+            OptionGroup::GameMode => vec![
+                Msg::ChangeGameMode(GameMode::Classic),
+                Msg::ChangeGameMode(GameMode::Relay),
+                Msg::ChangeGameMode(GameMode::Quadruple),
+                Msg::ChangeGameMode(GameMode::Hotseat),
+                Msg::ChangeGameMode(GameMode::Practice(None)),
+                Msg::ChangeGameMode(GameMode::DailyWord(today)),
+            ],
+            OptionGroup::Theme => vec![
+                Msg::ChangeTheme(Theme::Dark),
+                Msg::ChangeTheme(Theme::Colorblind),
+            ],
+        }
+    }
+}
+
 #[derive(Properties, Clone, PartialEq)]
 pub struct MenuModalProps {
     pub callback: Callback<Msg>,
+    pub tab: MenuTab,
     pub word_length: usize,
     pub game_mode: GameMode,
     pub current_word_list: WordList,
+    pub is_mixed_list_available: bool,
+    // The browser offered to install the app, the offer can be taken from the menu
+    pub is_installable: bool,
+    // The latest drawn words, only given in debug mode
+    pub drawn_words: Option<String>,
+    pub word_language: WordLanguage,
     pub allow_profanities: bool,
+    pub weighted_word_selection: bool,
     pub theme: Theme,
+    pub language: Language,
+    pub keyboard_layout: KeyboardLayout,
+    pub detailed_key_colors: bool,
+    pub show_unused_letters: bool,
+    pub highlight_new_information: bool,
+    pub hide_typing_hints: bool,
+    pub warn_contradictions: bool,
+    pub show_candidates: bool,
+    pub beginner_help: bool,
+    pub hide_finished_word: bool,
+    pub auto_submit: bool,
+    pub emoji_set: EmojiSet,
+    pub share_solve_time: bool,
+    pub accessible_share: bool,
+    pub sharer_tag: Option<String>,
+    pub practice_letters: Vec<char>,
+    pub suspended_games: Vec<SuspendedGame>,
+    pub key_press: Option<MenuKeyPress>,
 
     pub max_streak: usize,
     pub total_played: usize,
     pub total_solved: usize,
+    pub guess_distribution: Vec<usize>,
+    pub best_milestone: usize,
+    pub daily_streak: usize,
+    pub max_daily_streak: usize,
+    pub daily_freezes: usize,
+    pub frozen_days: Vec<NaiveDate>,
+    pub broken_streaks: Vec<BrokenStreak>,
+    pub is_daily_pending: bool,
+    pub is_late_daily_available: bool,
 }
 
 #[function_component(MenuModal)]
 pub fn menu_modal(props: &MenuModalProps) -> Html {
     let callback = props.callback.clone();
-    let today = Local::now().naive_local().date();
     let toggle_menu = onmousedown!(callback, Msg::ToggleMenu);
 
+    let change_tab_settings = onmousedown!(callback, Msg::ChangeMenuTab(MenuTab::Settings));
+    let change_tab_statistics = onmousedown!(callback, Msg::ChangeMenuTab(MenuTab::Statistics));
+    let text = |text: Text| text.get(props.language);
+
+    html! {
+        <div class="modal">
+            <span onmousedown={toggle_menu} class="modal-close">{"✖"}</span>
+            <div class="tab-container">
+                <button class={classes!("tab", (props.tab == MenuTab::Settings).then(|| Some("tab-active")))}
+                    onmousedown={change_tab_settings}>
+                    {text(Text::Settings)}
+                </button>
+                <button class={classes!("tab", (props.tab == MenuTab::Statistics).then(|| Some("tab-active")))}
+                    onmousedown={change_tab_statistics}>
+                    {text(Text::Statistics)}
+                </button>
+            </div>
+            {
+                match props.tab {
+                    MenuTab::Settings => html! { <SettingsTab ..props.clone() /> },
+                    MenuTab::Statistics => html! { <StatisticsTab ..props.clone() /> },
+                }
+            }
+            <div class="version">
+                <a class="version" href={CHANGELOG_URL} target="_blank">{ VERSION }</a>
+            </div>
+        </div>
+    }
+}
+
+#[function_component(SettingsTab)]
+fn settings_tab(props: &MenuModalProps) -> Html {
+    let callback = props.callback.clone();
+    let today = Local::now().naive_local().date();
+
+    let change_word_list_mixed = onmousedown!(callback, Msg::ChangeWordList(WordList::Mixed));
+
     let change_word_length_5 = onmousedown!(callback, Msg::ChangeWordLength(5));
     let change_word_length_6 = onmousedown!(callback, Msg::ChangeWordLength(6));
 
@@ -131,118 +464,830 @@ pub fn menu_modal(props: &MenuModalProps) -> Html {
         onmousedown!(callback, Msg::ChangeGameMode(GameMode::DailyWord(today)));
     let change_game_mode_quadruple =
         onmousedown!(callback, Msg::ChangeGameMode(GameMode::Quadruple));
+    let change_game_mode_hotseat = onmousedown!(callback, Msg::ChangeGameMode(GameMode::Hotseat));
+    let change_game_mode_practice = onmousedown!(callback, Msg::ChangeGameMode(GameMode::Practice(None)));
+    let yesterday = today.pred_opt().unwrap_or(today);
+    let change_game_mode_late_daily =
+        onmousedown!(callback, Msg::ChangeGameMode(GameMode::DailyWord(yesterday)));
 
-    let change_word_list_easy = onmousedown!(callback, Msg::ChangeWordList(WordList::Easy));
-    let change_word_list_common = onmousedown!(callback, Msg::ChangeWordList(WordList::Common));
-    let change_word_list_full = onmousedown!(callback, Msg::ChangeWordList(WordList::Full));
+    let change_word_language_finnish =
+        onmousedown!(callback, Msg::ChangeWordLanguage(WordLanguage::Finnish));
+    let change_word_language_swedish =
+        onmousedown!(callback, Msg::ChangeWordLanguage(WordLanguage::Swedish));
 
     let change_allow_profanities_yes = onmousedown!(callback, Msg::ChangeAllowProfanities(true));
     let change_allow_profanities_no = onmousedown!(callback, Msg::ChangeAllowProfanities(false));
 
+    let change_weighted_word_selection_yes =
+        onmousedown!(callback, Msg::ChangeWeightedWordSelection(true));
+    let change_weighted_word_selection_no =
+        onmousedown!(callback, Msg::ChangeWeightedWordSelection(false));
+
     let change_theme_dark = onmousedown!(callback, Msg::ChangeTheme(Theme::Dark));
     let change_theme_colorblind = onmousedown!(callback, Msg::ChangeTheme(Theme::Colorblind));
 
-    let is_hide_settings = matches!(props.game_mode, GameMode::DailyWord(_) | GameMode::Shared);
+    let change_keyboard_layout_qwerty =
+        onmousedown!(callback, Msg::ChangeKeyboardLayout(KeyboardLayout::Qwerty));
+    let change_keyboard_layout_alphabetical =
+        onmousedown!(callback, Msg::ChangeKeyboardLayout(KeyboardLayout::Alphabetical));
+    let change_keyboard_layout_compact =
+        onmousedown!(callback, Msg::ChangeKeyboardLayout(KeyboardLayout::Compact));
+
+    let change_detailed_key_colors_yes =
+        onmousedown!(callback, Msg::ChangeDetailedKeyColors(true));
+    let change_detailed_key_colors_no =
+        onmousedown!(callback, Msg::ChangeDetailedKeyColors(false));
+
+    let change_show_unused_letters_yes =
+        onmousedown!(callback, Msg::ChangeShowUnusedLetters(true));
+    let change_show_unused_letters_no =
+        onmousedown!(callback, Msg::ChangeShowUnusedLetters(false));
+
+    let change_highlight_new_information_yes =
+        onmousedown!(callback, Msg::ChangeHighlightNewInformation(true));
+    let change_highlight_new_information_no =
+        onmousedown!(callback, Msg::ChangeHighlightNewInformation(false));
+
+    let change_hide_typing_hints_yes = onmousedown!(callback, Msg::ChangeHideTypingHints(true));
+    let change_hide_typing_hints_no = onmousedown!(callback, Msg::ChangeHideTypingHints(false));
+
+    let change_warn_contradictions_yes =
+        onmousedown!(callback, Msg::ChangeWarnContradictions(true));
+    let change_warn_contradictions_no =
+        onmousedown!(callback, Msg::ChangeWarnContradictions(false));
+
+    let change_show_candidates_yes = onmousedown!(callback, Msg::ChangeShowCandidates(true));
+    let change_show_candidates_no = onmousedown!(callback, Msg::ChangeShowCandidates(false));
+
+    let change_beginner_help_yes = onmousedown!(callback, Msg::ChangeBeginnerHelp(true));
+    let change_beginner_help_no = onmousedown!(callback, Msg::ChangeBeginnerHelp(false));
+
+    let change_hide_finished_word_yes = onmousedown!(callback, Msg::ChangeHideFinishedWord(true));
+    let change_hide_finished_word_no = onmousedown!(callback, Msg::ChangeHideFinishedWord(false));
+
+    let change_auto_submit_yes = onmousedown!(callback, Msg::ChangeAutoSubmit(true));
+    let change_auto_submit_no = onmousedown!(callback, Msg::ChangeAutoSubmit(false));
+
+    let change_emoji_set_party = onmousedown!(callback, Msg::ChangeEmojiSet(EmojiSet::Party));
+    let change_emoji_set_animals = onmousedown!(callback, Msg::ChangeEmojiSet(EmojiSet::Animals));
+    let change_emoji_set_hearts = onmousedown!(callback, Msg::ChangeEmojiSet(EmojiSet::Hearts));
+    let change_emoji_set_off = onmousedown!(callback, Msg::ChangeEmojiSet(EmojiSet::Off));
+
+    let change_share_solve_time_yes = onmousedown!(callback, Msg::ChangeShareSolveTime(true));
+    let change_share_solve_time_no = onmousedown!(callback, Msg::ChangeShareSolveTime(false));
+
+    let change_accessible_share_yes = onmousedown!(callback, Msg::ChangeAccessibleShare(true));
+    let change_accessible_share_no = onmousedown!(callback, Msg::ChangeAccessibleShare(false));
+
+    let enable_sharer_tag_yes = onmousedown!(callback, Msg::EnableSharerTag(true));
+    let enable_sharer_tag_no = onmousedown!(callback, Msg::EnableSharerTag(false));
+    let change_sharer_tag = {
+        let callback = callback.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            callback.emit(Msg::ChangeSharerTag(input.value()));
+        })
+    };
+    // Typing the tag shouldn't type into the game behind the menu
+    let onkeydown = Callback::from(|e: KeyboardEvent| e.stop_propagation());
+
+    let change_language_finnish = onmousedown!(callback, Msg::ChangeLanguage(Language::Finnish));
+    let change_language_english = onmousedown!(callback, Msg::ChangeLanguage(Language::English));
+
+    let language = props.language;
+    let text = |text: Text| text.get(language);
+
+    let new_game = {
+        let callback = callback.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            callback.emit(Msg::NewGame);
+        })
+    };
+
+    let clear_all_data = {
+        let callback = callback.clone();
+        Callback::from(move |_| callback.emit(Msg::ClearAllData))
+    };
+
+    let install_app = onmousedown!(callback, Msg::InstallApp);
+    let copy_drawn_words = onmousedown!(callback, Msg::CopyDrawnWords);
+
+    let reset_settings = {
+        let callback = callback.clone();
+        Callback::from(move |_| callback.emit(Msg::ResetSettings))
+    };
+
+    // A dot next to the label of each setting that isn't at its default
+    let defaults = Settings::default();
+    let changed = |is_changed: bool| {
+        if is_changed {
+            html! { <span class="changed" title={text(Text::ChangedFromDefault)} /> }
+        } else {
+            html! {}
+        }
+    };
+
+    let word_language = props.word_language;
+    let is_hide_settings = matches!(
+        props.game_mode,
+        GameMode::DailyWord(_) | GameMode::Shared | GameMode::Hotseat
+    );
+
+    let focus = use_state(|| None::<(OptionGroup, usize)>);
+    {
+        let focus = focus.clone();
+        let callback = callback.clone();
+        use_effect_with(props.key_press, move |key_press| {
+            let groups = OptionGroup::visible(is_hide_settings);
+            let last_option = |group: OptionGroup| group.messages(today, word_language).len() - 1;
+            let key = match key_press {
+                Some(key_press) => key_press.key,
+                None => return,
+            };
+
+            match (key, *focus) {
+                (MenuKey::Enter, Some((group, option))) => {
+                    if let Some(msg) = group.messages(today, word_language).into_iter().nth(option) {
+                        callback.emit(msg);
+                    }
+                }
+                (MenuKey::Enter, None) => {}
+                (_, None) => focus.set(Some((groups[0], 0))),
+                (MenuKey::Up | MenuKey::Down, Some((group, option))) => {
+                    let index = groups.iter().position(|g| *g == group).unwrap_or(0);
+                    let index = if key == MenuKey::Up {
+                        index.saturating_sub(1)
+                    } else {
+                        (index + 1).min(groups.len() - 1)
+                    };
+                    let group = groups[index];
+                    focus.set(Some((group, option.min(last_option(group)))));
+                }
+                (MenuKey::Left, Some((group, option))) => {
+                    focus.set(Some((group, option.saturating_sub(1))));
+                }
+                (MenuKey::Right, Some((group, option))) => {
+                    focus.set(Some((group, (option + 1).min(last_option(group)))));
+                }
+            }
+        });
+    }
+    let focused = |group: OptionGroup, option: usize| {
+        (*focus == Some((group, option))).then_some("select-focused")
+    };
 
     html! {
-        <div class="modal">
-            <span onmousedown={toggle_menu} class="modal-close">{"✖"}</span>
+        <>
+            {if WordLanguage::available().len() > 1 {
+                html! {
+                    <div>
+                        <label class="label">{text(Text::WordLanguageLabel)}{changed(word_language != defaults.word_language)}</label>
+                        <div class="select-container">
+                            <button class={classes!("select", (word_language == WordLanguage::Finnish).then_some("select-active"), focused(OptionGroup::WordLanguage, 0))}
+                                onmousedown={change_word_language_finnish}>
+                                {"Suomi"}
+                            </button>
+                            <button class={classes!("select", (word_language == WordLanguage::Swedish).then_some("select-active"), focused(OptionGroup::WordLanguage, 1))}
+                                onmousedown={change_word_language_swedish}>
+                                {"Svenska"}
+                            </button>
+                        </div>
+                    </div>
+                }
+            } else {
+                html! {}
+            }}
             {if !is_hide_settings {
                 html! {
                     <>
                         <div>
-                            <label class="label">{"Sanulien pituus:"}</label>
+                            <label class="label">{text(Text::WordLengthLabel)}{changed(props.word_length != defaults.word_length)}</label>
                             <div class="select-container">
-                                <button class={classes!("select", (props.word_length == 5).then(|| Some("select-active")))}
+                                <button class={classes!("select", (props.word_length == 5).then(|| Some("select-active")), focused(OptionGroup::WordLength, 0))}
                                     onmousedown={change_word_length_5}>
-                                    {"5 merkkiä"}
+                                    {strings::word_length(language, 5)}
                                 </button>
-                                <button class={classes!("select", (props.word_length == 6).then(|| Some("select-active")))}
+                                <button class={classes!("select", (props.word_length == 6).then(|| Some("select-active")), focused(OptionGroup::WordLength, 1))}
                                     onmousedown={change_word_length_6}>
-                                    {"6 merkkiä"}
+                                    {strings::word_length(language, 6)}
                                 </button>
                             </div>
                         </div>
                         <div>
-                            <label class="label">{"Sanulista:"}</label>
+                            <label class="label">{text(Text::WordListLabel)}{changed(props.current_word_list != defaults.word_list)}</label>
                             <div class="select-container">
-                                <button class={classes!("select", (props.current_word_list == WordList::Easy).then(|| Some("select-active")))}
-                                    onmousedown={change_word_list_easy}>
-                                    {"Helppo"}
-                                </button>
-                                <button class={classes!("select", (props.current_word_list == WordList::Common).then(|| Some("select-active")))}
-                                    onmousedown={change_word_list_common}>
-                                    {"Tavallinen"}
-                                </button>
-                                <button class={classes!("select", (props.current_word_list == WordList::Full).then(|| Some("select-active")))}
-                                    onmousedown={change_word_list_full}>
-                                    {"Vaikea"}
-                                </button>
+                                {props.word_language.word_lists().iter().enumerate().map(|(index, word_list)| {
+                                    let word_list = *word_list;
+                                    let change_word_list = onmousedown!(callback, Msg::ChangeWordList(word_list));
+
+                                    html! {
+                                        <button class={classes!("select", (props.current_word_list == word_list).then_some("select-active"), focused(OptionGroup::WordList, index))}
+                                            onmousedown={change_word_list}>
+                                            {strings::word_list_name(language, word_list)}
+                                        </button>
+                                    }
+                                }).collect::<Html>()}
+                                {
+                                    if props.is_mixed_list_available {
+                                        html! {
+                                            <button class={classes!("select", (props.current_word_list == WordList::Mixed).then_some("select-active"))}
+                                                onmousedown={change_word_list_mixed}>
+                                                {text(Text::ListMixed)}
+                                            </button>
+                                        }
+                                    } else {
+                                        html! {}
+                                    }
+                                }
                             </div>
                         </div>
                         <div>
-                            <label class="label">{"Rumat sanulit:"}</label>
+                            <label class="label">{text(Text::ProfanitiesLabel)}{changed(props.allow_profanities != defaults.allow_profanities)}</label>
                             <div class="select-container">
                                 <button class={classes!("select", (!props.allow_profanities).then(|| Some("select-active")))}
                                     onmousedown={change_allow_profanities_no}>
-                                    {"Ei"}
+                                    {text(Text::No)}
                                 </button>
                                 <button class={classes!("select", (props.allow_profanities).then(|| Some("select-active")))}
                                     onmousedown={change_allow_profanities_yes}>
-                                    {"Kyllä"}
+                                    {text(Text::Yes)}
                                 </button>
                             </div>
                         </div>
+                        {if props.current_word_list == WordList::Full {
+                            html! {
+                                <div>
+                                    <label class="label">{text(Text::WeightedSelectionLabel)}{changed(props.weighted_word_selection != defaults.weighted_word_selection)}</label>
+                                    <div class="select-container">
+                                        <button class={classes!("select", (!props.weighted_word_selection).then_some("select-active"))}
+                                            onmousedown={change_weighted_word_selection_no}>
+                                            {text(Text::No)}
+                                        </button>
+                                        <button class={classes!("select", props.weighted_word_selection.then_some("select-active"))}
+                                            onmousedown={change_weighted_word_selection_yes}>
+                                            {text(Text::Yes)}
+                                        </button>
+                                    </div>
+                                </div>
+                            }
+                        } else {
+                            html! {}
+                        }}
                     </>
                 }
             } else {
                 html! {}
             }}
             <div>
-                <label class="label">{"Pelimuoto:"}</label>
+                <label class="label">{text(Text::GameModeLabel)}</label>
                 <div class="select-container">
-                    <button class={classes!("select", (props.game_mode == GameMode::Classic).then(|| Some("select-active")))}
+                    <button class={classes!("select", (props.game_mode == GameMode::Classic).then(|| Some("select-active")), focused(OptionGroup::GameMode, 0))}
                         onmousedown={change_game_mode_classic}>
-                        {"Peruspeli"}
+                        {text(Text::ModeClassic)}
                     </button>
-                    <button class={classes!("select", (props.game_mode == GameMode::Relay).then(|| Some("select-active")))}
+                    <button class={classes!("select", (props.game_mode == GameMode::Relay).then(|| Some("select-active")), focused(OptionGroup::GameMode, 1))}
                         onmousedown={change_game_mode_relay}>
-                        {"Sanuliketju"}
+                        {text(Text::ModeRelay)}
                     </button>
-                    <button class={classes!("select", (props.game_mode == GameMode::Quadruple).then(|| Some("select-active")))}
+                    <button class={classes!("select", (props.game_mode == GameMode::Quadruple).then(|| Some("select-active")), focused(OptionGroup::GameMode, 2))}
                         onmousedown={change_game_mode_quadruple}>
-                        {"Neluli"}
+                        {text(Text::ModeQuadruple)}
                     </button>
-                    <button class={classes!("select", matches!(props.game_mode, GameMode::DailyWord(_)).then(|| Some("select-active")))}
+                    <button class={classes!("select", (props.game_mode == GameMode::Hotseat).then_some("select-active"), focused(OptionGroup::GameMode, 3))}
+                        onmousedown={change_game_mode_hotseat}>
+                        {text(Text::ModeHotseat)}
+                    </button>
+                    <button class={classes!("select", matches!(props.game_mode, GameMode::Practice(_)).then_some("select-active"), focused(OptionGroup::GameMode, 4))}
+                        onmousedown={change_game_mode_practice}>
+                        {text(Text::ModePractice)}
+                    </button>
+                    <button class={classes!("select", matches!(props.game_mode, GameMode::DailyWord(_)).then(|| Some("select-active")), focused(OptionGroup::GameMode, 5))}
                         onclick={change_game_mode_daily}>
-                        {"Päivän sanuli"}
+                        {text(Text::ModeDaily)}
+                        {
+                            if props.is_daily_pending {
+                                html! { <span class="badge" /> }
+                            } else {
+                                html! {}
+                            }
+                        }
                     </button>
                 </div>
+                {
+                    if matches!(props.game_mode, GameMode::DailyWord(_) | GameMode::Shared) {
+                        html! {}
+                    } else {
+                        html! {
+                            <a class="link" href={"javascript:void(0)"} onclick={new_game}>
+                                {text(Text::NewGame)}
+                            </a>
+                        }
+                    }
+                }
+                {
+                    if props.is_late_daily_available && props.game_mode != GameMode::DailyWord(yesterday) {
+                        html! {
+                            <a class="link" href={"javascript:void(0)"} onmousedown={change_game_mode_late_daily}>
+                                {text(Text::YesterdaysWord)}
+                            </a>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
             </div>
+            {
+                if let GameMode::Practice(current) = props.game_mode {
+                    html! { <PracticeLetters callback={callback.clone()} current={current} letters={props.practice_letters.clone()} language={language} word_language={word_language} /> }
+                } else {
+                    html! {}
+                }
+            }
+            {
+                if props.suspended_games.is_empty() {
+                    html! {}
+                } else {
+                    html! { <SuspendedGames callback={callback.clone()} games={props.suspended_games.clone()} language={language} /> }
+                }
+            }
             <div>
-                <label class="label">{"Omat tilastosi:"}</label>
-                <ul>
-                    <li class="statistics">{format!("Pisin putki: {}", props.max_streak)}</li>
-                    <li class="statistics">{format!("Pelatut sanulit: {}", props.total_played)}</li>
-                    <li class="statistics">{format!("Ratkaistut sanulit: {}", props.total_solved)}</li>
-                </ul>
-            </div>
-            <div>
-                <label class="label">{"Teema:"}</label>
+                <label class="label">{text(Text::ThemeLabel)}{changed(props.theme != defaults.theme)}</label>
                 <div class="select-container">
-                    <button class={classes!("select", (props.theme == Theme::Dark).then(|| Some("select-active")))}
+                    <button class={classes!("select", (props.theme == Theme::Dark).then(|| Some("select-active")), focused(OptionGroup::Theme, 0))}
                         onmousedown={change_theme_dark}>
-                        {"Oletus"}
+                        {text(Text::ThemeDark)}
                     </button>
-                    <button class={classes!("select", (props.theme == Theme::Colorblind).then(|| Some("select-active")))}
+                    <button class={classes!("select", (props.theme == Theme::Colorblind).then(|| Some("select-active")), focused(OptionGroup::Theme, 1))}
                         onmousedown={change_theme_colorblind}>
-                        {"Värisokeille"}
+                        {text(Text::ThemeColorblind)}
                     </button>
                 </div>
             </div>
-            <div class="version">
-                <a class="version" href={CHANGELOG_URL} target="_blank">{ VERSION }</a>
+            <div>
+                <label class="label">{text(Text::KeyboardLayoutLabel)}{changed(props.keyboard_layout != defaults.keyboard_layout)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (props.keyboard_layout == KeyboardLayout::Qwerty).then_some("select-active"))}
+                        onmousedown={change_keyboard_layout_qwerty}>
+                        {"QWERTY"}
+                    </button>
+                    <button class={classes!("select", (props.keyboard_layout == KeyboardLayout::Alphabetical).then_some("select-active"))}
+                        onmousedown={change_keyboard_layout_alphabetical}>
+                        {"ABC"}
+                    </button>
+                    <button class={classes!("select", (props.keyboard_layout == KeyboardLayout::Compact).then_some("select-active"))}
+                        onmousedown={change_keyboard_layout_compact}>
+                        {text(Text::CompactKeyboard)}
+                    </button>
+                </div>
             </div>
+            <div>
+                <label class="label">{text(Text::KeyColorsLabel)}{changed(props.detailed_key_colors != defaults.detailed_key_colors)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (!props.detailed_key_colors).then_some("select-active"))}
+                        onmousedown={change_detailed_key_colors_no}>
+                        {text(Text::KeyColorsBest)}
+                    </button>
+                    <button class={classes!("select", props.detailed_key_colors.then_some("select-active"))}
+                        onmousedown={change_detailed_key_colors_yes}>
+                        {text(Text::KeyColorsDetailed)}
+                    </button>
+                </div>
+            </div>
+            <div>
+                <label class="label">{text(Text::UnusedLettersLabel)}{changed(props.show_unused_letters != defaults.show_unused_letters)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (!props.show_unused_letters).then(|| Some("select-active")))}
+                        onmousedown={change_show_unused_letters_no}>
+                        {text(Text::Hide)}
+                    </button>
+                    <button class={classes!("select", (props.show_unused_letters).then(|| Some("select-active")))}
+                        onmousedown={change_show_unused_letters_yes}>
+                        {text(Text::Show)}
+                    </button>
+                </div>
+            </div>
+            <div>
+                <label class="label">{text(Text::NewInformationLabel)}{changed(props.highlight_new_information != defaults.highlight_new_information)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (!props.highlight_new_information).then_some("select-active"))}
+                        onmousedown={change_highlight_new_information_no}>
+                        {text(Text::NoHighlight)}
+                    </button>
+                    <button class={classes!("select", props.highlight_new_information.then_some("select-active"))}
+                        onmousedown={change_highlight_new_information_yes}>
+                        {text(Text::Highlight)}
+                    </button>
+                </div>
+            </div>
+            <div>
+                <label class="label">{text(Text::TypingHintsLabel)}{changed(props.hide_typing_hints != defaults.hide_typing_hints)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (!props.hide_typing_hints).then_some("select-active"))}
+                        onmousedown={change_hide_typing_hints_no}>
+                        {text(Text::Show)}
+                    </button>
+                    <button class={classes!("select", props.hide_typing_hints.then_some("select-active"))}
+                        onmousedown={change_hide_typing_hints_yes}>
+                        {text(Text::Hide)}
+                    </button>
+                </div>
+            </div>
+            <div>
+                <label class="label">{text(Text::ContradictionWarningLabel)}{changed(props.warn_contradictions != defaults.warn_contradictions)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (!props.warn_contradictions).then_some("select-active"))}
+                        onmousedown={change_warn_contradictions_no}>
+                        {text(Text::No)}
+                    </button>
+                    <button class={classes!("select", props.warn_contradictions.then_some("select-active"))}
+                        onmousedown={change_warn_contradictions_yes}>
+                        {text(Text::Yes)}
+                    </button>
+                </div>
+            </div>
+            <div>
+                <label class="label">{text(Text::AnalysisLabel)}{changed(props.show_candidates != defaults.show_candidates)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (!props.show_candidates).then_some("select-active"))}
+                        onmousedown={change_show_candidates_no}>
+                        {text(Text::Hide)}
+                    </button>
+                    <button class={classes!("select", props.show_candidates.then_some("select-active"))}
+                        onmousedown={change_show_candidates_yes}>
+                        {text(Text::Show)}
+                    </button>
+                </div>
+            </div>
+            <div>
+                <label class="label">{text(Text::BeginnerHelpLabel)}{changed(props.beginner_help != defaults.beginner_help)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (!props.beginner_help).then_some("select-active"))}
+                        onmousedown={change_beginner_help_no}>
+                        {text(Text::Hide)}
+                    </button>
+                    <button class={classes!("select", props.beginner_help.then_some("select-active"))}
+                        onmousedown={change_beginner_help_yes}>
+                        {text(Text::Show)}
+                    </button>
+                </div>
+            </div>
+            <div>
+                <label class="label">{text(Text::CelebrationLabel)}{changed(props.emoji_set != defaults.emoji_set)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (props.emoji_set == EmojiSet::Party).then_some("select-active"))}
+                        onmousedown={change_emoji_set_party}>
+                        {"🥳"}
+                    </button>
+                    <button class={classes!("select", (props.emoji_set == EmojiSet::Animals).then_some("select-active"))}
+                        onmousedown={change_emoji_set_animals}>
+                        {"🐱"}
+                    </button>
+                    <button class={classes!("select", (props.emoji_set == EmojiSet::Hearts).then_some("select-active"))}
+                        onmousedown={change_emoji_set_hearts}>
+                        {"💛"}
+                    </button>
+                    <button class={classes!("select", (props.emoji_set == EmojiSet::Off).then_some("select-active"))}
+                        onmousedown={change_emoji_set_off}>
+                        {text(Text::NoCelebration)}
+                    </button>
+                </div>
+            </div>
+            <div>
+                <label class="label">{text(Text::ShareSolveTimeLabel)}{changed(props.share_solve_time != defaults.share_solve_time)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (!props.share_solve_time).then_some("select-active"))}
+                        onmousedown={change_share_solve_time_no}>
+                        {text(Text::No)}
+                    </button>
+                    <button class={classes!("select", props.share_solve_time.then_some("select-active"))}
+                        onmousedown={change_share_solve_time_yes}>
+                        {text(Text::Yes)}
+                    </button>
+                </div>
+            </div>
+            <div>
+                <label class="label">{text(Text::AccessibleShareLabel)}{changed(props.accessible_share != defaults.accessible_share)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (!props.accessible_share).then_some("select-active"))}
+                        onmousedown={change_accessible_share_no}>
+                        {text(Text::No)}
+                    </button>
+                    <button class={classes!("select", props.accessible_share.then_some("select-active"))}
+                        onmousedown={change_accessible_share_yes}>
+                        {text(Text::Yes)}
+                    </button>
+                </div>
+            </div>
+            <div>
+                <label class="label">{text(Text::SharerTagLabel)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", props.sharer_tag.is_none().then_some("select-active"))}
+                        onmousedown={enable_sharer_tag_no}>
+                        {text(Text::No)}
+                    </button>
+                    <button class={classes!("select", props.sharer_tag.is_some().then_some("select-active"))}
+                        onmousedown={enable_sharer_tag_yes}>
+                        {text(Text::Yes)}
+                    </button>
+                    {
+                        if let Some(tag) = &props.sharer_tag {
+                            html! {
+                                <input class="sharer-tag" type="text" maxlength="4" autocomplete="off"
+                                    value={tag.clone()} onchange={change_sharer_tag} {onkeydown} />
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </div>
+            </div>
+            <div>
+                <label class="label">{text(Text::HideFinishedWordLabel)}{changed(props.hide_finished_word != defaults.hide_finished_word)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (!props.hide_finished_word).then_some("select-active"))}
+                        onmousedown={change_hide_finished_word_no}>
+                        {text(Text::No)}
+                    </button>
+                    <button class={classes!("select", props.hide_finished_word.then_some("select-active"))}
+                        onmousedown={change_hide_finished_word_yes}>
+                        {text(Text::Yes)}
+                    </button>
+                </div>
+            </div>
+            <div>
+                <label class="label">{text(Text::AutoSubmitLabel)}{changed(props.auto_submit != defaults.auto_submit)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (!props.auto_submit).then_some("select-active"))}
+                        onmousedown={change_auto_submit_no}>
+                        {text(Text::No)}
+                    </button>
+                    <button class={classes!("select", props.auto_submit.then_some("select-active"))}
+                        onmousedown={change_auto_submit_yes}>
+                        {text(Text::Yes)}
+                    </button>
+                </div>
+            </div>
+            <div>
+                <label class="label">{text(Text::LanguageLabel)}</label>
+                <div class="select-container">
+                    <button class={classes!("select", (language == Language::Finnish).then_some("select-active"))}
+                        onmousedown={change_language_finnish}>
+                        {"Suomi"}
+                    </button>
+                    <button class={classes!("select", (language == Language::English).then_some("select-active"))}
+                        onmousedown={change_language_english}>
+                        {"English"}
+                    </button>
+                </div>
+            </div>
+            {
+                if props.is_installable {
+                    html! {
+                        <div>
+                            <label class="label">{text(Text::AppLabel)}</label>
+                            <div class="select-container">
+                                <button class="select" onmousedown={install_app}>
+                                    {text(Text::InstallApp)}
+                                </button>
+                            </div>
+                        </div>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+            <div>
+                <label class="label">{text(Text::StoredDataLabel)}</label>
+                <div>
+                    <ConfirmLink label={text(Text::ResetSettings)} language={language} onconfirm={reset_settings} />
+                </div>
+                <div>
+                    <ConfirmLink label={text(Text::ClearAllData)} language={language} onconfirm={clear_all_data} />
+                </div>
+            </div>
+            {
+                match &props.drawn_words {
+                    Some(drawn_words) => html! {
+                        <div>
+                            <label class="label">{text(Text::DrawnWordsLabel)}</label>
+                            <pre class="drawn-words">{drawn_words}</pre>
+                            <div class="select-container">
+                                <button class="select" onmousedown={copy_drawn_words}>
+                                    {text(Text::CopyDrawnWords)}
+                                </button>
+                            </div>
+                        </div>
+                    },
+                    None => html! {},
+                }
+            }
+        </>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PracticeLettersProps {
+    pub callback: Callback<Msg>,
+    pub current: Option<char>,
+    // Letters that at least one word contains
+    pub letters: Vec<char>,
+    pub language: Language,
+    pub word_language: WordLanguage,
+}
+
+#[function_component(PracticeLetters)]
+fn practice_letters(props: &PracticeLettersProps) -> Html {
+    let callback = props.callback.clone();
+    let change_letter_any = onmousedown!(callback, Msg::ChangeGameMode(GameMode::Practice(None)));
+
+    html! {
+        <div>
+            <label class="label">{Text::PracticeLetterLabel.get(props.language)}</label>
+            <div class="select-container">
+                <button class={classes!("select", props.current.is_none().then_some("select-active"))}
+                    onmousedown={change_letter_any}>
+                    {Text::AnyLetter.get(props.language)}
+                </button>
+                {game::alphabet(props.word_language).map(|letter| {
+                    let callback = props.callback.clone();
+                    let change_letter = onmousedown!(callback, Msg::ChangeGameMode(GameMode::Practice(Some(letter))));
+
+                    html! {
+                        <button class={classes!("select", "select-letter", (props.current == Some(letter)).then_some("select-active"))}
+                            disabled={!props.letters.contains(&letter)}
+                            onmousedown={change_letter}>
+                            {letter}
+                        </button>
+                    }
+                }).collect::<Html>()}
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SuspendedGamesProps {
+    pub callback: Callback<Msg>,
+    pub games: Vec<SuspendedGame>,
+    pub language: Language,
+}
+
+#[function_component(SuspendedGames)]
+fn suspended_games(props: &SuspendedGamesProps) -> Html {
+    let language = props.language;
+
+    html! {
+        <div>
+            <label class="label">{Text::SuspendedGamesLabel.get(language)}</label>
+            <ul>
+                {props.games.iter().map(|suspended| {
+                    let game = suspended.game;
+                    let (game_mode, word_list, word_length) = game;
+                    let callback = props.callback.clone();
+
+                    let mut description = format!(
+                        "{} · {}",
+                        strings::game_mode_name(language, game_mode),
+                        strings::word_length(language, word_length)
+                    );
+                    if !matches!(game_mode, GameMode::DailyWord(_)) {
+                        description += &format!(" · {}", strings::word_list_name(language, word_list));
+                    }
+
+                    if suspended.is_finished {
+                        description += &format!(" · {}", Text::Finished.get(language));
+                        let start_new = onmousedown!(callback, Msg::ContinueWithNewGame(game));
+
+                        html! {
+                            <li class="statistics">
+                                {description}
+                                {
+                                    if matches!(game_mode, GameMode::DailyWord(_)) {
+                                        html! {}
+                                    } else {
+                                        html! {
+                                            <>
+                                                {" "}
+                                                <a class="link" href={"javascript:void(0)"} onmousedown={start_new}>{Text::NewButton.get(language)}</a>
+                                            </>
+                                        }
+                                    }
+                                }
+                            </li>
+                        }
+                    } else {
+                        description += &format!(" · {}", strings::guess_number(language, suspended.guesses_used + 1));
+                        let continue_game = onmousedown!(callback, Msg::ContinueGame(game));
+
+                        html! {
+                            <li class="statistics">
+                                {description}
+                                {" "}
+                                <a class="link" href={"javascript:void(0)"} onmousedown={continue_game}>{Text::Continue.get(language)}</a>
+                            </li>
+                        }
+                    }
+                }).collect::<Html>()}
+            </ul>
+        </div>
+    }
+}
+
+#[function_component(StatisticsTab)]
+fn statistics_tab(props: &MenuModalProps) -> Html {
+    let language = props.language;
+    let text = |text: Text| text.get(language);
+
+    html! {
+        <div>
+            <label class="label">{text(Text::OwnStatisticsLabel)}</label>
+            <ul>
+                <li class="statistics">{format!("{}: {}", text(Text::LongestStreak), props.max_streak)}</li>
+                <li class="statistics">{format!("{}: {}", text(Text::WordsPlayed), props.total_played)}</li>
+                <li class="statistics">{format!("{}: {}", text(Text::WordsSolved), props.total_solved)}</li>
+            </ul>
+            <label class="label">{text(Text::DailyWordsLabel)}</label>
+            <ul>
+                <li class="statistics">{format!("{}: {}", text(Text::Streak), props.daily_streak)}</li>
+                <li class="statistics">{format!("{}: {}", text(Text::LongestStreak), props.max_daily_streak)}</li>
+                <li class="statistics">{format!("{}: {} ❄️", text(Text::Freezes), props.daily_freezes)}</li>
+            </ul>
+            {
+                if props.frozen_days.is_empty() {
+                    html! {}
+                } else {
+                    html! {
+                        <>
+                            <label class="label">{text(Text::FrozenDaysLabel)}</label>
+                            <div class="select-container">
+                                {props.frozen_days.iter().rev().map(|date| {
+                                    html! { <span class="select">{format!("❄️ {}", date.format("%-d.%-m."))}</span> }
+                                }).collect::<Html>()}
+                            </div>
+                        </>
+                    }
+                }
+            }
+            {
+                if props.broken_streaks.is_empty() {
+                    html! {}
+                } else {
+                    html! {
+                        <>
+                            <label class="label">{text(Text::BrokenStreaksLabel)}</label>
+                            <ul>
+                                {props.broken_streaks.iter().rev().map(|broken| {
+                                    html! {
+                                        <li class="statistics">
+                                            {strings::msg_broken_streak(language, broken.streak)}
+                                            {" "}
+                                            <a class="link" href={dictionary_link(broken.word_language, &broken.word)}
+                                                target="_blank">{ &broken.word }
+                                            </a>
+                                            {format!(", {}", broken.date.format("%-d.%-m."))}
+                                        </li>
+                                    }
+                                }).collect::<Html>()}
+                            </ul>
+                        </>
+                    }
+                }
+            }
+            {
+                if props.best_milestone > 0 {
+                    html! {
+                        <>
+                            <label class="label">{text(Text::MilestonesLabel)}</label>
+                            <div class="select-container">
+                                {STREAK_MILESTONES.iter().filter(|milestone| **milestone <= props.best_milestone).map(|milestone| {
+                                    html! { <span class="select select-active">{format!("🔥 {}", milestone)}</span> }
+                                }).collect::<Html>()}
+                            </div>
+                        </>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+            {
+                if props.guess_distribution.is_empty() {
+                    html! {}
+                } else {
+                    html! {
+                        <>
+                            <label class="label">{text(Text::GuessDistributionLabel)}</label>
+                            <ul>
+                                {props.guess_distribution.iter().enumerate().map(|(index, count)| {
+                                    html! {
+                                        <li class="statistics">{format!("{}: {}", strings::guess_number(language, index + 1), count)}</li>
+                                    }
+                                }).collect::<Html>()}
+                            </ul>
+                        </>
+                    }
+                }
+            }
         </div>
     }
 }
@@ -0,0 +1,156 @@
+use std::rc::Rc;
+
+use gloo_timers::callback::Interval;
+use yew::prelude::*;
+
+use sanuli::game::SharedGuesses;
+
+use crate::components::board::Board;
+use crate::manager::TileState;
+
+// Time between revealing two tiles of the replayed guesses
+const REPLAY_STEP_MS: u32 = 300;
+
+/// Guesses as they were after the first `step` tiles had been revealed. The row being
+/// revealed shows all of its letters, only the colors appear one tile at a time.
+pub fn replay_guesses(guesses: &SharedGuesses, step: usize, word_length: usize) -> SharedGuesses {
+    let rows = guesses
+        .iter()
+        .enumerate()
+        .map(|(row, guess)| {
+            let revealed = step.saturating_sub(row * word_length).min(word_length);
+            if revealed == 0 {
+                return Vec::new();
+            }
+
+            guess
+                .iter()
+                .enumerate()
+                .map(|(index, (character, state))| {
+                    if index < revealed {
+                        (*character, *state)
+                    } else {
+                        (*character, TileState::Unknown)
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    Rc::new(rows)
+}
+
+enum ReplayAction {
+    Tick,
+    TogglePlay,
+    Back,
+    Forward,
+}
+
+struct ReplayState {
+    step: usize,
+    steps: usize,
+    word_length: usize,
+    is_playing: bool,
+}
+
+impl Reducible for ReplayState {
+    type Action = ReplayAction;
+
+    fn reduce(self: Rc<Self>, action: ReplayAction) -> Rc<Self> {
+        let (step, is_playing) = match action {
+            ReplayAction::Tick => {
+                let step = (self.step + 1).min(self.steps);
+                (step, step < self.steps)
+            }
+            // Playing from the end starts over from the beginning
+            ReplayAction::TogglePlay if self.step == self.steps => (0, true),
+            ReplayAction::TogglePlay => (self.step, !self.is_playing),
+            ReplayAction::Back => {
+                let step = match self.step % self.word_length {
+                    0 => self.step.saturating_sub(self.word_length),
+                    revealed => self.step - revealed,
+                };
+                (step, false)
+            }
+            ReplayAction::Forward => {
+                let step = self.step - self.step % self.word_length + self.word_length;
+                (step.min(self.steps), false)
+            }
+        };
+
+        Rc::new(ReplayState {
+            step,
+            is_playing,
+            ..*self
+        })
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ReplayProps {
+    pub guesses: SharedGuesses,
+    pub max_guesses: usize,
+    pub word_length: usize,
+    pub show_glyphs: bool,
+    pub on_close: Callback<MouseEvent>,
+}
+
+/// Plays the guesses of a finished game back one tile at a time on a regular board.
+#[function_component(Replay)]
+pub fn replay(props: &ReplayProps) -> Html {
+    let word_length = props.word_length.max(1);
+    let steps = props.guesses.iter().filter(|guess| !guess.is_empty()).count() * word_length;
+
+    let state = use_reducer(|| ReplayState {
+        step: 0,
+        steps,
+        word_length,
+        is_playing: true,
+    });
+
+    {
+        let dispatcher = state.dispatcher();
+        use_effect_with(state.is_playing, move |is_playing| {
+            // Dropping the interval stops it
+            let interval = is_playing.then(|| {
+                Interval::new(REPLAY_STEP_MS, move || dispatcher.dispatch(ReplayAction::Tick))
+            });
+            move || drop(interval)
+        });
+    }
+
+    let control = |action: fn() -> ReplayAction| {
+        let dispatcher = state.dispatcher();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            dispatcher.dispatch(action());
+        })
+    };
+
+    html! {
+        <>
+            <Board
+                guesses={replay_guesses(&props.guesses, state.step, word_length)}
+                new_information={Vec::new()}
+                highlight_new_information={false}
+                is_guessing={false}
+                current_guess={0}
+                is_reset={false}
+                is_hidden={false}
+                previous_guesses={SharedGuesses::default()}
+                max_guesses={props.max_guesses}
+                word_length={props.word_length}
+                show_glyphs={props.show_glyphs}
+            />
+            <div class="replay-controls">
+                <button class="replay-button" onmousedown={control(|| ReplayAction::Back)}>{ "⏮" }</button>
+                <button class="replay-button" onmousedown={control(|| ReplayAction::TogglePlay)}>
+                    { if state.is_playing { "⏸" } else { "▶" } }
+                </button>
+                <button class="replay-button" onmousedown={control(|| ReplayAction::Forward)}>{ "⏭" }</button>
+                <button class="replay-button" onmousedown={props.on_close.clone()}>{ "✕" }</button>
+            </div>
+        </>
+    }
+}
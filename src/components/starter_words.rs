@@ -0,0 +1,31 @@
+use yew::prelude::*;
+
+use crate::Msg;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub callback: Callback<Msg>,
+    pub words: &'static [&'static str],
+}
+
+/// Tappable starter words for beginners, a tap fills the first guess with the word.
+#[function_component(StarterWords)]
+pub fn starter_words(props: &Props) -> Html {
+    html! {
+        <div class="starter-words">
+            {
+                props.words.iter().map(|word| {
+                    let callback = props.callback.clone();
+                    let onmousedown = Callback::from(move |e: MouseEvent| {
+                        e.prevent_default();
+                        callback.emit(Msg::FillGuess(word));
+                    });
+
+                    html! {
+                        <button class="starter-word" {onmousedown}>{ word }</button>
+                    }
+                }).collect::<Html>()
+            }
+        </div>
+    }
+}
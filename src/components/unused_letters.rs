@@ -0,0 +1,33 @@
+use std::collections::HashMap;
+use yew::prelude::*;
+
+use sanuli::game;
+
+use crate::manager::{KeyState, TileState, WordLanguage};
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub keyboard: HashMap<char, KeyState>,
+    pub word_language: WordLanguage,
+}
+
+// With several boards the letter is only unused if no board knows anything about it
+fn is_unused(key_state: Option<&KeyState>) -> bool {
+    key_state.is_none_or(|key_state| {
+        key_state
+            .states()
+            .iter()
+            .all(|state| *state == TileState::Unknown)
+    })
+}
+
+#[function_component(UnusedLetters)]
+pub fn unused_letters(props: &Props) -> Html {
+    let letters = game::alphabet(props.word_language)
+        .filter(|key| is_unused(props.keyboard.get(key)))
+        .collect::<String>();
+
+    html! {
+        <div data-nosnippet="" class="unused-letters">{ letters }</div>
+    }
+}
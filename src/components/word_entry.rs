@@ -0,0 +1,38 @@
+use yew::prelude::*;
+
+use crate::components::board::{board_class, board_style, row_style};
+
+#[derive(Properties, PartialEq)]
+pub struct WordEntryProps {
+    pub entered: usize,
+    pub max_guesses: usize,
+    pub word_length: usize,
+}
+
+/// The secret word of a two-player game, masked so that the guesser can't
+/// read it over the shoulder.
+#[function_component(WordEntry)]
+pub fn word_entry(props: &WordEntryProps) -> Html {
+    html! {
+        <div class={board_class(props.max_guesses)}
+            style={board_style(props.max_guesses, props.word_length)}>
+            <div class="row" style={row_style(props.word_length)}>
+                {
+                    (0..props.word_length).map(|tile_index| {
+                        html! {
+                            <div class={classes!("tile", "unknown", "current")}>
+                                {
+                                    if tile_index < props.entered {
+                                        '•'
+                                    } else {
+                                        ' '
+                                    }
+                                }
+                            </div>
+                        }
+                    }).collect::<Html>()
+                }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,75 @@
+// Addresses and the version the app links to. Forks can point the suggestion links at a form
+// of their own by setting SANULI_FORMS_URL and the SANULI_FORMS_*_ENTRY field ids at build time.
+
+pub const VERSION: &str = "v1.14";
+pub const CHANGELOG_URL: &str = "https://github.com/Cadiac/sanuli/blob/master/CHANGELOG.md";
+
+const FORMS_URL: &str = match option_env!("SANULI_FORMS_URL") {
+    Some(url) => url,
+    None => "https://docs.google.com/forms/d/e/1FAIpQLSfH8gs4sq-Ynn8iGOvlc99J_zOG2rJEC4m8V0kCgF_en3RHFQ/viewform",
+};
+const FORMS_KIND_ENTRY: &str = match option_env!("SANULI_FORMS_KIND_ENTRY") {
+    Some(entry) => entry,
+    None => "entry.461337706",
+};
+const FORMS_WORD_ENTRY: &str = match option_env!("SANULI_FORMS_WORD_ENTRY") {
+    Some(entry) => entry,
+    None => "entry.560255602",
+};
+// The form ignores entries it doesn't have, these are filled in only once it has the fields
+const FORMS_VERSION_ENTRY: &str = match option_env!("SANULI_FORMS_VERSION_ENTRY") {
+    Some(entry) => entry,
+    None => "entry.version",
+};
+const FORMS_WORD_LENGTH_ENTRY: &str = match option_env!("SANULI_FORMS_WORD_LENGTH_ENTRY") {
+    Some(entry) => entry,
+    None => "entry.word_length",
+};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Suggestion {
+    Addition,
+    Removal,
+}
+
+/// A link to the suggestion form, prefilled with the word and the version of the app. The word
+/// may be left empty for a suggestion the player fills in themselves.
+pub fn suggestion_link(suggestion: Suggestion, word: &str) -> String {
+    let kind = match suggestion {
+        Suggestion::Addition => "Lis%C3%A4yst%C3%A4",
+        Suggestion::Removal => "Poistoa",
+    };
+
+    let mut link = format!(
+        "{}?usp=pp_url&{}={}&{}={}&{}={}",
+        FORMS_URL, FORMS_KIND_ENTRY, kind, FORMS_VERSION_ENTRY, VERSION, FORMS_WORD_ENTRY, word
+    );
+    if !word.is_empty() {
+        link = format!(
+            "{}&{}={}",
+            link,
+            FORMS_WORD_LENGTH_ENTRY,
+            word.chars().count()
+        );
+    }
+
+    link
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggestions_are_prefilled() {
+        let link = suggestion_link(Suggestion::Removal, "äitiä");
+        assert!(link.starts_with(FORMS_URL));
+        assert!(link.contains(&format!("{}=Poistoa", FORMS_KIND_ENTRY)));
+        assert!(link.contains(&format!("{}=äitiä", FORMS_WORD_ENTRY)));
+        assert!(link.contains(&format!("{}={}", FORMS_VERSION_ENTRY, VERSION)));
+        assert!(link.contains(&format!("{}=5", FORMS_WORD_LENGTH_ENTRY)));
+
+        let link = suggestion_link(Suggestion::Addition, "");
+        assert!(!link.contains(FORMS_WORD_LENGTH_ENTRY));
+    }
+}
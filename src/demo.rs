@@ -0,0 +1,72 @@
+// The scripted game played on the landing page while a first time visitor is idle.
+// Colors come from the same feedback the real game gives, only the words are fixed.
+
+use crate::manager::TileState;
+use crate::solver;
+
+pub const DEMO_WORD: &str = "TALVI";
+pub const DEMO_GUESSES: [&str; 2] = ["SAUNA", "TALVI"];
+
+fn chars(word: &str) -> Vec<char> {
+    word.chars().collect()
+}
+
+pub fn word_length() -> usize {
+    DEMO_WORD.chars().count()
+}
+
+/// Each guess is typed one letter per step and takes one more step to submit.
+pub fn steps() -> usize {
+    DEMO_GUESSES.len() * (word_length() + 1)
+}
+
+/// The demo board after `step` steps, one row for each of the scripted guesses.
+pub fn board(step: usize) -> Vec<Vec<(char, TileState)>> {
+    let word = chars(DEMO_WORD);
+
+    DEMO_GUESSES
+        .iter()
+        .enumerate()
+        .map(|(row, guess)| {
+            let guess = chars(guess);
+            let typed = step.saturating_sub(row * (guess.len() + 1));
+
+            if typed > guess.len() {
+                guess
+                    .iter()
+                    .copied()
+                    .zip(solver::feedback(&guess, &word))
+                    .collect()
+            } else {
+                guess
+                    .iter()
+                    .take(typed)
+                    .map(|c| (*c, TileState::Unknown))
+                    .collect()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn demo_types_and_submits_each_guess() {
+        assert!(board(0).iter().all(|row| row.is_empty()));
+
+        let typing = board(3);
+        assert_eq!(typing[0].len(), 3);
+        assert!(typing[0].iter().all(|(_, state)| *state == TileState::Unknown));
+        assert!(typing[1].is_empty());
+
+        let submitted = board(word_length() + 1);
+        assert!(submitted[0][0] == ('S', TileState::Absent));
+        assert!(submitted[0][1] == ('A', TileState::Correct));
+        assert!(submitted[0][4] == ('A', TileState::Absent));
+
+        let solved = board(steps());
+        assert!(solved[1].iter().all(|(_, state)| *state == TileState::Correct));
+    }
+}
@@ -1,35 +1,231 @@
+use chrono::NaiveDate;
+use rand::seq::SliceRandom;
 use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
 
-use gloo_storage::errors::StorageError;
+use serde_json::Value;
 
-pub type KnownStates = HashMap<(char, usize), CharacterState>;
 pub type KnownCounts = HashMap<char, CharacterCount>;
+pub type SharedGuesses = Rc<Vec<Vec<(char, TileState)>>>;
+// Guesses used on each daily word by its index, None when it wasn't solved
+pub type DailyHistory = HashMap<usize, Option<usize>>;
+// Markers the player has put on tiles by their row and column
+pub type TileMarkers = HashMap<(usize, usize), TileMarker>;
 
 use crate::manager::{
-    CharacterCount, CharacterState, GameMode, KeyState, Theme, TileState, WordList,
+    CharacterCount, CharacterState, EmojiSet, GameMode, KeyState, Theme, TileMarker, TileState,
+    WordLanguage, WordList,
 };
+use crate::strings::{self, Language, Text};
 
-pub const SUCCESS_EMOJIS: [&str; 9] = ["🥳", "🤩", "🤗", "🎉", "😊", "😺", "😎", "👏", ":3"];
+// Character of a tile that has nothing typed in it
+pub const EMPTY: char = ' ';
+// Dailies to compare to before there is a percentile to show
+pub const MIN_PERCENTILE_DAILIES: usize = 5;
+pub const STREAK_MILESTONES: [usize; 6] = [5, 10, 25, 50, 100, 250];
+pub const ALLOWED_KEYS: [char; 29] = [
+    'Q', 'W', 'E', 'R', 'T', 'Y', 'U', 'I', 'O', 'P', 'A', 'S', 'D', 'F', 'G', 'H', 'J', 'K', 'L',
+    'Ö', 'Ä', 'Z', 'X', 'C', 'V', 'B', 'N', 'M', 'Å',
+];
+
+// Keyboard rows of each word language, only the letters its words can have
+const FINNISH_KEYBOARD: [&[char]; 3] = [
+    &['Q', 'W', 'E', 'R', 'T', 'Y', 'U', 'I', 'O', 'P'],
+    &['A', 'S', 'D', 'F', 'G', 'H', 'J', 'K', 'L', 'Ö', 'Ä'],
+    &['Z', 'X', 'C', 'V', 'B', 'N', 'M'],
+];
+const SWEDISH_KEYBOARD: [&[char]; 3] = [
+    &['Q', 'W', 'E', 'R', 'T', 'Y', 'U', 'I', 'O', 'P', 'Å'],
+    &['A', 'S', 'D', 'F', 'G', 'H', 'J', 'K', 'L', 'Ö', 'Ä'],
+    &['Z', 'X', 'C', 'V', 'B', 'N', 'M'],
+];
 pub const DEFAULT_WORD_LENGTH: usize = 5;
 pub const DEFAULT_MAX_GUESSES: usize = 6;
 pub const DEFAULT_ALLOW_PROFANITIES: bool = false;
 
+// Optional additions to the shared result
+#[derive(Clone, Copy, Default)]
+pub struct ShareOptions {
+    pub show_solve_time: bool,
+    // A line of text for each row under the emojis, for screen readers
+    pub describe_rows: bool,
+}
+
+/// A character of `ALLOWED_KEYS`, stored as its index there. Converting a char is where
+/// input gets validated, anything else can't be a letter of a word.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Letter(u8);
+
+impl Letter {
+    pub fn all() -> impl Iterator<Item = Letter> {
+        (0..ALLOWED_KEYS.len() as u8).map(Letter)
+    }
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl TryFrom<char> for Letter {
+    type Error = char;
+
+    fn try_from(character: char) -> Result<Self, Self::Error> {
+        ALLOWED_KEYS
+            .iter()
+            .position(|key| *key == character)
+            .map(|index| Letter(index as u8))
+            .ok_or(character)
+    }
+}
+
+impl From<Letter> for char {
+    fn from(letter: Letter) -> char {
+        ALLOWED_KEYS[letter.index()]
+    }
+}
+
+impl fmt::Display for Letter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", char::from(*self))
+    }
+}
+
+/// What is known about the positions of each letter on one row, as a pair of position
+/// bitmasks per letter in `ALLOWED_KEYS`. Copying it to the next row is a plain copy.
+/// Characters outside `ALLOWED_KEYS`, or positions past the mask, are never known.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct KnownStates {
+    correct: [u32; ALLOWED_KEYS.len()],
+    absent: [u32; ALLOWED_KEYS.len()],
+}
+
+impl KnownStates {
+    fn slot(character: char, index: usize) -> Option<(usize, u32)> {
+        let letter = Letter::try_from(character).ok()?;
+        let position = 1u32.checked_shl(u32::try_from(index).ok()?)?;
+        Some((letter.index(), position))
+    }
+
+    pub fn get(&self, character: char, index: usize) -> Option<CharacterState> {
+        let (letter, position) = Self::slot(character, index)?;
+        if self.correct[letter] & position != 0 {
+            Some(CharacterState::Correct)
+        } else if self.absent[letter] & position != 0 {
+            Some(CharacterState::Absent)
+        } else {
+            None
+        }
+    }
+
+    pub fn insert(&mut self, character: char, index: usize, state: CharacterState) {
+        if let Some((letter, position)) = Self::slot(character, index) {
+            self.correct[letter] &= !position;
+            self.absent[letter] &= !position;
+            match state {
+                CharacterState::Correct => self.correct[letter] |= position,
+                CharacterState::Absent => self.absent[letter] |= position,
+                CharacterState::Unknown => {}
+            }
+        }
+    }
+
+    /// How many positions of the character are known to be correct.
+    pub fn correct_count(&self, character: char) -> usize {
+        Letter::try_from(character).map_or(0, |letter| {
+            self.correct[letter.index()].count_ones() as usize
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (char, usize, CharacterState)> + '_ {
+        Letter::all().flat_map(move |letter| {
+            let character = char::from(letter);
+            (0..u32::BITS as usize).filter_map(move |index| {
+                self.get(character, index)
+                    .map(|state| (character, index, state))
+            })
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.correct
+            .iter()
+            .chain(&self.absent)
+            .all(|mask| *mask == 0)
+    }
+}
+
+/// How a game ended, returned by the guess that ended it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameResult {
+    pub game_mode: GameMode,
+    // The word of each board
+    pub words: Vec<Vec<char>>,
+    pub is_winner: bool,
+    pub guesses_used: usize,
+    pub streak: usize,
+    pub is_late: bool,
+}
+
 pub trait Game {
     fn title(&self) -> String;
     fn next_word(&mut self);
-    fn keyboard_tilestate(&self, key: &char) -> KeyState;
-    fn submit_guess(&mut self);
-    fn push_character(&mut self, character: char);
-    fn pop_character(&mut self);
-    fn share_emojis(&self, theme: Theme) -> Option<String>;
-    fn share_link(&self) -> Option<String>;
+    // Draws the next word ahead of time so that next_word only has to swap it in
+    fn prepare_next_word(&mut self);
+    fn is_next_word_prepared(&self) -> bool;
+    fn keyboard_state(&self) -> &HashMap<char, KeyState>;
+    // Returns the result once the guess ends the game
+    fn submit_guess(&mut self) -> Option<GameResult>;
+    // Both return whether the board changed, so that a no-op needs no render
+    fn push_character(&mut self, character: char) -> bool;
+    fn pop_character(&mut self) -> bool;
+    fn share_emojis(&self, theme: Theme, options: ShareOptions) -> Option<String>;
+    // Like share_emojis, but without revealing which positions were hit
+    fn share_emoji_counts(&self, theme: Theme, options: ShareOptions) -> Option<String>;
+    // The sharer tag tells the receiver whose game the link came from
+    fn share_payload(&self, sharer_tag: Option<&str>) -> Option<String>;
+    // Like share_payload, but for an unfinished game and without the word
+    fn progress_payload(&self, sharer_tag: Option<&str>) -> Option<String>;
+    fn hide_word(&mut self);
     fn reveal_hidden_tiles(&mut self);
     fn reset(&mut self);
+    // Ends the entry animation of a new word, so that later renders don't replay it
+    fn clear_reset(&mut self);
     fn refresh(&mut self);
-    fn persist(&self) -> Result<(), StorageError>;
+    fn storage_key(&self) -> Option<String>;
+    fn snapshot(&self) -> serde_json::Result<Value>;
     fn set_allow_profanities(&mut self, is_allowed: bool);
+    fn set_weighted_selection(&mut self, is_weighted: bool);
+    // Time while the page is hidden doesn't count towards the solve time
+    fn pause_timer(&mut self);
+    fn resume_timer(&mut self);
+    fn set_emoji_set(&mut self, emoji_set: EmojiSet);
+    fn set_language(&mut self, language: Language);
+    // A daily word played after its own day, it's titled and shared as late
+    fn mark_late(&mut self);
+    // Continues a streak whose game couldn't be restored, letting the player know about it
+    fn restore_streak(&mut self, streak: usize);
+    // Tells the player why something they asked for didn't happen
+    fn set_message(&mut self, message: String);
+    // Typed letters stay uncolored until the guess is submitted
+    fn set_hide_typing_hints(&mut self, is_hidden: bool);
+    // A guess going against known information has to be submitted twice to be accepted
+    fn set_warn_contradictions(&mut self, is_warned: bool);
+    // Keeps count of the words that still fit the guesses, for the analysis overlay
+    fn set_show_candidates(&mut self, is_shown: bool);
+    // Whether the hint colors of typed letters helped with this word
+    fn is_hint_assisted(&self) -> bool;
+    // The list the word came from, for telling how hard of a word it was
+    fn word_source(&self) -> Option<WordList>;
+    // Whether the typed guess is full and would be accepted if submitted now
+    fn is_guess_ready(&self) -> bool;
+    // Moves a tile of a submitted guess to its next marker, returns whether it changed
+    fn cycle_tile_marker(&mut self, board: usize, row: usize, column: usize) -> bool;
+    // A friend's game opened from a progress link, it can only be watched
+    fn is_spectating(&self) -> bool;
 
     fn game_mode(&self) -> &GameMode;
+    fn word_language(&self) -> WordLanguage;
     fn word_list(&self) -> &WordList;
     fn word_length(&self) -> usize;
     fn max_guesses(&self) -> usize;
@@ -38,15 +234,28 @@ pub trait Game {
     fn last_guess(&self) -> String;
     fn boards(&self) -> Vec<Board>;
     fn streak(&self) -> usize;
+    // How many guesses the finished game took, None while still guessing
+    fn guesses_used(&self) -> Option<usize>;
+    // Solved boards and the guesses left, only for games with several boards
+    fn board_progress(&self) -> Option<BoardProgress>;
+    // How many words of the full list still fit the guesses, only the count is ever exposed
+    fn remaining_candidates(&self) -> Option<usize>;
+    // Words left before and after each guess of a finished game, with the analysis turned on
+    fn guess_analysis(&self) -> Option<Vec<GuessAnalysis>>;
 
     fn is_guessing(&self) -> bool;
     fn is_reset(&self) -> bool;
     fn is_hidden(&self) -> bool;
     fn is_winner(&self) -> bool;
     fn is_unknown(&self) -> bool;
+    // The last guess went against known information and waits to be submitted again
+    fn is_contradiction_pending(&self) -> bool;
+    // Nothing has been typed on the word yet, not even a letter that was erased again
+    fn is_untouched(&self) -> bool;
+    fn is_late(&self) -> bool;
 
     fn message(&self) -> String;
-    fn previous_guesses(&self) -> Vec<Vec<(char, TileState)>>;
+    fn previous_guesses(&self) -> SharedGuesses;
 }
 
 impl PartialEq for dyn Game {
@@ -65,15 +274,162 @@ impl PartialEq for dyn Game {
     }
 }
 
+// How far along a game with several boards is, for a status line while it's played
+#[derive(Clone, Copy, PartialEq)]
+pub struct BoardProgress {
+    pub solved: usize,
+    pub boards: usize,
+    pub guesses_left: usize,
+}
+
+// How much one guess narrowed the words down, for the analysis after the game
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuessAnalysis {
+    pub guess: String,
+    pub before: usize,
+    pub after: usize,
+}
+
+impl GuessAnalysis {
+    /// A guess that kept more than half of the words it could have ruled out.
+    pub fn is_weak(&self) -> bool {
+        self.after > 1 && self.after * 2 > self.before
+    }
+}
+
 #[derive(PartialEq)]
 pub struct Board {
-    pub guesses: Vec<Vec<(char, TileState)>>,
+    pub guesses: SharedGuesses,
+    pub new_information: Vec<Vec<bool>>,
     pub current_guess: usize,
     pub is_guessing: bool,
+    pub is_winner: bool,
+    pub word: Vec<char>,
+    pub markers: TileMarkers,
 }
 
 // Common game logic
 
+pub fn keyboard_layout(word_language: WordLanguage) -> [&'static [char]; 3] {
+    match word_language {
+        WordLanguage::Finnish => FINNISH_KEYBOARD,
+        WordLanguage::Swedish => SWEDISH_KEYBOARD,
+    }
+}
+
+/// The letters of the language in keyboard order.
+pub fn alphabet(word_language: WordLanguage) -> impl Iterator<Item = char> {
+    keyboard_layout(word_language)
+        .into_iter()
+        .flatten()
+        .copied()
+}
+
+// Finnish games keep the keys they had before there were other languages
+pub fn storage_key(
+    word_language: WordLanguage,
+    game_mode: &GameMode,
+    word_list: &WordList,
+    word_length: usize,
+) -> String {
+    let key = format!(
+        "game|{}|{}|{}",
+        serde_json::to_string(game_mode).unwrap(),
+        serde_json::to_string(word_list).unwrap(),
+        word_length
+    );
+
+    match word_language {
+        WordLanguage::Finnish => key,
+        _ => format!("{}|{}", key, word_language.code()),
+    }
+}
+
+pub fn daily_word_index(date: NaiveDate) -> usize {
+    let epoch = NaiveDate::from_ymd_opt(2022, 1, 7).unwrap(); // Epoch of the daily word mode, index 0
+    date.signed_duration_since(epoch).num_days() as usize
+}
+
+/// Percentage of the other recorded dailies that took more guesses than the daily at `index`
+/// or weren't solved at all. Ties don't count as beaten.
+pub fn daily_percentile(history: &DailyHistory, index: usize) -> Option<usize> {
+    // Lost games rank below any number of guesses
+    let rank = |guesses: &Option<usize>| guesses.unwrap_or(usize::MAX);
+    let own = rank(history.get(&index)?);
+
+    let others = history
+        .iter()
+        .filter(|(other, _)| **other != index)
+        .map(|(_, guesses)| rank(guesses))
+        .collect::<Vec<_>>();
+    if others.len() < MIN_PERCENTILE_DAILIES {
+        return None;
+    }
+
+    let beaten = others.iter().filter(|other| **other > own).count();
+    Some(beaten * 100 / others.len())
+}
+
+/// The highest streak milestone `streak` has reached, if any.
+pub fn streak_milestone(streak: usize) -> Option<usize> {
+    STREAK_MILESTONES
+        .iter()
+        .rev()
+        .find(|milestone| **milestone <= streak)
+        .copied()
+}
+
+pub fn celebration(emoji_set: EmojiSet) -> String {
+    emoji_set
+        .emojis()
+        .choose(&mut rand::thread_rng())
+        .map(|emoji| emoji.to_string())
+        .unwrap_or_default()
+}
+
+pub fn format_duration(milliseconds: i64) -> String {
+    let seconds = milliseconds / 1000;
+
+    if seconds >= 60 {
+        format!("{} min {} s", seconds / 60, seconds % 60)
+    } else {
+        format!("{} s", seconds)
+    }
+}
+
+/// The language and game a key from `storage_key` was made for.
+pub fn parse_storage_key(key: &str) -> Option<(WordLanguage, (GameMode, WordList, usize))> {
+    let mut parts = key.strip_prefix("game|")?.splitn(4, '|');
+
+    let game_mode = serde_json::from_str(parts.next()?).ok()?;
+    let word_list = serde_json::from_str(parts.next()?).ok()?;
+    let word_length = parts.next()?.parse().ok()?;
+    let word_language = match parts.next() {
+        Some(code) => WordLanguage::from_code(code)?,
+        None => WordLanguage::Finnish,
+    };
+
+    Some((word_language, (game_mode, word_list, word_length)))
+}
+
+// Each word length and list keeps a streak of its own, so name the one being played
+pub fn streak_title(
+    language: Language,
+    name: &str,
+    word_length: usize,
+    word_list: WordList,
+    streak: usize,
+) -> String {
+    let list = strings::word_list_name(language, word_list);
+    let title = format!("{} {} · {}", name, word_length, list);
+
+    if streak > 0 {
+        format!("{} — {}: {}", title, Text::Streak.get(language), streak)
+    } else {
+        title
+    }
+}
+
 pub fn known_count(
     character: &char,
     current_guess: usize,
@@ -120,7 +476,7 @@ fn revealed_by_char(
     let mut revealed_count_on_row: HashMap<char, usize> = HashMap::with_capacity(guess.len());
 
     for (index, (character, _)) in guess.iter().enumerate() {
-        if let Some(CharacterState::Correct) = states[current_guess].get(&(*character, index)) {
+        if let Some(CharacterState::Correct) = states[current_guess].get(*character, index) {
             revealed_count_on_row
                 .entry(*character)
                 .and_modify(|count| *count += 1)
@@ -159,7 +515,7 @@ pub fn board_tile_state(
     index: usize,
     character: &char,
 ) -> TileState {
-    match states[current_guess].get(&(*character, index)) {
+    match states[current_guess].get(*character, index) {
         Some(CharacterState::Correct) => {
             return TileState::Correct;
         }
@@ -196,7 +552,7 @@ pub fn hint_tile_state(
     states: &[KnownStates],
     counts: &[KnownCounts],
 ) -> TileState {
-    match states[guess_index].get(&(character, character_index)) {
+    match states[guess_index].get(character, character_index) {
         Some(CharacterState::Correct) => TileState::Correct,
         Some(CharacterState::Absent) => TileState::Absent,
         _ => {
@@ -207,13 +563,8 @@ pub fn hint_tile_state(
                         return TileState::Absent;
                     }
 
-                    let is_every_correct_found = states[guess_index]
-                        .iter()
-                        .filter(|((c, _i), state)| {
-                            c == &character && *state == &CharacterState::Correct
-                        })
-                        .count()
-                        == *count;
+                    let is_every_correct_found =
+                        states[guess_index].correct_count(character) == *count;
 
                     if !is_every_correct_found {
                         return TileState::Present;
@@ -228,16 +579,47 @@ pub fn hint_tile_state(
     }
 }
 
+/// Known information a guess goes against, the first one found.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Contradiction {
+    // The letter was already found not to be in the word
+    AbsentLetter(char),
+    // The letter was already found in this position, but the guess has something else there
+    MissingCorrect(char, usize),
+}
+
+/// Checks a full guess against what the earlier guesses revealed, without looking at the word.
+pub fn find_contradiction(
+    guess: &[(char, TileState)],
+    guess_index: usize,
+    states: &[KnownStates],
+    counts: &[KnownCounts],
+) -> Option<Contradiction> {
+    let missing_correct = states[guess_index]
+        .iter()
+        .find(|(character, index, state)| {
+            *state == CharacterState::Correct
+                && guess.get(*index).map(|(c, _)| c) != Some(character)
+        })
+        .map(|(character, index, _)| Contradiction::MissingCorrect(character, index));
+
+    missing_correct.or_else(|| {
+        guess
+            .iter()
+            .find(|(character, _)| {
+                counts[guess_index].get(character) == Some(&CharacterCount::Exactly(0))
+            })
+            .map(|(character, _)| Contradiction::AbsentLetter(*character))
+    })
+}
+
 pub fn keyboard_tile_state(
     key: &char,
     current_guess: usize,
     states: &[KnownStates],
     counts: &[KnownCounts],
 ) -> TileState {
-    let is_correct = states[current_guess]
-        .iter()
-        .any(|((c, _index), state)| c == key && state == &CharacterState::Correct);
-    if is_correct {
+    if states[current_guess].correct_count(*key) > 0 {
         return TileState::Correct;
     }
 
@@ -258,23 +640,47 @@ pub fn keyboard_tile_state(
     }
 }
 
+/// Keyboard colors taken straight from already colored guesses, for when there's no word to work
+/// them out from. A letter seen in the right place anywhere is correct, like on the regular keyboard.
+pub fn keyboard_from_guesses(guesses: &[Vec<(char, TileState)>]) -> HashMap<char, KeyState> {
+    let rank = |state: TileState| match state {
+        TileState::Correct => 3,
+        TileState::Present => 2,
+        TileState::Absent => 1,
+        TileState::Unknown => 0,
+    };
+
+    let mut keyboard = HashMap::new();
+    for (character, state) in guesses.iter().flatten() {
+        let best = keyboard.entry(*character).or_insert(TileState::Unknown);
+        if rank(*state) > rank(*best) {
+            *best = *state;
+        }
+    }
+
+    keyboard
+        .into_iter()
+        .map(|(character, state)| (character, KeyState::Single(state)))
+        .collect()
+}
+
+/// Colors the guess and records what it reveals. Returns for each tile whether its color
+/// was news, as opposed to already following from the earlier guesses.
 pub fn update_known_information(
     states: &mut [KnownStates],
     counts: &mut [KnownCounts],
     guess: &mut [(char, TileState)],
     guess_index: usize,
     word: &[char],
-    max_guesses: usize,
-) {
-    for (index, (character, _)) in guess.iter().enumerate() {
-        let known = states[guess_index]
-            .entry((*character, index))
-            .or_insert(CharacterState::Unknown);
+) -> Vec<bool> {
+    let mut already_known = guess.to_vec();
+    update_guess_tile_states(&mut already_known, guess_index, states, counts);
 
+    for (index, (character, _)) in guess.iter().enumerate() {
         if word[index] == *character {
-            *known = CharacterState::Correct;
+            states[guess_index].insert(*character, index, CharacterState::Correct);
         } else {
-            *known = CharacterState::Absent;
+            states[guess_index].insert(*character, index, CharacterState::Absent);
 
             if let Some(updated_count) = known_count(character, guess_index, guess, counts, word) {
                 counts[guess_index].insert(*character, updated_count);
@@ -282,12 +688,375 @@ pub fn update_known_information(
         }
     }
 
-    // Copy the previous knowledge to the next guess
-    if guess_index < max_guesses - 1 {
-        let next = guess_index + 1;
-        states[next] = states[guess_index].clone();
+    // Copy the previous knowledge to the next guess, the last row has nowhere to copy to
+    let next = guess_index + 1;
+    if next < states.len() && next < counts.len() {
+        states[next] = states[guess_index];
         counts[next] = counts[guess_index].clone();
     }
 
     update_guess_tile_states(guess, guess_index, states, counts);
+
+    guess
+        .iter()
+        .zip(already_known)
+        .map(|((_, state), (_, known))| *state != known)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_GUESSES: usize = 6;
+
+    fn empty_knowledge() -> (Vec<KnownStates>, Vec<KnownCounts>) {
+        (
+            vec![KnownStates::default(); MAX_GUESSES],
+            vec![HashMap::new(); MAX_GUESSES],
+        )
+    }
+
+    fn guess(word: &str) -> Vec<(char, TileState)> {
+        word.chars().map(|c| (c, TileState::Unknown)).collect()
+    }
+
+    fn states(guess: &[(char, TileState)]) -> Vec<TileState> {
+        guess.iter().map(|(_, state)| *state).collect()
+    }
+
+    #[test]
+    fn streak_title_names_the_word_length_and_list() {
+        assert_eq!(
+            streak_title(Language::Finnish, "Sanuli", 5, WordList::Common, 0),
+            "Sanuli 5 · Tavallinen"
+        );
+        assert_eq!(
+            streak_title(Language::Finnish, "Sanuli", 6, WordList::Full, 3),
+            "Sanuli 6 · Vaikea — Putki: 3"
+        );
+        assert_eq!(
+            streak_title(Language::Finnish, "Neluli", 5, WordList::Easy, 12),
+            "Neluli 5 · Helppo — Putki: 12"
+        );
+        assert_eq!(
+            streak_title(Language::English, "Sanuli", 5, WordList::Full, 2),
+            "Sanuli 5 · Hard — Streak: 2"
+        );
+    }
+
+    fn chars(word: &str) -> Vec<char> {
+        word.chars().collect()
+    }
+
+    #[test]
+    fn update_known_information_marks_tiles() {
+        let (mut known_states, mut known_counts) = empty_knowledge();
+        let mut guess = guess("KOIRA");
+
+        update_known_information(
+            &mut known_states,
+            &mut known_counts,
+            &mut guess,
+            0,
+            &chars("KARIT"),
+        );
+
+        assert_eq!(
+            states(&guess),
+            vec![
+                TileState::Correct,
+                TileState::Absent,
+                TileState::Present,
+                TileState::Present,
+                TileState::Present,
+            ]
+        );
+    }
+
+    #[test]
+    fn update_known_information_copies_knowledge_to_next_guess() {
+        let (mut known_states, mut known_counts) = empty_knowledge();
+        let mut guess = guess("KOIRA");
+
+        update_known_information(
+            &mut known_states,
+            &mut known_counts,
+            &mut guess,
+            0,
+            &chars("KARIT"),
+        );
+
+        assert_eq!(known_states[1], known_states[0]);
+        assert_eq!(known_counts[1], known_counts[0]);
+        assert!(known_states[2].is_empty());
+    }
+
+    #[test]
+    fn only_tiles_that_reveal_something_are_new() {
+        let (mut known_states, mut known_counts) = empty_knowledge();
+        let word = chars("KARIT");
+
+        let first = update_known_information(
+            &mut known_states,
+            &mut known_counts,
+            &mut guess("KOIRA"),
+            0,
+            &word,
+        );
+        assert_eq!(first, vec![true; 5]);
+
+        // The first guess already showed K in place and A present but elsewhere
+        let second = update_known_information(
+            &mut known_states,
+            &mut known_counts,
+            &mut guess("KIRJA"),
+            1,
+            &word,
+        );
+        assert_eq!(second, vec![false, true, true, true, false]);
+    }
+
+    #[test]
+    fn duplicate_letters_reveal_exact_count() {
+        let (mut known_states, mut known_counts) = empty_knowledge();
+        let mut guess = guess("SIKSI");
+
+        update_known_information(
+            &mut known_states,
+            &mut known_counts,
+            &mut guess,
+            0,
+            &chars("KISSA"),
+        );
+
+        assert_eq!(
+            states(&guess),
+            vec![
+                TileState::Present,
+                TileState::Correct,
+                TileState::Present,
+                TileState::Correct,
+                TileState::Absent,
+            ]
+        );
+        assert_eq!(known_counts[0].get(&'I'), Some(&CharacterCount::Exactly(1)));
+        assert_eq!(known_counts[0].get(&'S'), Some(&CharacterCount::AtLeast(2)));
+    }
+
+    #[test]
+    fn duplicate_letters_only_highlight_once() {
+        let (mut known_states, mut known_counts) = empty_knowledge();
+        let mut guess = guess("AAAAA");
+
+        update_known_information(
+            &mut known_states,
+            &mut known_counts,
+            &mut guess,
+            0,
+            &chars("KOIRA"),
+        );
+
+        assert_eq!(
+            states(&guess),
+            vec![
+                TileState::Absent,
+                TileState::Absent,
+                TileState::Absent,
+                TileState::Absent,
+                TileState::Correct,
+            ]
+        );
+    }
+
+    #[test]
+    fn letters_round_trip_through_chars() {
+        for key in ALLOWED_KEYS {
+            let letter = Letter::try_from(key).unwrap();
+            assert_eq!(char::from(letter), key);
+            assert_eq!(letter.to_string(), key.to_string());
+        }
+        assert_eq!(Letter::all().count(), ALLOWED_KEYS.len());
+        assert!(Letter::try_from('Ü').is_err());
+        assert!(Letter::try_from('k').is_err());
+    }
+
+    #[test]
+    fn known_states_remember_the_latest_state() {
+        let mut known = KnownStates::default();
+        known.insert('S', 2, CharacterState::Absent);
+        known.insert('S', 3, CharacterState::Correct);
+        known.insert('S', 2, CharacterState::Correct);
+        known.insert('Ü', 0, CharacterState::Correct);
+
+        assert_eq!(known.get('S', 2), Some(CharacterState::Correct));
+        assert!(known.get('S', 4).is_none());
+        assert!(known.get('Ü', 0).is_none());
+        assert_eq!(known.correct_count('S'), 2);
+        assert_eq!(known.iter().count(), 2);
+
+        known.insert('S', 2, CharacterState::Unknown);
+        known.insert('S', 3, CharacterState::Unknown);
+        assert!(known.is_empty());
+    }
+
+    #[test]
+    fn last_row_keeps_its_knowledge_to_itself() {
+        // Nine rows like a Neluli board, and a single row like the solver uses
+        for rows in [9, 1] {
+            let mut known_states = vec![KnownStates::default(); rows];
+            let mut known_counts = vec![HashMap::new(); rows];
+            let mut guess = guess("KOIRA");
+
+            update_known_information(
+                &mut known_states,
+                &mut known_counts,
+                &mut guess,
+                rows - 1,
+                &chars("KARIT"),
+            );
+
+            assert_eq!(states(&guess)[0], TileState::Correct);
+            assert_eq!(known_states.len(), rows);
+            assert!(known_states[..rows - 1].iter().all(KnownStates::is_empty));
+        }
+    }
+
+    #[test]
+    fn known_count_keeps_an_exact_count() {
+        let (_, mut known_counts) = empty_knowledge();
+        let word = chars("KISSA");
+        let count = |character, row: &str, counts: &[KnownCounts]| {
+            known_count(&character, 0, &guess(row), counts, &word)
+        };
+
+        assert_eq!(
+            count('B', "BBBBB", &known_counts),
+            Some(CharacterCount::Exactly(0))
+        );
+        assert_eq!(
+            count('S', "SSSAA", &known_counts),
+            Some(CharacterCount::Exactly(2))
+        );
+        assert_eq!(
+            count('S', "SAAAA", &known_counts),
+            Some(CharacterCount::AtLeast(1))
+        );
+
+        known_counts[0].insert('S', CharacterCount::Exactly(2));
+        assert!(count('S', "SSSAA", &known_counts).is_none());
+        assert!(count('S', "SAAAA", &known_counts).is_none());
+    }
+
+    #[test]
+    fn hint_tile_state_uses_known_information() {
+        let (mut known_states, mut known_counts) = empty_knowledge();
+        let mut guess = guess("SIKSI");
+
+        update_known_information(
+            &mut known_states,
+            &mut known_counts,
+            &mut guess,
+            0,
+            &chars("KISSA"),
+        );
+
+        let hint =
+            |character, index| hint_tile_state(character, index, 1, &known_states, &known_counts);
+
+        assert_eq!(hint('I', 1), TileState::Correct);
+        // Every I has been found already
+        assert_eq!(hint('I', 0), TileState::Absent);
+        assert_eq!(hint('S', 1), TileState::Present);
+        assert_eq!(hint('K', 0), TileState::Present);
+        assert_eq!(hint('K', 2), TileState::Absent);
+        assert_eq!(hint('B', 0), TileState::Unknown);
+    }
+
+    #[test]
+    fn keyboard_tile_state_prefers_correct() {
+        let (mut known_states, mut known_counts) = empty_knowledge();
+        let mut guess = guess("SIKSB");
+
+        update_known_information(
+            &mut known_states,
+            &mut known_counts,
+            &mut guess,
+            0,
+            &chars("KISSA"),
+        );
+
+        let key = |character| keyboard_tile_state(&character, 1, &known_states, &known_counts);
+
+        assert_eq!(key('S'), TileState::Correct);
+        assert_eq!(key('I'), TileState::Correct);
+        assert_eq!(key('K'), TileState::Present);
+        assert_eq!(key('B'), TileState::Absent);
+        assert_eq!(key('A'), TileState::Unknown);
+    }
+
+    #[test]
+    fn contradictions_point_out_ignored_information() {
+        let (mut known_states, mut known_counts) = empty_knowledge();
+        update_known_information(
+            &mut known_states,
+            &mut known_counts,
+            &mut guess("SAUNA"),
+            0,
+            &chars("TALVI"),
+        );
+
+        let contradiction =
+            |word: &str| find_contradiction(&guess(word), 1, &known_states, &known_counts);
+
+        assert!(contradiction("TALVI").is_none());
+        assert!(contradiction("KALJA").is_none());
+        assert_eq!(
+            contradiction("MUSTA"),
+            Some(Contradiction::MissingCorrect('A', 1))
+        );
+        assert_eq!(
+            contradiction("TASKU"),
+            Some(Contradiction::AbsentLetter('S'))
+        );
+    }
+
+    #[test]
+    fn weak_guesses_keep_most_of_the_words() {
+        let row = |before, after| GuessAnalysis {
+            guess: "SAUNA".to_owned(),
+            before,
+            after,
+        };
+
+        assert!(row(100, 60).is_weak());
+        assert!(!row(100, 50).is_weak());
+        // Pinning the word down is never weak, even when it was already the last one
+        assert!(!row(1, 1).is_weak());
+    }
+
+    fn daily_history(guesses: &[Option<usize>]) -> DailyHistory {
+        guesses.iter().copied().enumerate().collect()
+    }
+
+    #[test]
+    fn daily_percentile_counts_only_strictly_worse_dailies() {
+        // Today is the last one, solved in 3
+        let history = daily_history(&[Some(2), Some(3), Some(4), Some(5), None, Some(3)]);
+        assert_eq!(daily_percentile(&history, 5), Some(60));
+
+        let history = daily_history(&[Some(3); 6]);
+        assert_eq!(daily_percentile(&history, 5), Some(0));
+
+        let history = daily_history(&[None, None, None, None, None, Some(6)]);
+        assert_eq!(daily_percentile(&history, 5), Some(100));
+    }
+
+    #[test]
+    fn daily_percentile_needs_enough_dailies() {
+        let history = daily_history(&[Some(4), Some(5), Some(6), None, Some(2)]);
+        assert_eq!(daily_percentile(&history, 4), None);
+        assert_eq!(daily_percentile(&history, 10), None);
+        assert_eq!(daily_percentile(&DailyHistory::new(), 0), None);
+    }
 }
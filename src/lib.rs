@@ -0,0 +1,15 @@
+pub mod config;
+pub mod demo;
+pub mod game;
+pub mod manager;
+#[cfg(feature = "migration")]
+pub mod migration;
+pub mod neluli;
+pub mod persistence;
+pub mod sanuli;
+pub mod share;
+pub mod solver;
+pub mod starters;
+pub mod strings;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tooling;
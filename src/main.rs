@@ -1,72 +1,250 @@
-use std::collections::HashMap;
-use wasm_bindgen::{prelude::Closure, JsCast};
-use web_sys::{window, Window};
+use chrono::Local;
+use std::cell::Cell;
+use gloo_timers::callback::{Interval, Timeout};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use web_sys::{window, AddEventListenerOptions, StorageEvent, Window};
 use yew::prelude::*;
 
 mod components;
-mod game;
-mod manager;
-mod neluli;
-mod sanuli;
+mod pwa;
+
+use sanuli::game::{Letter, SharedGuesses};
+use sanuli::manager;
+use sanuli::strings::{self, Language, Text};
 
 use components::{
+    banner::Banner,
     board::Board,
+    demo::Demo,
     header::Header,
     keyboard::Keyboard,
-    modal::{HelpModal, MenuModal},
+    modal::{HelpModal, MenuKey, MenuKeyPress, MenuModal, MenuTab},
+    replay::Replay,
+    analysis::Analysis,
+    word_entry::WordEntry,
+};
+use manager::{
+    EmojiSet, GameMode, KeyboardLayout, Manager, StorageFailure, Theme, WordLanguage, WordList,
 };
-use manager::{GameMode, KeyState, Manager, Theme, WordList};
 
-const ALLOWED_KEYS: [char; 28] = [
-    'Q', 'W', 'E', 'R', 'T', 'Y', 'U', 'I', 'O', 'P', 'A', 'S', 'D', 'F', 'G', 'H', 'J', 'K', 'L',
-    'Ö', 'Ä', 'Z', 'X', 'C', 'V', 'B', 'N', 'M',
-];
+const DATE_CHECK_INTERVAL_MS: u32 = 60_000;
+// Storage is written once the player has been idle for this long
+const SAVE_DELAY_MS: u32 = 500;
+// Long enough for the slowest of the slide animations of a new word
+const SLIDE_ANIMATION_MS: u32 = 1200;
+// Enter and ARVAA pressed together count as one guess
+const GUESS_DEBOUNCE_MS: u32 = 100;
+// After the final guess the debounce also holds off moving on, so that the end of the game is seen
+const GAME_END_DEBOUNCE_MS: u32 = 500;
+// First time visitors who do nothing for this long are shown the demo game
+const DEMO_IDLE_MS: u32 = 10_000;
+// Time to correct a full row before it's guessed automatically
+const AUTO_SUBMIT_MS: u32 = 300;
+
+// What an entry pushed to the browser history stands for, Back closes the latest of them
+#[derive(Clone, Copy, PartialEq)]
+enum HistoryEntry {
+    Modal,
+    SharedGame,
+}
 
 pub enum Msg {
     KeyPress(char),
+    // A starter word tapped by a beginner, typed in letter by letter
+    FillGuess(&'static str),
     Backspace,
     Enter,
     Guess,
+    GuessSettled,
+    AutoSubmit,
     NextWord,
     ToggleHelp,
     ToggleMenu,
+    ToggleReplay,
+    ToggleAnalysis,
+    CycleTileMarker(usize, usize, usize),
+    StartDemo,
+    DismissDemo,
+    MenuKey(MenuKey),
+    NumberKey(u32),
+    ChangeMenuTab(MenuTab),
     ChangeGameMode(GameMode),
     ChangePreviousGameMode,
     ChangeWordLength(usize),
     ChangeWordList(WordList),
+    ChangeWordLanguage(WordLanguage),
     ChangeAllowProfanities(bool),
+    ChangeWeightedWordSelection(bool),
     ChangeTheme(Theme),
+    ChangeKeyboardLayout(KeyboardLayout),
+    ChangeLanguage(Language),
+    ChangeDetailedKeyColors(bool),
+    ChangeShowUnusedLetters(bool),
+    ChangeHighlightNewInformation(bool),
+    ChangeHideTypingHints(bool),
+    ChangeWarnContradictions(bool),
+    ChangeShowCandidates(bool),
+    ChangeBeginnerHelp(bool),
+    ChangeHideFinishedWord(bool),
+    ChangeAutoSubmit(bool),
+    ChangeEmojiSet(EmojiSet),
+    ChangeShareSolveTime(bool),
+    ChangeAccessibleShare(bool),
+    EnableSharerTag(bool),
+    ChangeSharerTag(String),
     ShareEmojis,
+    ShareEmojiCounts,
     ShareLink,
+    ShareProgress,
     RevealHiddenTiles,
     ResetGame,
+    CheckDate,
+    Save,
+    PrepareNextWord,
+    AnimationDone,
+    DismissStorageWarning,
+    RetryStorage,
+    CleanupStorage,
+    StorageChanged(String),
+    VisibilityChanged(bool),
+    HistoryBack,
+    ConnectionChanged(bool),
+    InstallabilityChanged,
+    InstallApp,
+    CopyDrawnWords,
+    ClearAllData,
+    ResetSettings,
+    NewGame,
+    ConfirmGameModeChange,
+    CancelGameModeChange,
+    ReturnToDaily,
+    ReturnToGame,
+    ContinueGame((GameMode, WordList, usize)),
+    ContinueWithNewGame((GameMode, WordList, usize)),
 }
 
 pub struct App {
     manager: Manager,
     is_help_visible: bool,
     is_menu_visible: bool,
+    menu_tab: MenuTab,
+    menu_key_press: Option<MenuKeyPress>,
     is_emojis_copied: bool,
+    is_counts_copied: bool,
     is_link_copied: bool,
+    // The guesses being replayed, the replay closes once the board no longer shows them
+    replay: Option<SharedGuesses>,
+    // The guesses being analysed, closed the same way as the replay
+    analysis: Option<SharedGuesses>,
+    is_demo_visible: bool,
     keyboard_listener: Option<Closure<dyn Fn(KeyboardEvent)>>,
+    storage_listener: Option<Closure<dyn Fn(StorageEvent)>>,
+    visibility_listener: Option<Closure<dyn Fn()>>,
+    unload_listener: Option<Closure<dyn Fn()>>,
+    popstate_listener: Option<Closure<dyn Fn()>>,
+    online_listener: Option<Closure<dyn Fn()>>,
+    offline_listener: Option<Closure<dyn Fn()>>,
+    install_listener: Option<Closure<dyn Fn()>>,
+    is_online: bool,
+    is_installable: bool,
+    // Renders so far, logged in debug mode to check that messages changing nothing don't render
+    render_count: Cell<usize>,
+    // Entries pushed on top of the page's own, the latest last
+    history: Vec<HistoryEntry>,
+    // Going back to drop entries of things closed in the app fires popstate too
+    ignored_pops: usize,
+    was_shared: bool,
+    save_timeout: Option<Timeout>,
+    prepare_timeout: Option<Timeout>,
+    animation_timeout: Option<Timeout>,
+    guess_timeout: Option<Timeout>,
+    auto_submit_timeout: Option<Timeout>,
+    demo_timeout: Option<Timeout>,
+    _date_check_interval: Interval,
 }
 
 impl Component for App {
     type Message = Msg;
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
+        let manager = Manager::new();
+        // Show the instructions automatically for first time visitors
+        let is_help_visible = !manager.has_seen_help;
+
+        // Periodically check if the date has changed while the page stays open
+        let link = ctx.link().clone();
+        let date_check_interval =
+            Interval::new(DATE_CHECK_INTERVAL_MS, move || link.send_message(Msg::CheckDate));
+
+        // Visitors who arrived through a shared link already have something to play
+        let link = ctx.link().clone();
+        let demo_timeout = (manager.is_first_visit()
+            && manager.current_game_mode != GameMode::Shared)
+            .then(|| Timeout::new(DEMO_IDLE_MS, move || link.send_message(Msg::StartDemo)));
+
+        // A shared link opened from elsewhere gets no entry, Back returns to where it was shared
+        let was_shared = manager.current_game_mode == GameMode::Shared;
+
         Self {
-            manager: Manager::new(),
-            is_help_visible: false,
+            manager,
+            is_help_visible,
             is_menu_visible: false,
+            menu_tab: MenuTab::default(),
+            menu_key_press: None,
             is_emojis_copied: false,
+            is_counts_copied: false,
             is_link_copied: false,
+            replay: None,
+            analysis: None,
+            is_demo_visible: false,
             keyboard_listener: None,
+            storage_listener: None,
+            visibility_listener: None,
+            unload_listener: None,
+            popstate_listener: None,
+            online_listener: None,
+            offline_listener: None,
+            install_listener: None,
+            is_online: window().is_none_or(|window| window.navigator().on_line()),
+            is_installable: pwa::is_installable(),
+            render_count: Cell::new(0),
+            history: Vec::new(),
+            ignored_pops: 0,
+            was_shared,
+            save_timeout: None,
+            prepare_timeout: None,
+            animation_timeout: None,
+            guess_timeout: None,
+            auto_submit_timeout: None,
+            demo_timeout,
+            _date_check_interval: date_check_interval,
         }
     }
 
     fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        // Draw the next word once the current one is on screen, not when it's asked for
+        if self.prepare_timeout.is_none() && !self.manager.is_next_word_prepared() {
+            let link = ctx.link().clone();
+            self.prepare_timeout = Some(Timeout::new(0, move || {
+                link.send_message(Msg::PrepareNextWord)
+            }));
+        }
+
+        // The slide-in class stays on until the animation is over, then the board settles down
+        let is_reset = self.manager.game.as_ref().is_some_and(|game| game.is_reset());
+        if !is_reset {
+            // Dropping the timeout of a game that was switched away from cancels it
+            self.animation_timeout = None;
+        } else if self.animation_timeout.is_none() {
+            let link = ctx.link().clone();
+            self.animation_timeout = Some(Timeout::new(SLIDE_ANIMATION_MS, move || {
+                link.send_message(Msg::AnimationDone)
+            }));
+        }
+
+        self.sync_history();
+
         if !first_render {
             return;
         }
@@ -74,9 +252,32 @@ impl Component for App {
         let window: Window = window().expect("window not available");
 
         let cb = ctx.link().batch_callback(|e: KeyboardEvent| {
-            if e.key().chars().count() == 1 {
+            if e.ctrl_key() || e.alt_key() || e.meta_key() {
+                return None;
+            }
+
+            let menu_key = match e.key().as_str() {
+                "ArrowUp" => Some(MenuKey::Up),
+                "ArrowDown" => Some(MenuKey::Down),
+                "ArrowLeft" => Some(MenuKey::Left),
+                "ArrowRight" => Some(MenuKey::Right),
+                _ => None,
+            };
+
+            if let Some(menu_key) = menu_key {
+                Some(Msg::MenuKey(menu_key))
+            } else if e.key() == "?" {
+                e.prevent_default();
+                Some(Msg::ToggleHelp)
+            } else if e.key() == "M" && e.shift_key() {
+                e.prevent_default();
+                Some(Msg::ToggleMenu)
+            } else if let Some(digit) = e.key().chars().next().and_then(|c| c.to_digit(10)) {
+                Some(Msg::NumberKey(digit))
+            } else if e.key().chars().count() == 1 {
+                // Letters of other word languages are left for the game to turn down
                 let key = e.key().to_uppercase().chars().next().unwrap();
-                if ALLOWED_KEYS.contains(&key) && !e.ctrl_key() && !e.alt_key() && !e.meta_key() {
+                if Letter::try_from(key).is_ok() {
                     e.prevent_default();
                     Some(Msg::KeyPress(key))
                 } else {
@@ -96,26 +297,225 @@ impl Component for App {
         let listener =
             Closure::<dyn Fn(KeyboardEvent)>::wrap(Box::new(move |e: KeyboardEvent| cb.emit(e)));
 
+        // Not passive, otherwise the browser ignores prevent_default for Enter and Backspace
+        let options = AddEventListenerOptions::new();
+        options.set_passive(false);
         window
-            .add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref())
+            .add_event_listener_with_callback_and_add_event_listener_options(
+                "keydown",
+                listener.as_ref().unchecked_ref(),
+                &options,
+            )
             .unwrap();
         self.keyboard_listener = Some(listener);
+
+        // Other tabs playing at the same time save over our storage
+        let cb = ctx
+            .link()
+            .batch_callback(|e: StorageEvent| e.key().map(Msg::StorageChanged));
+
+        let listener =
+            Closure::<dyn Fn(StorageEvent)>::wrap(Box::new(move |e: StorageEvent| cb.emit(e)));
+
+        window
+            .add_event_listener_with_callback("storage", listener.as_ref().unchecked_ref())
+            .unwrap();
+        self.storage_listener = Some(listener);
+
+        // Time spent on other tabs doesn't count towards the solve time
+        let document = window.document().expect("document not available");
+        let cb = ctx.link().callback(|_: ()| {
+            let document = web_sys::window().and_then(|window| window.document());
+            Msg::VisibilityChanged(document.is_some_and(|document| document.hidden()))
+        });
+
+        let listener = Closure::<dyn Fn()>::wrap(Box::new(move || cb.emit(())));
+
+        document
+            .add_event_listener_with_callback("visibilitychange", listener.as_ref().unchecked_ref())
+            .unwrap();
+        self.visibility_listener = Some(listener);
+
+        // Save whatever is still waiting when the page is closed
+        let cb = ctx.link().callback(|_: ()| Msg::Save);
+        let listener = Closure::<dyn Fn()>::wrap(Box::new(move || cb.emit(())));
+
+        window
+            .add_event_listener_with_callback("beforeunload", listener.as_ref().unchecked_ref())
+            .unwrap();
+        self.unload_listener = Some(listener);
+
+        // Back closes the modal or leaves the shared game instead of the whole page
+        let cb = ctx.link().callback(|_: ()| Msg::HistoryBack);
+        let listener = Closure::<dyn Fn()>::wrap(Box::new(move || cb.emit(())));
+
+        window
+            .add_event_listener_with_callback("popstate", listener.as_ref().unchecked_ref())
+            .unwrap();
+        self.popstate_listener = Some(listener);
+
+        // The game works offline, only links out of it don't
+        let cb = ctx.link().callback(|_: ()| Msg::ConnectionChanged(true));
+        let listener = Closure::<dyn Fn()>::wrap(Box::new(move || cb.emit(())));
+
+        window
+            .add_event_listener_with_callback("online", listener.as_ref().unchecked_ref())
+            .unwrap();
+        self.online_listener = Some(listener);
+
+        let cb = ctx.link().callback(|_: ()| Msg::ConnectionChanged(false));
+        let listener = Closure::<dyn Fn()>::wrap(Box::new(move || cb.emit(())));
+
+        window
+            .add_event_listener_with_callback("offline", listener.as_ref().unchecked_ref())
+            .unwrap();
+        self.offline_listener = Some(listener);
+
+        // The browser offers to install the app some time after it has loaded, if at all
+        let cb = ctx.link().callback(|_: ()| Msg::InstallabilityChanged);
+        let listener = Closure::<dyn Fn()>::wrap(Box::new(move || cb.emit(())));
+
+        window
+            .add_event_listener_with_callback(
+                pwa::INSTALLABLE_EVENT,
+                listener.as_ref().unchecked_ref(),
+            )
+            .unwrap();
+        self.install_listener = Some(listener);
     }
 
     fn destroy(&mut self, _: &Context<Self>) {
-        // Remove the keyboard listener
+        let window: Window = window().expect("window not available");
+
+        // Remove the listeners added on the first render
         if let Some(listener) = self.keyboard_listener.take() {
-            let window: Window = window().expect("window not available");
             window
                 .remove_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref())
                 .unwrap();
         }
+        if let Some(listener) = self.storage_listener.take() {
+            window
+                .remove_event_listener_with_callback("storage", listener.as_ref().unchecked_ref())
+                .unwrap();
+        }
+        if let Some(listener) = self.visibility_listener.take() {
+            let document = window.document().expect("document not available");
+            document
+                .remove_event_listener_with_callback(
+                    "visibilitychange",
+                    listener.as_ref().unchecked_ref(),
+                )
+                .unwrap();
+        }
+        if let Some(listener) = self.unload_listener.take() {
+            window
+                .remove_event_listener_with_callback(
+                    "beforeunload",
+                    listener.as_ref().unchecked_ref(),
+                )
+                .unwrap();
+        }
+        if let Some(listener) = self.popstate_listener.take() {
+            window
+                .remove_event_listener_with_callback("popstate", listener.as_ref().unchecked_ref())
+                .unwrap();
+        }
+        if let Some(listener) = self.online_listener.take() {
+            window
+                .remove_event_listener_with_callback("online", listener.as_ref().unchecked_ref())
+                .unwrap();
+        }
+        if let Some(listener) = self.offline_listener.take() {
+            window
+                .remove_event_listener_with_callback("offline", listener.as_ref().unchecked_ref())
+                .unwrap();
+        }
+        if let Some(listener) = self.install_listener.take() {
+            window
+                .remove_event_listener_with_callback(
+                    pwa::INSTALLABLE_EVENT,
+                    listener.as_ref().unchecked_ref(),
+                )
+                .unwrap();
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        // Anything the player does cancels the demo, the press that closes it does nothing else
+        let is_interaction = !matches!(
+            msg,
+            Msg::GuessSettled
+                | Msg::AutoSubmit
+                | Msg::CheckDate
+                | Msg::Save
+                | Msg::PrepareNextWord
+                | Msg::AnimationDone
+                | Msg::StorageChanged(_)
+                | Msg::VisibilityChanged(_)
+                | Msg::HistoryBack
+                | Msg::ConnectionChanged(_)
+                | Msg::InstallabilityChanged
+                | Msg::StartDemo
+                | Msg::DismissDemo
+        );
+        // Backspace, opening the menu or anything else but typing cancels an automatic guess
+        if is_interaction && !matches!(msg, Msg::KeyPress(_)) {
+            self.auto_submit_timeout = None;
+        }
+        if is_interaction {
+            self.demo_timeout = None;
+            if self.is_demo_visible {
+                self.is_demo_visible = false;
+                return true;
+            }
+        }
+
         match msg {
-            Msg::KeyPress(c) => self.manager.push_character(c),
-            Msg::Backspace => self.manager.pop_character(),
+            // While the menu is open the keyboard drives the menu instead of the game
+            Msg::KeyPress(c) if self.is_menu_visible => {
+                if c == 'M' {
+                    ctx.link().send_message(Msg::ToggleMenu);
+                }
+                return false;
+            }
+            Msg::Backspace if self.is_menu_visible => return false,
+            Msg::Enter if self.is_menu_visible => {
+                ctx.link().send_message(Msg::MenuKey(MenuKey::Enter));
+                return false;
+            }
+            Msg::KeyPress(c) => {
+                if !self.manager.push_character(c) {
+                    return false;
+                }
+
+                if self.manager.is_auto_submit_ready() {
+                    let link = ctx.link().clone();
+                    self.auto_submit_timeout = Some(Timeout::new(AUTO_SUBMIT_MS, move || {
+                        link.send_message(Msg::AutoSubmit)
+                    }));
+                }
+            }
+            Msg::FillGuess(word) => {
+                let mut is_changed = false;
+                for c in word.chars() {
+                    is_changed |= self.manager.push_character(c);
+                }
+                if !is_changed {
+                    return false;
+                }
+            }
+            Msg::CycleTileMarker(board, row, column) => {
+                if !self.manager.cycle_tile_marker(board, row, column) {
+                    return false;
+                }
+            }
+            Msg::Backspace => {
+                if !self.manager.pop_character() {
+                    return false;
+                }
+            }
+            // Would start the next word right after the final guess of the game
+            Msg::Enter if self.guess_timeout.is_some() => return false,
             Msg::Enter => {
                 let link = ctx.link();
 
@@ -131,20 +531,158 @@ impl Component for App {
                     }
                 }
             }
-            Msg::Guess => self.manager.submit_guess(),
+            Msg::Guess if self.guess_timeout.is_some() => return false,
+            Msg::Guess => {
+                let was_guessing =
+                    self.manager.game.as_ref().is_some_and(|game| game.is_guessing());
+                self.manager.submit_guess();
+                let is_ended = was_guessing
+                    && self.manager.game.as_ref().is_some_and(|game| !game.is_guessing());
+
+                let delay = if is_ended { GAME_END_DEBOUNCE_MS } else { GUESS_DEBOUNCE_MS };
+                let link = ctx.link().clone();
+                self.guess_timeout = Some(Timeout::new(delay, move || {
+                    link.send_message(Msg::GuessSettled)
+                }));
+            }
+            Msg::GuessSettled => {
+                self.guess_timeout = None;
+                return false;
+            }
+            Msg::AutoSubmit => {
+                self.auto_submit_timeout = None;
+                if !self.manager.is_auto_submit_ready() {
+                    return false;
+                }
+                ctx.link().send_message(Msg::Guess);
+                return false;
+            }
+            Msg::NextWord if self.guess_timeout.is_some() => return false,
             Msg::NextWord => {
                 self.manager.next_word();
                 self.is_emojis_copied = false;
+                self.is_counts_copied = false;
                 self.is_link_copied = false;
             }
             Msg::ToggleHelp => {
+                if self.is_help_visible {
+                    self.manager.mark_help_seen();
+                }
                 self.is_help_visible = !self.is_help_visible;
                 self.is_menu_visible = false;
             }
+            Msg::StartDemo => {
+                self.demo_timeout = None;
+                self.is_demo_visible = true;
+            }
+            Msg::DismissDemo => {
+                if self.is_help_visible {
+                    self.manager.mark_help_seen();
+                }
+                self.is_demo_visible = false;
+                self.is_help_visible = false;
+                self.is_menu_visible = false;
+            }
+            Msg::ToggleReplay => {
+                self.analysis = None;
+                self.replay = match (&self.replay, &self.manager.game) {
+                    (None, Some(game)) if !game.is_guessing() => {
+                        game.boards().first().map(|board| board.guesses.clone())
+                    }
+                    _ => None,
+                };
+            }
+            Msg::ToggleAnalysis => {
+                self.replay = None;
+                self.analysis = match (&self.analysis, &self.manager.game) {
+                    (None, Some(game)) if game.guess_analysis().is_some() => {
+                        game.boards().first().map(|board| board.guesses.clone())
+                    }
+                    _ => None,
+                };
+            }
+            Msg::HistoryBack => {
+                if self.ignored_pops > 0 {
+                    self.ignored_pops -= 1;
+                    return false;
+                }
+                match self.history.pop() {
+                    Some(HistoryEntry::Modal) => {
+                        if self.is_help_visible {
+                            self.manager.mark_help_seen();
+                        }
+                        self.is_help_visible = false;
+                        self.is_menu_visible = false;
+                        self.menu_key_press = None;
+                    }
+                    Some(HistoryEntry::SharedGame) => {
+                        if self.manager.current_game_mode == GameMode::Shared {
+                            self.manager.change_previous_game_mode();
+                        }
+                        self.was_shared = self.manager.current_game_mode == GameMode::Shared;
+                    }
+                    None => return false,
+                }
+            }
+            Msg::ConnectionChanged(is_online) => {
+                if self.is_online == is_online {
+                    return false;
+                }
+                self.is_online = is_online;
+            }
+            Msg::InstallabilityChanged => {
+                let is_installable = pwa::is_installable();
+                if self.is_installable == is_installable {
+                    return false;
+                }
+                self.is_installable = is_installable;
+            }
+            Msg::InstallApp => {
+                pwa::prompt_install();
+                self.is_installable = pwa::is_installable();
+            }
+            Msg::CopyDrawnWords => {
+                #[cfg(web_sys_unstable_apis)]
+                {
+                    use web_sys::Navigator;
+
+                    if let Some(drawn_words) = self.manager.drawn_words_text() {
+                        let window: Window = window().expect("window not available");
+                        let navigator: Navigator = window.navigator();
+                        let _promise = navigator.clipboard().write_text(drawn_words.as_str());
+                    }
+                }
+                return false;
+            }
             Msg::ToggleMenu => {
+                if self.is_help_visible {
+                    self.manager.mark_help_seen();
+                }
                 self.is_menu_visible = !self.is_menu_visible;
                 self.is_help_visible = false;
+                self.menu_key_press = None;
+            }
+            Msg::MenuKey(key) => {
+                if !self.is_menu_visible {
+                    return false;
+                }
+                let sequence = self.menu_key_press.map_or(0, |key_press| key_press.sequence + 1);
+                self.menu_key_press = Some(MenuKeyPress { key, sequence });
             }
+            Msg::NumberKey(digit) => {
+                let is_word_length_visible = !matches!(
+                    self.manager.current_game_mode,
+                    GameMode::DailyWord(_) | GameMode::Shared | GameMode::Hotseat
+                );
+                match digit {
+                    1 | 2 if self.is_menu_visible && is_word_length_visible => {
+                        ctx.link().send_message(Msg::ChangeWordLength(digit as usize + 4));
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+            Msg::ChangeMenuTab(tab) => self.menu_tab = tab,
             Msg::ChangeWordLength(new_length) => {
                 self.manager.change_word_length(new_length);
                 self.is_menu_visible = false;
@@ -160,9 +698,23 @@ impl Component for App {
                 self.is_menu_visible = false;
                 self.is_help_visible = false;
             }
+            Msg::ChangeWordLanguage(word_language) => {
+                self.manager.change_word_language(word_language);
+                self.is_menu_visible = false;
+                self.is_help_visible = false;
+            }
+            Msg::ConfirmGameModeChange => {
+                self.manager.confirm_pending_game_mode();
+                self.is_emojis_copied = false;
+                self.is_counts_copied = false;
+                self.is_link_copied = false;
+            }
+            Msg::CancelGameModeChange => self.manager.cancel_pending_game_mode(),
+            Msg::ChangePreviousGameMode if self.guess_timeout.is_some() => return false,
             Msg::ChangePreviousGameMode => {
                 self.manager.change_previous_game_mode();
                 self.is_emojis_copied = false;
+                self.is_counts_copied = false;
                 self.is_link_copied = false;
             }
             Msg::ChangeAllowProfanities(is_allowed) => {
@@ -170,7 +722,42 @@ impl Component for App {
                 self.is_menu_visible = false;
                 self.is_help_visible = false;
             }
+            Msg::ChangeWeightedWordSelection(is_weighted) => {
+                self.manager.change_weighted_word_selection(is_weighted)
+            }
             Msg::ChangeTheme(theme) => self.manager.change_theme(theme),
+            Msg::ChangeKeyboardLayout(keyboard_layout) => {
+                self.manager.change_keyboard_layout(keyboard_layout)
+            }
+            Msg::ChangeLanguage(language) => self.manager.change_language(language),
+            Msg::ChangeDetailedKeyColors(is_detailed) => {
+                self.manager.change_detailed_key_colors(is_detailed)
+            }
+            Msg::ChangeShowUnusedLetters(is_visible) => {
+                self.manager.change_show_unused_letters(is_visible)
+            }
+            Msg::ChangeHighlightNewInformation(is_highlighted) => {
+                self.manager.change_highlight_new_information(is_highlighted)
+            }
+            Msg::ChangeHideTypingHints(is_hidden) => self.manager.change_hide_typing_hints(is_hidden),
+            Msg::ChangeWarnContradictions(is_warned) => {
+                self.manager.change_warn_contradictions(is_warned)
+            }
+            Msg::ChangeShowCandidates(is_shown) => self.manager.change_show_candidates(is_shown),
+            Msg::ChangeBeginnerHelp(is_enabled) => self.manager.change_beginner_help(is_enabled),
+            Msg::ChangeHideFinishedWord(is_hidden) => {
+                self.manager.change_hide_finished_word(is_hidden)
+            }
+            Msg::ChangeAutoSubmit(is_enabled) => self.manager.change_auto_submit(is_enabled),
+            Msg::ChangeEmojiSet(emoji_set) => self.manager.change_emoji_set(emoji_set),
+            Msg::ChangeShareSolveTime(is_shared) => {
+                self.manager.change_share_solve_time(is_shared)
+            }
+            Msg::ChangeAccessibleShare(is_accessible) => {
+                self.manager.change_accessible_share(is_accessible)
+            }
+            Msg::EnableSharerTag(is_enabled) => self.manager.enable_sharer_tag(is_enabled),
+            Msg::ChangeSharerTag(tag) => self.manager.change_sharer_tag(&tag),
             Msg::ShareEmojis => {
                 #[cfg(web_sys_unstable_apis)]
                 {
@@ -183,6 +770,22 @@ impl Component for App {
                     }
                 }
                 self.is_emojis_copied = true;
+                self.is_counts_copied = false;
+                self.is_link_copied = false;
+            }
+            Msg::ShareEmojiCounts => {
+                #[cfg(web_sys_unstable_apis)]
+                {
+                    use web_sys::Navigator;
+
+                    if let Some(emojis) = self.manager.share_emoji_counts() {
+                        let window: Window = window().expect("window not available");
+                        let navigator: Navigator = window.navigator();
+                        let _promise = navigator.clipboard().write_text(emojis.as_str());
+                    }
+                }
+                self.is_counts_copied = true;
+                self.is_emojis_copied = false;
                 self.is_link_copied = false;
             }
             Msg::ShareLink => {
@@ -198,69 +801,285 @@ impl Component for App {
                 }
                 self.is_link_copied = true;
                 self.is_emojis_copied = false;
+                self.is_counts_copied = false;
+            }
+            Msg::ShareProgress => {
+                #[cfg(web_sys_unstable_apis)]
+                {
+                    use web_sys::Navigator;
+
+                    if let Some(link) = self.manager.share_progress_link() {
+                        let window: Window = window().expect("window not available");
+                        let navigator: Navigator = window.navigator();
+                        let _promise = navigator.clipboard().write_text(link.as_str());
+                    }
+                }
+                self.is_link_copied = true;
+                self.is_emojis_copied = false;
+                self.is_counts_copied = false;
             }
             Msg::RevealHiddenTiles => self.manager.reveal_hidden_tiles(),
             Msg::ResetGame => self.manager.reset_game(),
+            Msg::CheckDate => self.manager.check_date(),
+            Msg::PrepareNextWord => {
+                self.prepare_timeout = None;
+                self.manager.prepare_next_word();
+                return false;
+            }
+            Msg::AnimationDone => {
+                self.animation_timeout = None;
+                self.manager.clear_reset();
+            }
+            Msg::Save => {
+                self.save_timeout = None;
+                self.manager.flush();
+                return false;
+            }
+            Msg::DismissStorageWarning => self.manager.dismiss_storage_warning(),
+            Msg::RetryStorage => self.manager.retry_failed_saves(),
+            Msg::CleanupStorage => self.manager.cleanup_storage(),
+            Msg::StorageChanged(key) => self.manager.sync_external_change(&key),
+            Msg::VisibilityChanged(is_hidden) => {
+                if is_hidden {
+                    self.manager.pause_timer();
+                    // Mobile browsers may close a hidden page without an unload event
+                    self.manager.flush();
+                } else {
+                    self.manager.resume_timer();
+                }
+                return false;
+            }
+            Msg::ClearAllData => {
+                self.manager.clear_all_data();
+                self.is_menu_visible = false;
+            }
+            Msg::ResetSettings => self.manager.reset_settings(),
+            Msg::NewGame => {
+                self.manager.new_game();
+                self.is_emojis_copied = false;
+                self.is_counts_copied = false;
+                self.is_link_copied = false;
+                self.is_menu_visible = false;
+            }
+            Msg::ReturnToDaily => {
+                self.manager.dismiss_return_summary();
+                let today = Local::now().date_naive();
+                ctx.link().send_message(Msg::ChangeGameMode(GameMode::DailyWord(today)));
+            }
+            Msg::ReturnToGame => self.manager.continue_after_return(),
+            Msg::ContinueGame(game) => {
+                self.manager.continue_game(game);
+                self.is_emojis_copied = false;
+                self.is_counts_copied = false;
+                self.is_link_copied = false;
+                self.is_menu_visible = false;
+            }
+            Msg::ContinueWithNewGame(game) => {
+                self.manager.continue_game(game);
+                self.manager.new_game();
+                self.is_emojis_copied = false;
+                self.is_counts_copied = false;
+                self.is_link_copied = false;
+                self.is_menu_visible = false;
+            }
         };
 
+        // Replacing the timeout cancels the previous one, so saving waits for a quiet moment
+        if self.manager.has_unsaved_changes() {
+            let link = ctx.link().clone();
+            self.save_timeout = Some(Timeout::new(SAVE_DELAY_MS, move || {
+                link.send_message(Msg::Save)
+            }));
+        }
+
         true
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
+        if self.manager.is_debug() {
+            self.render_count.set(self.render_count.get() + 1);
+            log::debug!("Render #{}", self.render_count.get());
+        }
+
         let link = ctx.link();
+        let language = self.manager.language;
         if let Some(game) = &self.manager.game {
-            let keyboard_state = ALLOWED_KEYS
-                .iter()
-                .map(|key| (*key, game.keyboard_tilestate(key)))
-                .collect::<HashMap<char, KeyState>>();
+            let keyboard_state = game.keyboard_state().clone();
 
             let last_guess = game.last_guess();
 
             let boards = game.boards();
+            // Shown only while the boards are played, the message takes over once they're done
+            let board_progress = game.board_progress().filter(|_| game.is_guessing());
 
             html! {
                 <div class={classes!("game", self.manager.theme.to_string())}>
                     <Header
                         on_toggle_help_cb={link.callback(|_| Msg::ToggleHelp)}
                         on_toggle_menu_cb={link.callback(|_| Msg::ToggleMenu)}
+                        is_daily_pending={self.manager.is_daily_pending()}
+                        is_hint_assisted={game.is_hint_assisted()}
                         title={game.title()}
+                        language={language}
                     />
 
                     {
-                        match boards.len() {
-                            1 => html! {
+                        if let Some(summary) = self.manager.return_summary() {
+                            html! {
+                                <Banner
+                                    message={strings::msg_welcome_back(language, summary.streak, summary.is_daily_missed)}
+                                    action={Text::ModeDaily.get(language)}
+                                    on_action_cb={link.callback(|_| Msg::ReturnToDaily)}
+                                    secondary_action={(Text::Continue.get(language).to_owned(), link.callback(|_| Msg::ReturnToGame))}
+                                />
+                            }
+                        } else if self.manager.is_new_daily_available() {
+                            let today = Local::now().date_naive();
+                            html! {
+                                <Banner
+                                    message={Text::NewDailyAvailable.get(language)}
+                                    action={Text::Play.get(language)}
+                                    on_action_cb={link.callback(move |_| Msg::ChangeGameMode(GameMode::DailyWord(today)))}
+                                />
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+
+                    {
+                        if self.manager.pending_game_mode().is_some() {
+                            html! {
+                                <Banner
+                                    message={Text::DailyUnfinished.get(language)}
+                                    action={Text::Switch.get(language)}
+                                    on_action_cb={link.callback(|_| Msg::ConfirmGameModeChange)}
+                                    on_dismiss_cb={link.callback(|_| Msg::CancelGameModeChange)}
+                                />
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+
+                    {
+                        match self.manager.storage_failure() {
+                            Some(StorageFailure::QuotaExceeded) => html! {
+                                <Banner
+                                    message={Text::StorageFull.get(language)}
+                                    action={Text::CleanupStorage.get(language)}
+                                    on_action_cb={link.callback(|_| Msg::CleanupStorage)}
+                                    on_dismiss_cb={link.callback(|_| Msg::DismissStorageWarning)}
+                                />
+                            },
+                            Some(_) => html! {
+                                <Banner
+                                    message={Text::StorageFailed.get(language)}
+                                    action={Text::RetryStorage.get(language)}
+                                    on_action_cb={link.callback(|_| Msg::RetryStorage)}
+                                    on_dismiss_cb={link.callback(|_| Msg::DismissStorageWarning)}
+                                />
+                            },
+                            None => html! {},
+                        }
+                    }
+
+                    {
+                        if !self.is_online {
+                            html! {
+                                <div class="offline">{Text::Offline.get(language)}</div>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+
+                    {
+                        match (self.manager.hotseat_entry(), boards.len()) {
+                            (Some(entry), _) => html! {
+                                <div class="board-container">
+                                    <WordEntry
+                                        entered={entry.word.len()}
+                                        max_guesses={game.max_guesses()}
+                                        word_length={game.word_length()}
+                                    />
+                                </div>
+                            },
+                            (None, 1) if self.replay.as_ref() == Some(&boards[0].guesses) => html! {
+                                <div class="board-container">
+                                    <Replay
+                                        guesses={boards[0].guesses.clone()}
+                                        max_guesses={game.max_guesses()}
+                                        word_length={game.word_length()}
+                                        show_glyphs={self.manager.theme == Theme::Colorblind}
+                                        on_close={link.callback(|_| Msg::ToggleReplay)}
+                                    />
+                                </div>
+                            },
+                            (None, 1) if self.analysis.as_ref() == Some(&boards[0].guesses) => html! {
+                                <div class="board-container">
+                                    <Analysis
+                                        rows={game.guess_analysis().unwrap_or_default()}
+                                        language={language}
+                                        on_close={link.callback(|_| Msg::ToggleAnalysis)}
+                                    />
+                                </div>
+                            },
+                            (None, 1) => html! {
                                 <div class="board-container">
                                     <Board
                                         guesses={boards[0].guesses.clone()}
+                                        new_information={boards[0].new_information.clone()}
+                                        highlight_new_information={self.manager.highlight_new_information}
                                         is_guessing={boards[0].is_guessing}
                                         current_guess={boards[0].current_guess}
                                         is_reset={game.is_reset()}
                                         is_hidden={game.is_hidden()}
-                                        previous_guesses={game.previous_guesses().clone()}
+                                        previous_guesses={game.previous_guesses()}
                                         max_guesses={game.max_guesses()}
                                         word_length={game.word_length()}
+                                        show_glyphs={self.manager.theme == Theme::Colorblind}
+                                        markers={boards[0].markers.clone()}
+                                        on_mark={link.callback(|(row, column)| Msg::CycleTileMarker(0, row, column))}
                                     />
                                 </div>
                             },
-                            4 => html! {
-                                <div class="quadruple-container">
-                                    <div class="quadruple-grid">
-                                        {game.boards().iter().map(|board| {
-                                            html! {
-                                                <Board
-                                                    guesses={board.guesses.clone()}
-                                                    is_guessing={board.is_guessing}
-                                                    current_guess={board.current_guess}
-                                                    is_reset={game.is_reset()}
-                                                    is_hidden={game.is_hidden()}
-                                                    previous_guesses={game.previous_guesses().clone()}
-                                                    max_guesses={game.max_guesses()}
-                                                    word_length={game.word_length()}
-                                                />
-                                            }
-                                        }).collect::<Html>()}
+                            (None, 4) => html! {
+                                <>
+                                    <div class="quadruple-container">
+                                        <div class="quadruple-grid">
+                                            {game.boards().iter().enumerate().map(|(index, board)| {
+                                                html! {
+                                                    <Board
+                                                        guesses={board.guesses.clone()}
+                                                        new_information={board.new_information.clone()}
+                                                        highlight_new_information={self.manager.highlight_new_information}
+                                                        is_guessing={board.is_guessing}
+                                                        current_guess={board.current_guess}
+                                                        is_reset={game.is_reset()}
+                                                        is_hidden={game.is_hidden()}
+                                                        previous_guesses={game.previous_guesses()}
+                                                        max_guesses={game.max_guesses()}
+                                                        word_length={game.word_length()}
+                                                        show_glyphs={self.manager.theme == Theme::Colorblind}
+                                                        markers={board.markers.clone()}
+                                                        on_mark={link.callback(move |(row, column)| Msg::CycleTileMarker(index, row, column))}
+                                                    />
+                                                }
+                                            }).collect::<Html>()}
+                                        </div>
                                     </div>
-                                </div>
+                                    {
+                                        match board_progress {
+                                            Some(progress) => html! {
+                                                <p class="board-progress">
+                                                    {strings::msg_board_progress(language, progress.solved, progress.boards, progress.guesses_left)}
+                                                </p>
+                                            },
+                                            None => html! {},
+                                        }
+                                    }
+                                </>
                             },
                             _ => html! {}
                         }
@@ -269,21 +1088,54 @@ impl Component for App {
                     <Keyboard
                         callback={link.callback(move |msg| msg)}
                         is_unknown={game.is_unknown()}
+                        is_warning={game.is_contradiction_pending()}
+                        remaining_candidates={game.remaining_candidates().filter(|_| game.is_guessing())}
                         is_winner={game.is_winner()}
                         is_guessing={game.is_guessing()}
                         is_hidden={game.is_hidden()}
+                        is_spectating={game.is_spectating()}
+                        can_share_progress={game.progress_payload(None).is_some()}
+                        has_analysis={game.guess_analysis().is_some()}
+                        starter_words={self.manager.starter_words()}
+                        show_unused_letters={self.manager.show_unused_letters}
+                        show_glyphs={self.manager.theme == Theme::Colorblind}
                         is_emojis_copied={self.is_emojis_copied}
+                        is_counts_copied={self.is_counts_copied}
                         is_link_copied={self.is_link_copied}
                         game_mode={game.game_mode().clone()}
-                        message={game.message()}
+                        message={self.manager.hotseat_entry().map_or_else(|| game.message(), |entry| entry.message.clone())}
                         word={game.word().iter().collect::<String>()}
+                        word_source={game.word_source()}
                         last_guess={last_guess}
+                        board_results={boards.iter().map(|board| (board.word.iter().collect::<String>(), board.is_winner)).collect::<Vec<_>>()}
+                        daily_percentile={self.manager.daily_percentile()}
                         keyboard={keyboard_state}
+                        language={language}
+                        word_language={game.word_language()}
+                        keyboard_layout={self.manager.keyboard_layout}
+                        detailed_key_colors={self.manager.detailed_key_colors}
                     />
 
                     {
                         if self.is_help_visible {
-                            html! { <HelpModal theme={self.manager.theme} callback={link.callback(move |msg| msg)} /> }
+                            html! { <HelpModal
+                                theme={self.manager.theme}
+                                game_mode={self.manager.current_game_mode}
+                                language={language}
+                                word_counts={self.manager.word_counts()}
+                                callback={link.callback(move |msg| msg)}
+                            /> }
+                        } else {
+                            html! {}
+                        }
+                    }
+
+                    {
+                        if self.is_demo_visible {
+                            html! { <Demo
+                                language={language}
+                                on_dismiss={link.callback(|_| Msg::DismissDemo)}
+                            /> }
                         } else {
                             html! {}
                         }
@@ -294,14 +1146,47 @@ impl Component for App {
                             html! {
                                 <MenuModal
                                     callback={link.callback(move |msg| msg)}
+                                    tab={self.menu_tab}
                                     game_mode={self.manager.current_game_mode}
                                     word_length={self.manager.current_word_length}
                                     current_word_list={self.manager.current_word_list}
+                                    is_mixed_list_available={self.manager.is_mixed_list_available()}
+                                    is_installable={self.is_installable}
+                                    drawn_words={self.manager.drawn_words_text()}
+                                    word_language={self.manager.word_language}
                                     allow_profanities={self.manager.allow_profanities}
+                                    weighted_word_selection={self.manager.weighted_word_selection}
                                     theme={self.manager.theme}
+                                    language={language}
+                                    keyboard_layout={self.manager.keyboard_layout}
+                                    detailed_key_colors={self.manager.detailed_key_colors}
+                                    show_unused_letters={self.manager.show_unused_letters}
+                                    highlight_new_information={self.manager.highlight_new_information}
+                                    hide_typing_hints={self.manager.hide_typing_hints}
+                                    warn_contradictions={self.manager.warn_contradictions}
+                                    show_candidates={self.manager.show_candidates}
+                                    beginner_help={self.manager.beginner_help}
+                                    hide_finished_word={self.manager.hide_finished_word}
+                                    auto_submit={self.manager.auto_submit}
+                                    emoji_set={self.manager.emoji_set}
+                                    share_solve_time={self.manager.share_solve_time}
+                                    accessible_share={self.manager.accessible_share}
+                                    sharer_tag={self.manager.sharer_tag.clone()}
+                                    practice_letters={self.manager.practice_letters()}
+                                    suspended_games={self.manager.suspended_games()}
                                     max_streak={self.manager.max_streak}
                                     total_played={self.manager.total_played}
                                     total_solved={self.manager.total_solved}
+                                    guess_distribution={self.manager.guess_distribution.clone()}
+                                    best_milestone={self.manager.best_milestone}
+                                    daily_streak={self.manager.daily_streak}
+                                    max_daily_streak={self.manager.max_daily_streak}
+                                    daily_freezes={self.manager.daily_freezes}
+                                    frozen_days={self.manager.frozen_days.clone()}
+                                    broken_streaks={self.manager.broken_streaks.clone()}
+                                    is_daily_pending={self.manager.is_daily_pending()}
+                                    is_late_daily_available={self.manager.is_late_daily_available()}
+                                    key_press={self.menu_key_press}
                                 />
                             }
                         } else {
@@ -314,20 +1199,101 @@ impl Component for App {
             html! {
                 <MenuModal
                     callback={link.callback(move |msg| msg)}
+                    tab={self.menu_tab}
                     game_mode={self.manager.current_game_mode}
                     word_length={self.manager.current_word_length}
                     current_word_list={self.manager.current_word_list}
+                    is_mixed_list_available={self.manager.is_mixed_list_available()}
+                    is_installable={self.is_installable}
+                    drawn_words={self.manager.drawn_words_text()}
+                    word_language={self.manager.word_language}
                     allow_profanities={self.manager.allow_profanities}
+                    weighted_word_selection={self.manager.weighted_word_selection}
                     theme={self.manager.theme}
+                    language={language}
+                    keyboard_layout={self.manager.keyboard_layout}
+                    detailed_key_colors={self.manager.detailed_key_colors}
+                    show_unused_letters={self.manager.show_unused_letters}
+                    highlight_new_information={self.manager.highlight_new_information}
+                    hide_typing_hints={self.manager.hide_typing_hints}
+                    warn_contradictions={self.manager.warn_contradictions}
+                    show_candidates={self.manager.show_candidates}
+                    beginner_help={self.manager.beginner_help}
+                    hide_finished_word={self.manager.hide_finished_word}
+                    auto_submit={self.manager.auto_submit}
+                    emoji_set={self.manager.emoji_set}
+                    share_solve_time={self.manager.share_solve_time}
+                    accessible_share={self.manager.accessible_share}
+                    sharer_tag={self.manager.sharer_tag.clone()}
+                    practice_letters={self.manager.practice_letters()}
+                    suspended_games={self.manager.suspended_games()}
                     max_streak={self.manager.max_streak}
                     total_played={self.manager.total_played}
                     total_solved={self.manager.total_solved}
+                    guess_distribution={self.manager.guess_distribution.clone()}
+                    best_milestone={self.manager.best_milestone}
+                    daily_streak={self.manager.daily_streak}
+                    max_daily_streak={self.manager.max_daily_streak}
+                    daily_freezes={self.manager.daily_freezes}
+                    frozen_days={self.manager.frozen_days.clone()}
+                    broken_streaks={self.manager.broken_streaks.clone()}
+                    is_daily_pending={self.manager.is_daily_pending()}
+                    is_late_daily_available={self.manager.is_late_daily_available()}
+                    key_press={self.menu_key_press}
                 />
             }
         }
     }
 }
 
+impl App {
+    // Pushes entries for a modal or a shared game that was just opened, and drops the entries of
+    // the ones closed in the app. An entry dropped and pushed at once is reused as it is.
+    fn sync_history(&mut self) {
+        let is_modal_open = self.is_menu_visible || self.is_help_visible;
+        let is_shared = self.manager.current_game_mode == GameMode::Shared;
+
+        let mut dropped = 0;
+        if !is_modal_open && self.history.last() == Some(&HistoryEntry::Modal) {
+            self.history.pop();
+            dropped += 1;
+        }
+        if !is_shared && self.history.last() == Some(&HistoryEntry::SharedGame) {
+            self.history.pop();
+            dropped += 1;
+        }
+
+        let mut entered = Vec::new();
+        if is_shared && !self.was_shared {
+            entered.push(HistoryEntry::SharedGame);
+        }
+        if is_modal_open && self.history.last() != Some(&HistoryEntry::Modal) {
+            entered.push(HistoryEntry::Modal);
+        }
+        self.was_shared = is_shared;
+
+        if dropped == 0 && entered.is_empty() {
+            return;
+        }
+        let history = match window().and_then(|window| window.history().ok()) {
+            Some(history) => history,
+            None => return,
+        };
+
+        let reused = dropped.min(entered.len());
+        for (index, entry) in entered.into_iter().enumerate() {
+            if index >= reused {
+                let _ = history.push_state(&JsValue::NULL, "");
+            }
+            self.history.push(entry);
+        }
+        if dropped > reused {
+            self.ignored_pops += 1;
+            let _ = history.go_with_delta(-((dropped - reused) as i32));
+        }
+    }
+}
+
 fn main() {
     wasm_logger::init(wasm_logger::Config::default());
     yew::Renderer::<App>::new().render();
@@ -1,96 +1,240 @@
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::fmt;
 use std::mem;
 use std::rc::Rc;
 use std::str::FromStr;
 
-use chrono::{Local, NaiveDate};
-use gloo_storage::{errors::StorageError, LocalStorage, Storage};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use gloo_storage::errors::StorageError;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use wasm_bindgen::JsValue;
 use web_sys::{window, Window};
 
-use crate::game::Game;
-use crate::neluli::Neluli;
+use crate::game;
+use crate::game::{DailyHistory, Game, GameResult, Letter, ShareOptions};
+#[cfg(feature = "migration")]
+use crate::migration;
+use crate::neluli::{self, Neluli};
+use crate::persistence::{self, GamePersistence, LocalStoragePersistence};
 use crate::sanuli::Sanuli;
+use crate::share;
+use crate::starters;
+use crate::strings::{self, Language, Text};
 
 const EASY_WORDS: &str = include_str!("../easy-words.txt");
 const COMMON_WORDS: &str = include_str!("../common-words.txt");
 const FULL_WORDS: &str = include_str!("../full-words.txt");
 const PROFANITIES: &str = include_str!("../profanities.txt");
+const ANIMAL_WORDS: &str = include_str!("../animal-words.txt");
+const FOOD_WORDS: &str = include_str!("../food-words.txt");
+#[cfg(feature = "swedish")]
+const SWEDISH_COMMON_WORDS: &str = include_str!("../swedish-common-words.txt");
+#[cfg(feature = "swedish")]
+const SWEDISH_FULL_WORDS: &str = include_str!("../swedish-full-words.txt");
 
 pub const DEFAULT_WORD_LENGTH: usize = 5;
 pub const DEFAULT_MAX_GUESSES: usize = 6;
 pub const DEFAULT_ALLOW_PROFANITIES: bool = false;
 pub const DAILY_WORD_LEN: usize = 5;
-
-pub type WordLists = HashMap<(WordList, usize), HashSet<Vec<char>>>;
-
-#[derive(PartialEq, Copy, Clone)]
+pub const WORD_LENGTHS: [usize; 2] = [5, 6];
+
+// Version of the persisted settings shape, bump it together with a new migration
+pub const SCHEMA_VERSION: u32 = 1;
+
+// Suspended games beyond this are dropped from memory and rehydrated from persistence on demand
+const MAX_BACKGROUND_GAMES: usize = 8;
+// Daily games older than this are removed from persistence on startup
+const DAILY_GAME_RETENTION_DAYS: i64 = 30;
+// Every this many dailies solved in a row earns a freeze that covers one missed day
+const DAILY_FREEZE_INTERVAL: usize = 7;
+// Players away for longer than this are reminded of what they left behind
+const RETURN_AFTER_DAYS: i64 = 3;
+// Streaks remembered with the word that ended them, the latest ones
+const MAX_BROKEN_STREAKS: usize = 5;
+// Randomly drawn words kept in debug mode, the latest ones
+const MAX_DRAWN_WORDS: usize = 20;
+
+pub type WordLists = HashMap<(WordLanguage, WordList, usize), HashSet<Vec<char>>>;
+
+// The most boards a single key can show the state of
+pub const MAX_KEY_SEGMENTS: usize = 8;
+
+/// The color of a keyboard key, one state per board for games with several boards.
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
 pub enum KeyState {
-    Quadruple([TileState; 4]),
     Single(TileState),
+    Multiple {
+        states: [TileState; MAX_KEY_SEGMENTS],
+        count: usize,
+    },
+}
+
+impl KeyState {
+    /// Combines the states of a key on each board, in board order. Boards past
+    /// `MAX_KEY_SEGMENTS` are left out.
+    pub fn combine(board_states: &[TileState]) -> KeyState {
+        match board_states {
+            [] => KeyState::Single(TileState::Unknown),
+            [state] => KeyState::Single(*state),
+            _ => {
+                let mut states = [TileState::Unknown; MAX_KEY_SEGMENTS];
+                let count = board_states.len().min(MAX_KEY_SEGMENTS);
+                states[..count].copy_from_slice(&board_states[..count]);
+                KeyState::Multiple { states, count }
+            }
+        }
+    }
+
+    pub fn states(&self) -> &[TileState] {
+        match self {
+            KeyState::Single(state) => std::slice::from_ref(state),
+            KeyState::Multiple { states, count } => &states[..*count],
+        }
+    }
+
+    /// The most informative of the states: a letter found on any board matters more than
+    /// the boards it's missing from.
+    pub fn best(&self) -> TileState {
+        let rank = |state: &TileState| match state {
+            TileState::Correct => 3,
+            TileState::Present => 2,
+            TileState::Unknown => 1,
+            TileState::Absent => 0,
+        };
+
+        self.states()
+            .iter()
+            .copied()
+            .max_by_key(rank)
+            .unwrap_or(TileState::Unknown)
+    }
+}
+
+/// The words of a word list file, one per line. Blank lines and words with characters
+/// that can't be typed are skipped with a warning.
+pub fn parse_word_list(words: &str) -> impl Iterator<Item = Vec<char>> + '_ {
+    words.lines().enumerate().filter_map(|(index, line)| {
+        let word = line.trim();
+        if word.is_empty() {
+            log::warn!("Skipping blank line {} of a word list", index + 1);
+            return None;
+        }
+        if !word.chars().all(|c| Letter::try_from(c).is_ok()) {
+            log::warn!("Skipping \"{}\" on line {} of a word list", word, index + 1);
+            return None;
+        }
+        Some(word.chars().collect())
+    })
+}
+
+pub fn parse_all_words() -> Rc<WordLists> {
+    let mut word_lists: WordLists = HashMap::with_capacity(3);
+    parse_finnish_words(&mut word_lists);
+    #[cfg(feature = "swedish")]
+    parse_swedish_words(&mut word_lists);
+
+    Rc::new(word_lists)
 }
 
-fn parse_all_words() -> Rc<WordLists> {
-    let mut word_lists: HashMap<(WordList, usize), HashSet<Vec<char>>> = HashMap::with_capacity(3);
-    for word in FULL_WORDS.lines() {
-        let chars = word.chars();
-        let word_length = chars.clone().count();
+fn parse_finnish_words(word_lists: &mut WordLists) {
+    let language = WordLanguage::Finnish;
+
+    for word in parse_word_list(FULL_WORDS) {
+        let word_length = word.len();
         word_lists
-            .entry((WordList::Full, word_length))
+            .entry((language, WordList::Full, word_length))
             .or_insert_with(HashSet::new)
-            .insert(chars.collect());
+            .insert(word);
     }
 
     // TODO: Only 5-letter easy words exist for now on this list; fake them from common list
-    for word in EASY_WORDS.lines() {
-        let chars = word.chars();
-        let word_length = chars.clone().count();
+    for word in parse_word_list(EASY_WORDS) {
+        let word_length = word.len();
         word_lists
-            .entry((WordList::Easy, word_length))
+            .entry((language, WordList::Easy, word_length))
             .or_insert_with(HashSet::new)
-            .insert(chars.collect());
+            .insert(word);
     }
 
-    for word in COMMON_WORDS.lines() {
-        let chars = word.chars();
-        let word_length = chars.clone().count();
+    for word in parse_word_list(COMMON_WORDS) {
+        let word_length = word.len();
 
         if word_length == 6 {
             // TODO: Fake 6-letter easy words from common words, get rid of this if the list is created
             word_lists
-                .entry((WordList::Easy, 6))
+                .entry((language, WordList::Easy, 6))
                 .or_insert_with(HashSet::new)
-                .insert(chars.clone().collect());
+                .insert(word.clone());
         }
 
         word_lists
-            .entry((WordList::Common, word_length))
+            .entry((language, WordList::Common, word_length))
             .or_insert_with(HashSet::new)
-            .insert(chars.collect());
+            .insert(word);
     }
 
-    for word in PROFANITIES.lines() {
-        let chars = word.chars();
-        let word_length = chars.clone().count();
+    for word in parse_word_list(PROFANITIES) {
+        let word_length = word.len();
         word_lists
-            .entry((WordList::Profanities, word_length))
+            .entry((language, WordList::Profanities, word_length))
             .or_insert_with(HashSet::new)
-            .insert(chars.collect());
+            .insert(word);
     }
 
-    Rc::new(word_lists)
+    for (word_list, words) in [
+        (WordList::Animals, ANIMAL_WORDS),
+        (WordList::Food, FOOD_WORDS),
+    ] {
+        for word in parse_word_list(words) {
+            let word_length = word.len();
+
+            // Guesses are checked against the full list, so themed words must be found there too
+            word_lists
+                .entry((language, WordList::Full, word_length))
+                .or_default()
+                .insert(word.clone());
+
+            word_lists
+                .entry((language, word_list, word_length))
+                .or_default()
+                .insert(word);
+        }
+    }
+}
+
+// Only the common and full lists exist in Swedish, the full list includes the common words
+#[cfg(feature = "swedish")]
+fn parse_swedish_words(word_lists: &mut WordLists) {
+    for (word_list, words) in [
+        (WordList::Full, SWEDISH_FULL_WORDS),
+        (WordList::Common, SWEDISH_COMMON_WORDS),
+    ] {
+        for word in parse_word_list(words) {
+            let word_length = word.len();
+            word_lists
+                .entry((WordLanguage::Swedish, word_list, word_length))
+                .or_default()
+                .insert(word);
+        }
+    }
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum WordList {
     Full,
     Common,
     Easy,
     Profanities,
     Daily,
+    Animals,
+    Food,
+    // Neluli boards drawn from lists of rising difficulty, from the easy list to the full one
+    Mixed,
 }
 
 impl Default for WordList {
@@ -99,13 +243,64 @@ impl Default for WordList {
     }
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+/// The language of the words, each one has word lists, daily words and saved games of its own.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum WordLanguage {
+    #[default]
+    Finnish,
+    Swedish,
+}
+
+impl WordLanguage {
+    pub fn code(self) -> &'static str {
+        match self {
+            WordLanguage::Finnish => "fi",
+            WordLanguage::Swedish => "sv",
+        }
+    }
+
+    /// The languages offered in the menu.
+    pub fn available() -> &'static [WordLanguage] {
+        if cfg!(feature = "swedish") {
+            &[WordLanguage::Finnish, WordLanguage::Swedish]
+        } else {
+            &[WordLanguage::Finnish]
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "fi" => Some(WordLanguage::Finnish),
+            "sv" if cfg!(feature = "swedish") => Some(WordLanguage::Swedish),
+            _ => None,
+        }
+    }
+
+    /// The word lists that can be played in the language.
+    pub fn word_lists(self) -> &'static [WordList] {
+        match self {
+            WordLanguage::Finnish => &[
+                WordList::Easy,
+                WordList::Common,
+                WordList::Full,
+                WordList::Animals,
+                WordList::Food,
+            ],
+            WordLanguage::Swedish => &[WordList::Common, WordList::Full],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum GameMode {
     Classic,
     Relay,
     DailyWord(NaiveDate),
     Shared,
     Quadruple,
+    Hotseat,
+    // Practice words, optionally only ones containing the given letter
+    Practice(Option<char>),
 }
 
 impl Default for GameMode {
@@ -126,14 +321,44 @@ impl Default for Theme {
     }
 }
 
-#[derive(Clone, PartialEq, Serialize, Deserialize)]
+/// How the letters of the on-screen keyboard are ordered on its rows.
+#[derive(PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Alphabetical,
+    // Two rows, for narrow screens and two thumbs
+    Compact,
+}
+
+#[derive(PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum EmojiSet {
+    #[default]
+    Party,
+    Animals,
+    Hearts,
+    Off,
+}
+
+impl EmojiSet {
+    pub fn emojis(&self) -> &'static [&'static str] {
+        match self {
+            EmojiSet::Party => &strings::SUCCESS_EMOJIS,
+            EmojiSet::Animals => &strings::ANIMAL_EMOJIS,
+            EmojiSet::Hearts => &strings::HEART_EMOJIS,
+            EmojiSet::Off => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CharacterState {
     Correct,
     Absent,
     Unknown,
 }
 
-#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TileState {
     Correct,
     Absent,
@@ -141,6 +366,46 @@ pub enum TileState {
     Unknown,
 }
 
+impl TileState {
+    /// Corner mark for telling the states apart without relying on color.
+    pub fn glyph(&self) -> Option<char> {
+        match self {
+            TileState::Correct => Some('✓'),
+            TileState::Present => Some('~'),
+            TileState::Absent => Some('·'),
+            TileState::Unknown => None,
+        }
+    }
+}
+
+/// A note the player pins on a guessed tile to remember it by. Markers are only for the player,
+/// the game and its share never look at them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TileMarker {
+    Star,
+    Strike,
+}
+
+impl TileMarker {
+    // Each press moves to the next marker, the press after the last one removes it
+    pub fn cycle(marker: Option<TileMarker>) -> Option<TileMarker> {
+        match marker {
+            None => Some(TileMarker::Star),
+            Some(TileMarker::Star) => Some(TileMarker::Strike),
+            Some(TileMarker::Strike) => None,
+        }
+    }
+}
+
+impl fmt::Display for TileMarker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TileMarker::Star => write!(f, "star"),
+            TileMarker::Strike => write!(f, "strike"),
+        }
+    }
+}
+
 impl fmt::Display for TileState {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -164,6 +429,19 @@ impl FromStr for Theme {
     }
 }
 
+// Same names as in the settings menu
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", strings::game_mode_name(Language::Finnish, *self))
+    }
+}
+
+impl fmt::Display for WordList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", strings::word_list_name(Language::Finnish, *self))
+    }
+}
+
 impl fmt::Display for Theme {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -173,68 +451,422 @@ impl fmt::Display for Theme {
     }
 }
 
-#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CharacterCount {
     AtLeast(usize),
     Exactly(usize),
 }
 
-#[derive(PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum StorageFailure {
+    QuotaExceeded,
+    Unavailable,
+    Serialization,
+}
+
+// Chromium and WebKit use the standard name, Firefox its own
+const QUOTA_EXCEEDED_ERRORS: [&str; 2] = ["QuotaExceededError", "NS_ERROR_DOM_QUOTA_REACHED"];
+
+fn is_quota_exceeded(err: &StorageError) -> bool {
+    matches!(err, StorageError::JsError(err) if QUOTA_EXCEEDED_ERRORS.contains(&err.name.as_str()))
+}
+
+fn has_seen_help_default() -> bool {
+    true
+}
+
+fn default_persistence() -> Rc<dyn GamePersistence> {
+    Rc::new(LocalStoragePersistence)
+}
+
+// Debug mode is turned on with ?debug=1, it lasts until the page is loaded again
+fn is_debug_requested() -> bool {
+    window()
+        .and_then(|window| window.location().search().ok())
+        .is_some_and(|qs| {
+            qs.trim_start_matches('?')
+                .split('&')
+                .any(|param| param == "debug=1")
+        })
+}
+
+fn browser_language() -> Language {
+    window()
+        .and_then(|window| window.navigator().language())
+        .map(|tag| Language::from_tag(&tag))
+        .unwrap_or_default()
+}
+
+/// The settings a player can change in the menu. The defaults here are the ones both
+/// new players start with and resetting the settings returns to.
+#[derive(Clone, PartialEq)]
+pub struct Settings {
+    pub word_language: WordLanguage,
+    pub word_list: WordList,
+    pub word_length: usize,
+    pub allow_profanities: bool,
+    pub weighted_word_selection: bool,
+    pub theme: Theme,
+    pub keyboard_layout: KeyboardLayout,
+    pub detailed_key_colors: bool,
+    pub show_unused_letters: bool,
+    pub highlight_new_information: bool,
+    pub hide_typing_hints: bool,
+    pub warn_contradictions: bool,
+    pub show_candidates: bool,
+    pub beginner_help: bool,
+    pub hide_finished_word: bool,
+    pub auto_submit: bool,
+    pub emoji_set: EmojiSet,
+    pub share_solve_time: bool,
+    pub accessible_share: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            word_language: WordLanguage::default(),
+            word_list: WordList::default(),
+            word_length: DEFAULT_WORD_LENGTH,
+            allow_profanities: DEFAULT_ALLOW_PROFANITIES,
+            weighted_word_selection: false,
+            theme: Theme::default(),
+            keyboard_layout: KeyboardLayout::default(),
+            detailed_key_colors: false,
+            show_unused_letters: false,
+            highlight_new_information: false,
+            hide_typing_hints: false,
+            warn_contradictions: false,
+            show_candidates: false,
+            beginner_help: false,
+            hide_finished_word: false,
+            auto_submit: false,
+            emoji_set: EmojiSet::default(),
+            share_solve_time: false,
+            accessible_share: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct Manager {
+    #[serde(default)]
+    pub schema_version: u32,
+
     pub current_game_mode: GameMode,
     pub current_word_list: WordList,
     pub current_word_length: usize,
+    #[serde(default)]
+    pub word_language: WordLanguage,
     pub allow_profanities: bool,
+    // Draws from the full list favor the words that are also on the common list
+    #[serde(default)]
+    pub weighted_word_selection: bool,
 
     pub previous_game: (GameMode, WordList, usize),
 
     pub theme: Theme,
+    // Settings stored before the language could be chosen were made with the Finnish interface
+    #[serde(default)]
+    pub language: Language,
+    #[serde(default)]
+    pub keyboard_layout: KeyboardLayout,
+    // Keys of several boards are split by board instead of colored by the best state of them
+    #[serde(default)]
+    pub detailed_key_colors: bool,
+    #[serde(default)]
+    pub show_unused_letters: bool,
+    // Rings the tiles that told something the earlier guesses hadn't
+    #[serde(default)]
+    pub highlight_new_information: bool,
+    // Typed letters stay uncolored until submitted, like in the original game
+    #[serde(default)]
+    pub hide_typing_hints: bool,
+    // Guesses going against known information are warned about before they are accepted
+    #[serde(default)]
+    pub warn_contradictions: bool,
+    // The analysis overlay, counting the words that still fit the guesses
+    #[serde(default)]
+    pub show_candidates: bool,
+    // Good first guesses are offered before anything is typed on a new word
+    #[serde(default)]
+    pub beginner_help: bool,
+    // Keeps the word hidden after the game ends until it's revealed, for streaming
+    #[serde(default)]
+    pub hide_finished_word: bool,
+    // A full row of an accepted word is guessed without pressing enter, for speedruns
+    #[serde(default)]
+    pub auto_submit: bool,
+    #[serde(default)]
+    pub emoji_set: EmojiSet,
+    #[serde(default)]
+    pub share_solve_time: bool,
+    #[serde(default)]
+    pub accessible_share: bool,
+    // Appended to shared links, so that the receiver can tell whose game it is
+    #[serde(default)]
+    pub sharer_tag: Option<String>,
+    // Players who already have settings stored have been here before
+    #[serde(default = "has_seen_help_default")]
+    pub has_seen_help: bool,
 
     pub max_streak: usize,
     pub total_played: usize,
     pub total_solved: usize,
+    // Wins by the number of guesses they took, the first item is for solving on the first guess
+    #[serde(default)]
+    pub guess_distribution: Vec<usize>,
+    // Highest of the streak milestones reached, shown as badges
+    #[serde(default)]
+    pub best_milestone: usize,
+    // The latest streaks ended by a lost word, the most recent last
+    #[serde(default)]
+    pub broken_streaks: Vec<BrokenStreak>,
+
+    #[serde(default)]
+    pub daily_streak: usize,
+    #[serde(default)]
+    pub max_daily_streak: usize,
+    #[serde(default)]
+    pub daily_freezes: usize,
+    // The latest day that kept the daily streak going, either solved or covered by a freeze
+    #[serde(default)]
+    last_daily_streak_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub frozen_days: Vec<NaiveDate>,
+    #[serde(default)]
+    pub daily_history: DailyHistory,
+    #[serde(default)]
+    pub last_played: Option<NaiveDate>,
+    // The streak of each game by its storage key, for when the game itself can't be restored
+    #[serde(default)]
+    pub streaks: HashMap<String, usize>,
+
+    // Increases on every save, so that a tab can tell its settings were saved over by another
+    #[serde(default)]
+    revision: Cell<u64>,
 
     #[serde(skip)]
     pub game: Option<Box<dyn Game>>,
     #[serde(skip)]
     pub background_games: HashMap<(GameMode, WordList, usize), Box<dyn Game>>,
     #[serde(skip)]
+    background_order: Vec<(GameMode, WordList, usize)>,
+    #[serde(skip)]
     pub word_lists: Rc<WordLists>,
+    #[serde(skip, default = "default_persistence")]
+    persistence: Rc<dyn GamePersistence>,
+    // Nothing was stored for this visitor before the page was loaded
+    #[serde(skip)]
+    is_first_visit: bool,
+    #[serde(skip)]
+    return_summary: Option<ReturnSummary>,
+    #[serde(skip)]
+    is_daily_pending: bool,
+    #[serde(skip)]
+    is_late_daily_available: bool,
+    #[serde(skip)]
+    is_new_daily_available: bool,
+    #[serde(skip)]
+    hotseat_entry: Option<HotseatEntry>,
+    // Game mode waiting for a confirmation before leaving an unfinished daily word
+    #[serde(skip)]
+    pending_game_mode: Option<GameMode>,
+    #[serde(skip)]
+    storage_failures: RefCell<HashMap<String, StorageFailure>>,
+    #[serde(skip)]
+    is_storage_warning_dismissed: Cell<bool>,
+    // Changes waiting for the next flush, see persist and persist_game
+    #[serde(skip)]
+    has_unsaved_settings: Cell<bool>,
+    #[serde(skip)]
+    has_unsaved_game: Cell<bool>,
+    #[serde(skip)]
+    is_debug: bool,
+    // Never persisted, the words drawn since the page was loaded with debug mode on
+    #[serde(skip)]
+    drawn_words: RefCell<VecDeque<DrawnWord>>,
+    // The practice letters of the word list they were found for, the menu asks on every render
+    #[serde(skip)]
+    practice_letters: RefCell<Option<PracticeLetters>>,
+    // Summaries of the stored games by their storage key, read once and then kept up to date
+    // as games are saved and removed
+    #[serde(skip)]
+    stored_games: RefCell<Option<BTreeMap<String, SuspendedGame>>>,
+    // The settings as this tab last saved or adopted them, what differs has been changed here
+    #[serde(skip)]
+    saved_settings: Option<Value>,
+}
+
+struct PracticeLetters {
+    word_list: (WordLanguage, WordList, usize),
+    letters: Vec<char>,
+}
+
+// The secret word of a two-player game while it's being typed in
+#[derive(Default)]
+pub struct HotseatEntry {
+    pub word: Vec<char>,
+    pub message: String,
+}
+
+// What was left behind by a player returning after days away
+#[derive(Clone, PartialEq)]
+pub struct ReturnSummary {
+    pub streak: usize,
+    pub is_daily_missed: bool,
+    // The game to continue, the one played before the daily words if that's where they were
+    pub game: (GameMode, WordList, usize),
+}
+
+// How many words a list has to draw from, without the profanities unless they're allowed
+#[derive(Clone, PartialEq)]
+pub struct WordCount {
+    pub word_list: WordList,
+    pub word_length: usize,
+    pub count: usize,
+}
+
+// A streak and the word that ended it, for the statistics
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrokenStreak {
+    pub streak: usize,
+    pub word: String,
+    #[serde(default)]
+    pub word_language: WordLanguage,
+    pub date: NaiveDate,
+}
+
+// A word drawn for a game, recorded in debug mode for bug reports
+#[derive(Clone, PartialEq)]
+pub struct DrawnWord {
+    pub drawn_at: DateTime<Utc>,
+    pub word_language: WordLanguage,
+    pub game_mode: GameMode,
+    pub word_list: WordList,
+    pub word_length: usize,
+    pub word: String,
+}
+
+// A game waiting to be continued, either in memory or in storage
+#[derive(Clone, PartialEq)]
+pub struct SuspendedGame {
+    pub game: (GameMode, WordList, usize),
+    pub guesses_used: usize,
+    pub is_finished: bool,
+}
+
+impl SuspendedGame {
+    fn new(game: (GameMode, WordList, usize), suspended: &dyn Game) -> Self {
+        let guesses_used = suspended.guesses_used().unwrap_or_else(|| {
+            suspended
+                .boards()
+                .iter()
+                .map(|board| board.current_guess)
+                .max()
+                .unwrap_or(0)
+        });
+
+        Self {
+            game,
+            guesses_used,
+            is_finished: !suspended.is_guessing(),
+        }
+    }
 }
 
 impl Default for Manager {
     fn default() -> Self {
+        let settings = Settings::default();
+
         Self {
+            schema_version: SCHEMA_VERSION,
+
             current_game_mode: GameMode::default(),
-            current_word_list: WordList::default(),
-            current_word_length: DEFAULT_WORD_LENGTH,
-            allow_profanities: DEFAULT_ALLOW_PROFANITIES,
+            current_word_list: settings.word_list,
+            current_word_length: settings.word_length,
+            word_language: settings.word_language,
+            allow_profanities: settings.allow_profanities,
+            weighted_word_selection: settings.weighted_word_selection,
 
             previous_game: (
                 GameMode::default(),
-                WordList::default(),
-                DEFAULT_WORD_LENGTH,
+                settings.word_list,
+                settings.word_length,
             ),
 
-            theme: Theme::default(),
+            theme: settings.theme,
+            language: Language::default(),
+            keyboard_layout: settings.keyboard_layout,
+            detailed_key_colors: settings.detailed_key_colors,
+            show_unused_letters: settings.show_unused_letters,
+            highlight_new_information: settings.highlight_new_information,
+            hide_typing_hints: settings.hide_typing_hints,
+            warn_contradictions: settings.warn_contradictions,
+            show_candidates: settings.show_candidates,
+            beginner_help: settings.beginner_help,
+            hide_finished_word: settings.hide_finished_word,
+            auto_submit: settings.auto_submit,
+            emoji_set: settings.emoji_set,
+            share_solve_time: settings.share_solve_time,
+            accessible_share: settings.accessible_share,
+            sharer_tag: None,
+            has_seen_help: false,
 
             max_streak: 0,
             total_played: 0,
             total_solved: 0,
+            guess_distribution: Vec::new(),
+            best_milestone: 0,
+
+            daily_streak: 0,
+            max_daily_streak: 0,
+            daily_freezes: 0,
+            last_daily_streak_date: None,
+            frozen_days: Vec::new(),
+            broken_streaks: Vec::new(),
+            daily_history: HashMap::new(),
+            last_played: None,
+            streaks: HashMap::new(),
+
+            revision: Cell::new(0),
 
             game: None,
             background_games: HashMap::new(),
+            background_order: Vec::new(),
             word_lists: Rc::new(HashMap::new()),
+            persistence: default_persistence(),
+            is_first_visit: false,
+            return_summary: None,
+            is_daily_pending: false,
+            is_late_daily_available: false,
+            is_new_daily_available: false,
+            hotseat_entry: None,
+            pending_game_mode: None,
+            storage_failures: RefCell::new(HashMap::new()),
+            is_storage_warning_dismissed: Cell::new(false),
+            has_unsaved_settings: Cell::new(false),
+            has_unsaved_game: Cell::new(false),
+            is_debug: false,
+            drawn_words: RefCell::new(VecDeque::new()),
+            practice_letters: RefCell::new(None),
+            stored_games: RefCell::new(None),
+            saved_settings: None,
         }
     }
 }
 
 impl Manager {
     pub fn new() -> Self {
+        Self::with_persistence(default_persistence())
+    }
+
+    pub fn with_persistence(persistence: Rc<dyn GamePersistence>) -> Self {
         let word_lists = parse_all_words();
+        let is_debug = is_debug_requested();
 
-        // Attempt to rehydrate manager from localStorage
-        let mut initial_manager = if let Ok(mut manager) = Manager::rehydrate() {
+        // Attempt to rehydrate manager from the persistence
+        let mut initial_manager = if let Ok(mut manager) = Manager::rehydrate(persistence.clone()) {
             if let GameMode::DailyWord(date) = manager.current_game_mode {
                 let today = Local::now().date_naive();
 
@@ -244,33 +876,26 @@ impl Manager {
                 }
             }
 
-            match manager.current_game_mode {
-                GameMode::Classic | GameMode::Relay | GameMode::DailyWord(_) => {
-                    manager.game = Some(Box::new(Sanuli::new_or_rehydrate(
-                        manager.current_game_mode,
-                        manager.current_word_list,
-                        manager.current_word_length,
-                        manager.allow_profanities,
-                        word_lists.clone(),
-                    )));
-                }
-                GameMode::Quadruple => {
-                    manager.game = Some(Box::new(Neluli::new_or_rehydrate(
-                        manager.current_word_list,
-                        manager.current_word_length,
-                        manager.allow_profanities,
-                        word_lists.clone(),
-                    )));
-                }
-                GameMode::Shared => {}
-            };
-
             manager.word_lists = word_lists;
+            manager.is_debug = is_debug;
+            // A language chosen in a build that offered it is played in Finnish in one that doesn't
+            if !WordLanguage::available().contains(&manager.word_language) {
+                manager.word_language = WordLanguage::default();
+            }
+
+            if manager.current_game_mode != GameMode::Shared {
+                manager.game = Some(manager.open_game((
+                    manager.current_game_mode,
+                    manager.current_word_list,
+                    manager.current_word_length,
+                )));
+            }
 
             manager
         } else {
             // Otherwise either create everything from scratch or recover some data from legacy storage manager
-            let game = Sanuli::new(
+            let mut game = Sanuli::new(
+                WordLanguage::default(),
                 GameMode::Classic,
                 WordList::Common,
                 DEFAULT_WORD_LENGTH,
@@ -278,26 +903,42 @@ impl Manager {
                 DEFAULT_ALLOW_PROFANITIES,
                 word_lists.clone(),
             );
+            // First visits follow the language of the browser
+            let language = browser_language();
+            game.set_language(language);
 
             let manager = Self {
                 game: Some(Box::new(game)),
                 word_lists,
+                persistence,
+                language,
+                is_first_visit: true,
+                is_debug,
                 ..Self::default()
             };
+            if let Some(game) = manager.game.as_ref() {
+                manager.record_drawn_words(game.as_ref());
+            }
 
-            let _res = manager.persist();
-            let _res = manager.game.as_ref().unwrap().persist();
+            manager.persist();
+            manager.persist_game();
 
             manager
         };
 
         // If this is a shared game switch to it immediately. Set the game we were going to display in the background
-        if let Some(game) = initial_manager.rehydrate_shared_game() {
+        let shared_game = initial_manager.rehydrate_shared_game();
+        let is_shared_link = shared_game.is_some();
+        if let Some(mut game) = shared_game {
+            game.set_language(initial_manager.language);
+            game.set_hide_typing_hints(initial_manager.hide_typing_hints);
+            game.set_warn_contradictions(initial_manager.warn_contradictions);
+            game.set_show_candidates(initial_manager.show_candidates);
             initial_manager.current_game_mode = *game.game_mode();
             initial_manager.current_word_list = *game.word_list();
             initial_manager.current_word_length = game.word_length();
 
-            initial_manager.background_games.insert(
+            initial_manager.suspend_game(
                 (*game.game_mode(), *game.word_list(), game.word_length()),
                 Box::new(game),
             );
@@ -305,269 +946,2928 @@ impl Manager {
             initial_manager.switch_active_game();
         }
 
+        initial_manager.cleanup_daily_games(DAILY_GAME_RETENTION_DAYS);
+        initial_manager.update_daily_pending();
+        initial_manager.check_missed_dailies();
+
+        // A shared link is opened for the shared game, not for what was left behind
+        if !is_shared_link {
+            initial_manager.return_summary =
+                initial_manager.summarize_return(Local::now().date_naive());
+        }
+
         initial_manager
     }
 
-    fn rehydrate_shared_game(&self) -> Option<Sanuli> {
-        let window: Window = window().expect("window not available");
-        let qs = window.location().search().ok()?;
-        if qs.is_empty() {
+    fn summarize_return(&self, today: NaiveDate) -> Option<ReturnSummary> {
+        let days_away = today.signed_duration_since(self.last_played?).num_days();
+        if days_away <= RETURN_AFTER_DAYS {
             return None;
         }
 
-        // Skip the leading "?"
-        for param in qs.chars().skip(1).collect::<String>().split("&") {
-            let mut parts = param.split("=");
-
-            let key = parts.next()?;
-            let value = parts.next()?;
+        let game = match self.current_game_mode {
+            GameMode::DailyWord(_) => self.previous_game,
+            _ => (
+                self.current_game_mode,
+                self.current_word_list,
+                self.current_word_length,
+            ),
+        };
+        let key = game::storage_key(self.word_language, &game.0, &game.1, game.2);
 
-            if key == "peli" && !value.is_empty() {
-                // Replace URL safe characters back to +/=
-                let base64 = value.replace("-", "+").replace(".", "/").replace("_", "=");
+        Some(ReturnSummary {
+            streak: self.streaks.get(&key).copied().unwrap_or(0),
+            is_daily_missed: self.is_late_daily_available,
+            game,
+        })
+    }
 
-                let game_str = window.atob(&base64).ok()?;
+    pub fn return_summary(&self) -> Option<&ReturnSummary> {
+        self.return_summary.as_ref()
+    }
 
-                let game = Sanuli::from_shared_link(&game_str, self.word_lists.clone());
+    pub fn dismiss_return_summary(&mut self) {
+        self.return_summary = None;
+    }
 
-                // Remove the query string
-                window
-                    .history()
-                    .ok()?
-                    .replace_state_with_url(&JsValue::null(), "", Some("/"))
-                    .ok()?;
+    /// Switches to the game the returning player left behind, unless it's already on screen.
+    pub fn continue_after_return(&mut self) {
+        let game = match self.return_summary.take() {
+            Some(summary) => summary.game,
+            None => return,
+        };
 
-                return game;
-            }
+        let current = (
+            self.current_game_mode,
+            self.current_word_list,
+            self.current_word_length,
+        );
+        if game != current {
+            self.continue_game(game);
         }
-
-        return None;
     }
 
-    pub fn push_character(&mut self, character: char) {
-        if let Some(game) = self.game.as_mut() {
-            game.push_character(character);
+    pub fn storage_failure(&self) -> Option<StorageFailure> {
+        if self.is_storage_warning_dismissed.get() {
+            return None;
         }
-    }
 
-    pub fn pop_character(&mut self) {
-        if let Some(game) = self.game.as_mut() {
-            game.pop_character();
+        let failures = self.storage_failures.borrow();
+        if failures
+            .values()
+            .any(|failure| *failure == StorageFailure::QuotaExceeded)
+        {
+            return Some(StorageFailure::QuotaExceeded);
         }
+        failures.values().next().copied()
     }
 
-    pub fn next_word(&mut self) {
-        if let Some(game) = self.game.as_mut() {
-            game.next_word();
-        }
+    pub fn dismiss_storage_warning(&mut self) {
+        self.is_storage_warning_dismissed.set(true);
     }
 
-    pub fn submit_guess(&mut self) {
-        if self.game.is_none() || !self.game.as_ref().unwrap().is_guessing() {
-            return;
+    pub fn cleanup_storage(&mut self) {
+        self.cleanup_daily_games(0);
+        self.retry_failed_saves();
+    }
+
+    // Forgets the player entirely: removes everything sanuli has stored and starts over
+    pub fn clear_all_data(&mut self) {
+        for key in persistence::owned_keys(self.persistence.as_ref()) {
+            self.persistence.remove(&key);
         }
 
-        self.game.as_mut().unwrap().submit_guess();
+        let persistence = self.persistence.clone();
+        let word_lists = std::mem::take(&mut self.word_lists);
+        *self = Self {
+            word_lists,
+            persistence,
+            is_debug: self.is_debug,
+            ..Self::default()
+        };
 
-        if !self.game.as_ref().unwrap().is_guessing() {
-            self.update_game_statistics(
-                self.game.as_ref().unwrap().is_winner(),
-                self.game.as_ref().unwrap().streak(),
-            );
-        }
+        self.game =
+            Some(self.open_game((GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH)));
+        self.persist();
+        self.persist_game();
+        self.flush();
+        self.update_daily_pending();
     }
 
-    pub fn change_word_length(&mut self, new_length: usize) {
-        if self.current_word_length == new_length {
+    // Saves everything again after an earlier failure, clearing the failures that now succeed
+    pub fn retry_failed_saves(&mut self) {
+        if self.storage_failures.borrow().is_empty() {
             return;
         }
 
-        self.current_word_length = new_length;
-        self.switch_active_game();
-
-        let _res = self.persist();
-        if let Some(game) = self.game.as_mut() {
-            let _res = game.persist();
+        self.storage_failures.borrow_mut().clear();
+        self.persist();
+        self.persist_game();
+        self.flush();
+        for game in self.background_games.values() {
+            self.persist_snapshot(game.as_ref());
         }
     }
 
-    pub fn change_game_mode(&mut self, new_mode: GameMode) {
-        if self.current_game_mode == new_mode {
-            return;
-        }
+    // Removes stored daily games older than the retention period. The active, previous
+    // and today's daily games are always kept.
+    fn cleanup_daily_games(&self, retention_days: i64) {
+        let today = Local::now().date_naive();
+        let referenced = [
+            (
+                self.current_game_mode,
+                self.current_word_list,
+                self.current_word_length,
+            ),
+            self.previous_game,
+            (GameMode::DailyWord(today), WordList::Daily, DAILY_WORD_LEN),
+        ]
+        .iter()
+        .map(|(game_mode, word_list, word_length)| {
+            game::storage_key(self.word_language, game_mode, word_list, *word_length)
+        })
+        .collect::<HashSet<_>>();
+
+        for key in self.persistence.keys_with_prefix("game|") {
+            if referenced.contains(&key) {
+                continue;
+            }
 
-        if matches!(self.current_game_mode, GameMode::DailyWord(_)) {
-            self.current_word_list = self.previous_game.1;
-            self.current_word_length = self.previous_game.2;
+            let game_mode = key
+                .split('|')
+                .nth(1)
+                .and_then(|game_mode| serde_json::from_str::<GameMode>(game_mode).ok());
+
+            if let Some(GameMode::DailyWord(date)) = game_mode {
+                if today.signed_duration_since(date).num_days() > retention_days {
+                    self.remove_stored_game(&key);
+                }
+            }
         }
+    }
 
-        if matches!(new_mode, GameMode::DailyWord(_)) {
-            self.current_word_list = WordList::Daily;
-            self.current_word_length = DAILY_WORD_LEN;
-        } else if self.current_word_list == WordList::Daily {
-            // Prevent getting stuck in non-daily word gamemode with
-            // daily list somehow, for instance by having a daily game as
-            // the previous game in manager state
-            self.current_word_list = WordList::default();
+    /// How the finished daily on screen compares to the other dailies played on this device.
+    pub fn daily_percentile(&self) -> Option<usize> {
+        let game = self.game.as_ref()?;
+        match game.game_mode() {
+            GameMode::DailyWord(date) if !game.is_guessing() => {
+                game::daily_percentile(&self.daily_history, game::daily_word_index(*date))
+            }
+            _ => None,
         }
+    }
 
-        self.current_game_mode = new_mode;
-        self.switch_active_game();
-        let _res = self.persist();
-        let _res = self.game.as_ref().unwrap().persist();
+    pub fn is_first_visit(&self) -> bool {
+        self.is_first_visit
     }
 
-    pub fn change_word_list(&mut self, new_list: WordList) {
-        if self.current_word_list == new_list {
-            return;
-        }
+    pub fn is_daily_pending(&self) -> bool {
+        self.is_daily_pending
+    }
 
-        self.current_word_list = new_list;
-        self.switch_active_game();
+    // Yesterday's word can still be played late, as long as it wasn't played to the end
+    pub fn is_late_daily_available(&self) -> bool {
+        self.is_late_daily_available
+    }
 
-        let _res = self.persist();
-        let _res = self.game.as_ref().unwrap().persist();
+    fn update_daily_pending(&mut self) {
+        let today = Local::now().date_naive();
+
+        self.is_daily_pending = !self.is_daily_finished(today);
+        self.is_late_daily_available = today
+            .pred_opt()
+            .is_some_and(|yesterday| !self.is_daily_finished(yesterday));
     }
 
-    pub fn change_previous_game_mode(&mut self) {
-        let (game_mode, word_list, word_length) = self.previous_game;
+    fn is_daily_finished(&self, date: NaiveDate) -> bool {
+        let daily_game = (GameMode::DailyWord(date), WordList::Daily, DAILY_WORD_LEN);
 
-        if matches!(game_mode, GameMode::DailyWord(_))
-            && matches!(self.current_game_mode, GameMode::DailyWord(_))
+        let is_active_daily = self
+            .game
+            .as_ref()
+            .map(|game| *game.game_mode() == daily_game.0)
+            .unwrap_or(false);
+
+        if is_active_daily {
+            !self.game.as_ref().unwrap().is_guessing()
+        } else if let Some(game) = self.background_games.get(&daily_game) {
+            !game.is_guessing()
+        } else {
+            // Read straight from the stored game, a daily never started isn't stored at all
+            let (game_mode, word_list, word_length) = daily_game;
+            let key = game::storage_key(self.word_language, &game_mode, &word_list, word_length);
+            self.persistence
+                .get(&key)
+                .ok()
+                .and_then(|snapshot| snapshot.get("is_guessing")?.as_bool())
+                .is_some_and(|is_guessing| !is_guessing)
+        }
+    }
+
+    // Another tab saved over the settings or a game, pick up its changes
+    pub fn sync_external_change(&mut self, key: &str) {
+        if key == "settings" {
+            self.adopt_newer_settings();
+        } else if self
+            .game
+            .as_ref()
+            .and_then(|game| game.storage_key())
+            .as_deref()
+            == Some(key)
         {
-            // Force the user to reset to the base game
-            self.current_game_mode = GameMode::default();
-            self.current_word_list = WordList::default();
-            self.current_word_length = DEFAULT_WORD_LENGTH;
+            let current_game = (
+                self.current_game_mode,
+                self.current_word_list,
+                self.current_word_length,
+            );
+            self.game = Some(self.new_or_rehydrate_game(current_game));
         } else {
-            self.current_game_mode = game_mode;
-            self.current_word_list = word_list;
-            self.current_word_length = word_length;
+            // Another tab saved or removed a game, the summaries are read again
+            self.stored_games.replace(None);
+
+            // Suspended games get rehydrated again when they are needed
+            let suspended = self
+                .background_games
+                .iter()
+                .find(|(_, game)| game.storage_key().as_deref() == Some(key))
+                .map(|(suspended, _)| *suspended);
+
+            if let Some(suspended) = suspended {
+                self.background_games.remove(&suspended);
+                self.background_order.retain(|game| *game != suspended);
+            }
         }
 
-        self.switch_active_game();
+        self.update_daily_pending();
+    }
 
-        let _res = self.persist();
-        let _res = self.game.as_mut().unwrap().persist();
+    fn adopt_newer_settings(&mut self) {
+        match Manager::rehydrate(self.persistence.clone()) {
+            Ok(stored) if stored.revision.get() > self.revision.get() => {
+                self.adopt_settings(stored)
+            }
+            _ => (),
+        }
     }
 
-    pub fn change_allow_profanities(&mut self, is_allowed: bool) {
-        self.allow_profanities = is_allowed;
-        self.game
-            .as_mut()
-            .unwrap()
-            .set_allow_profanities(self.allow_profanities);
-        self.background_games.values_mut().for_each(|game| {
-            game.set_allow_profanities(self.allow_profanities);
-        });
-        let _result = self.persist();
+    // Settings of another tab, with the changes made here since the last save on top
+    fn merge_newer_settings(&self) -> Option<Manager> {
+        let local = serde_json::to_value(self).ok()?;
+        let mut merged = self.persistence.get("settings").ok()?;
+        let fields = merged.as_object_mut()?;
+        for (field, value) in local.as_object()? {
+            let saved = self
+                .saved_settings
+                .as_ref()
+                .and_then(|saved| saved.get(field));
+            if saved != Some(value) {
+                fields.insert(field.clone(), value.clone());
+            }
+        }
+
+        Manager::from_settings(merged, self.persistence.clone()).ok()
     }
 
-    pub fn change_theme(&mut self, theme: Theme) {
-        self.theme = theme;
-        let _result = self.persist();
+    fn adopt_settings(&mut self, stored: Manager) {
+        self.saved_settings = stored.saved_settings;
+        self.theme = stored.theme;
+        self.keyboard_layout = stored.keyboard_layout;
+        self.detailed_key_colors = stored.detailed_key_colors;
+        self.show_unused_letters = stored.show_unused_letters;
+        self.highlight_new_information = stored.highlight_new_information;
+        if self.hide_typing_hints != stored.hide_typing_hints {
+            self.apply_hide_typing_hints(stored.hide_typing_hints);
+        }
+        if self.warn_contradictions != stored.warn_contradictions {
+            self.apply_warn_contradictions(stored.warn_contradictions);
+        }
+        if self.show_candidates != stored.show_candidates {
+            self.apply_show_candidates(stored.show_candidates);
+        }
+        self.beginner_help = stored.beginner_help;
+        self.hide_finished_word = stored.hide_finished_word;
+        self.auto_submit = stored.auto_submit;
+        self.emoji_set = stored.emoji_set;
+        if let Some(game) = self.game.as_mut() {
+            game.set_emoji_set(self.emoji_set);
+        }
+        if self.language != stored.language {
+            self.apply_language(stored.language);
+        }
+        self.share_solve_time = stored.share_solve_time;
+        self.accessible_share = stored.accessible_share;
+        self.sharer_tag = stored.sharer_tag;
+        self.has_seen_help = stored.has_seen_help;
+        self.max_streak = stored.max_streak;
+        self.total_played = stored.total_played;
+        self.total_solved = stored.total_solved;
+        self.guess_distribution = stored.guess_distribution;
+        self.best_milestone = stored.best_milestone;
+        self.daily_streak = stored.daily_streak;
+        self.max_daily_streak = stored.max_daily_streak;
+        self.daily_freezes = stored.daily_freezes;
+        self.last_daily_streak_date = stored.last_daily_streak_date;
+        self.frozen_days = stored.frozen_days;
+        self.broken_streaks = stored.broken_streaks;
+        self.daily_history = stored.daily_history;
+        self.last_played = stored.last_played;
+        self.streaks = stored.streaks;
+        self.revision.set(stored.revision.get());
+
+        if self.allow_profanities != stored.allow_profanities {
+            self.allow_profanities = stored.allow_profanities;
+            if let Some(game) = self.game.as_mut() {
+                game.set_allow_profanities(self.allow_profanities);
+            }
+            self.background_games.values_mut().for_each(|game| {
+                game.set_allow_profanities(self.allow_profanities);
+            });
+        }
+
+        if self.weighted_word_selection != stored.weighted_word_selection {
+            self.weighted_word_selection = stored.weighted_word_selection;
+            if let Some(game) = self.game.as_mut() {
+                game.set_weighted_selection(self.weighted_word_selection);
+            }
+            self.background_games.values_mut().for_each(|game| {
+                game.set_weighted_selection(self.weighted_word_selection);
+            });
+        }
     }
 
-    fn switch_active_game(&mut self) {
-        let next_game = (
-            self.current_game_mode,
+    pub fn is_new_daily_available(&self) -> bool {
+        self.is_new_daily_available
+    }
+
+    pub fn check_date(&mut self) {
+        if let GameMode::DailyWord(date) = self.current_game_mode {
+            // Day changed while the daily game was open - let the user decide when to switch
+            let is_late = self.game.as_ref().is_some_and(|game| game.is_late());
+            self.is_new_daily_available = date < Local::now().date_naive() && !is_late;
+        }
+
+        self.update_daily_pending();
+        self.check_missed_dailies();
+    }
+
+    fn rehydrate_shared_game(&self) -> Option<Sanuli> {
+        let window: Window = window().expect("window not available");
+        let qs = window.location().search().ok()?;
+        if qs.is_empty() {
+            return None;
+        }
+
+        // Skip the leading "?"
+        for param in qs.chars().skip(1).collect::<String>().split("&") {
+            let mut parts = param.split("=");
+
+            let key = parts.next()?;
+            let value = parts.next()?;
+
+            if key == "peli" && !value.is_empty() {
+                let game_str = share::decode_payload(value)?;
+
+                let game = Sanuli::from_shared_link(&game_str, self.word_lists.clone());
+
+                // Remove the query string. The entry is replaced rather than pushed, so that Back
+                // returns to the page the link was opened from
+                window
+                    .history()
+                    .ok()?
+                    .replace_state_with_url(&JsValue::null(), "", Some("/"))
+                    .ok()?;
+
+                return game;
+            }
+        }
+
+        return None;
+    }
+
+    // Returns whether anything visible changed
+    pub fn push_character(&mut self, character: char) -> bool {
+        if let Some(entry) = self.hotseat_entry.as_mut() {
+            let mut is_changed = !entry.message.is_empty();
+            if entry.word.len() < self.current_word_length {
+                entry.word.push(character);
+                is_changed = true;
+            }
+            entry.message.clear();
+            is_changed
+        } else if let Some(game) = self.game.as_mut() {
+            game.push_character(character)
+        } else {
+            false
+        }
+    }
+
+    // Returns whether anything visible changed
+    pub fn pop_character(&mut self) -> bool {
+        if let Some(entry) = self.hotseat_entry.as_mut() {
+            let is_changed = entry.word.pop().is_some() || !entry.message.is_empty();
+            entry.message.clear();
+            is_changed
+        } else if let Some(game) = self.game.as_mut() {
+            game.pop_character()
+        } else {
+            false
+        }
+    }
+
+    pub fn cycle_tile_marker(&mut self, board: usize, row: usize, column: usize) -> bool {
+        let is_changed = self
+            .game
+            .as_mut()
+            .is_some_and(|game| game.cycle_tile_marker(board, row, column));
+        if is_changed {
+            self.persist_game();
+        }
+        is_changed
+    }
+
+    // Letters that some word of the current list and length contains, for picking a practice letter
+    pub fn practice_letters(&self) -> Vec<char> {
+        let key = (
+            self.word_language,
             self.current_word_list,
             self.current_word_length,
         );
+        let mut cached = self.practice_letters.borrow_mut();
+        match &*cached {
+            Some(practice) if practice.word_list == key => practice.letters.clone(),
+            _ => {
+                let letters: Vec<char> = match self.word_lists.get(&key) {
+                    Some(words) => game::alphabet(self.word_language)
+                        .filter(|letter| words.iter().any(|word| word.contains(letter)))
+                        .collect(),
+                    None => Vec::new(),
+                };
+                *cached = Some(PracticeLetters {
+                    word_list: key,
+                    letters: letters.clone(),
+                });
+                letters
+            }
+        }
+    }
 
-        let previous = match mem::take(&mut self.game) {
-            Some(game) => game,
-            None => Box::new(Sanuli::default()) as Box<dyn Game>,
+    pub fn hotseat_entry(&self) -> Option<&HotseatEntry> {
+        self.hotseat_entry.as_ref()
+    }
+
+    fn start_hotseat_entry(&mut self) {
+        self.hotseat_entry = Some(HotseatEntry {
+            word: Vec::with_capacity(self.current_word_length),
+            message: Text::HotseatEntry.get(self.language).to_owned(),
+        });
+    }
+
+    // Starts the guessing once the secret word is a real word of the right length
+    fn submit_hotseat_word(&mut self) {
+        let language = self.language;
+        let entry = match self.hotseat_entry.as_mut() {
+            Some(entry) => entry,
+            None => return,
         };
 
-        let previous_game = (
-            *previous.game_mode(),
-            *previous.word_list(),
-            previous.word_length(),
+        if entry.word.len() < self.current_word_length {
+            entry.message = Text::TooFewLetters.get(language).to_owned();
+            return;
+        }
+
+        let is_accepted = self
+            .word_lists
+            .get(&(self.word_language, WordList::Full, self.current_word_length))
+            .is_some_and(|words| words.contains(&entry.word));
+
+        if !is_accepted {
+            entry.message = Text::NotInWordList.get(language).to_owned();
+            return;
+        }
+
+        let mut game = Sanuli::with_word(
+            self.word_language,
+            GameMode::Hotseat,
+            WordList::Full,
+            entry.word.clone(),
+            DEFAULT_MAX_GUESSES,
+            self.allow_profanities,
+            self.word_lists.clone(),
         );
+        game.set_emoji_set(self.emoji_set);
+        game.set_language(self.language);
+        game.set_hide_typing_hints(self.hide_typing_hints);
+        game.set_warn_contradictions(self.warn_contradictions);
+        game.set_show_candidates(self.show_candidates);
+        self.game = Some(Box::new(game));
+        self.hotseat_entry = None;
+    }
 
-        if next_game.0 == previous_game.0
-            && next_game.1 == previous_game.1
-            && next_game.2 == previous_game.2
-        {
+    pub fn prepare_next_word(&mut self) {
+        if let Some(game) = self.game.as_mut() {
+            game.prepare_next_word();
+        }
+    }
+
+    pub fn is_next_word_prepared(&self) -> bool {
+        self.game
+            .as_ref()
+            .is_none_or(|game| game.is_next_word_prepared())
+    }
+
+    pub fn next_word(&mut self) {
+        if self.current_game_mode == GameMode::Hotseat {
+            // Clears the finished board while the next secret word is typed in
+            if let Some(game) = self.game.as_mut() {
+                game.next_word();
+            }
+            self.start_hotseat_entry();
             return;
         }
 
-        self.previous_game = previous_game;
+        self.retry_failed_saves();
 
-        // Restore a suspended game or create a new one
-        let game = self
-            .background_games
-            .remove(&next_game)
-            .unwrap_or_else(|| match next_game.0 {
-                GameMode::Classic | GameMode::Relay | GameMode::DailyWord(_) | GameMode::Shared => {
-                    Box::new(Sanuli::new_or_rehydrate(
-                        next_game.0,
-                        next_game.1,
-                        next_game.2,
-                        self.allow_profanities,
-                        self.word_lists.clone(),
-                    ))
-                }
-                GameMode::Quadruple => Box::new(Neluli::new_or_rehydrate(
-                    next_game.1,
-                    next_game.2,
-                    self.allow_profanities,
-                    self.word_lists.clone(),
-                )),
-            });
+        if let Some(game) = self.game.as_mut() {
+            game.next_word();
+        }
+        if let Some(game) = self.game.as_ref() {
+            self.record_drawn_words(game.as_ref());
+        }
+        self.persist_game();
+    }
+
+    // Gives up on the current word for a fresh one in the same mode
+    pub fn new_game(&mut self) {
+        if matches!(
+            self.current_game_mode,
+            GameMode::DailyWord(_) | GameMode::Shared
+        ) {
+            return;
+        }
 
-        self.game = Some(game);
-        self.background_games.insert(previous_game, previous);
+        // An abandoned game isn't counted in the statistics, like switching modes mid-game
+        self.next_word();
     }
 
-    fn update_game_statistics(&mut self, is_winner: bool, streak: usize) {
-        self.total_played += 1;
+    pub fn submit_guess(&mut self) {
+        if self.hotseat_entry.is_some() {
+            self.submit_hotseat_word();
+            return;
+        }
 
-        if is_winner {
-            self.total_solved += 1;
+        if self.game.is_none() || !self.game.as_ref().unwrap().is_guessing() {
+            return;
+        }
 
-            if streak > self.max_streak {
-                self.max_streak = streak;
+        self.retry_failed_saves();
+
+        let previous_streak = self.game.as_ref().unwrap().streak();
+        let result = self.game.as_mut().unwrap().submit_guess();
+
+        let game = self.game.as_mut().unwrap();
+        if result.is_some()
+            && self.hide_finished_word
+            && !matches!(game.game_mode(), GameMode::Shared | GameMode::Quadruple)
+        {
+            game.hide_word();
+        }
+        self.persist_game();
+
+        if let Some(result) = result {
+            self.record_result(&result, previous_streak);
+            // Don't risk losing a result to a closed tab
+            self.flush();
+        }
+    }
+
+    fn record_result(&mut self, result: &GameResult, previous_streak: usize) {
+        // Two-player and practice games are just for fun
+        if matches!(result.game_mode, GameMode::Hotseat | GameMode::Practice(_)) {
+            return;
+        }
+
+        self.update_game_statistics(result.is_winner, result.streak, result.guesses_used);
+        self.update_broken_streaks(result, previous_streak);
+        self.update_streak_fallback(result);
+        if let GameMode::DailyWord(date) = result.game_mode {
+            self.update_daily_history(date, result);
+        }
+        // Late dailies count towards the totals but not towards the daily streak
+        if let GameMode::DailyWord(date) = result.game_mode {
+            if !result.is_late {
+                self.update_daily_streak(date, result.is_winner);
             }
         }
-        let _res = self.persist();
+        self.update_daily_pending();
     }
 
-    #[cfg(web_sys_unstable_apis)]
-    pub fn share_emojis(&self) -> Option<String> {
-        self.game.as_ref()?.share_emojis(self.theme)
+    fn update_streak_fallback(&mut self, result: &GameResult) {
+        if let GameMode::DailyWord(_) = result.game_mode {
+            return;
+        }
+
+        let key = game::storage_key(
+            self.word_language,
+            &result.game_mode,
+            &self.current_word_list,
+            self.current_word_length,
+        );
+        if result.streak > 0 {
+            self.streaks.insert(key, result.streak);
+        } else {
+            self.streaks.remove(&key);
+        }
     }
 
-    #[cfg(web_sys_unstable_apis)]
-    pub fn share_link(&self) -> Option<String> {
-        self.game.as_ref()?.share_link()
+    pub fn change_word_length(&mut self, new_length: usize) {
+        if self.current_word_length == new_length {
+            return;
+        }
+        if self.is_word_fixed() {
+            self.show_word_fixed_message();
+            return;
+        }
+
+        self.current_word_length = new_length;
+        self.switch_active_game();
+
+        self.persist();
+        self.persist_game();
     }
 
-    pub fn reveal_hidden_tiles(&mut self) {
+    /// Mixed boards are only for Neluli, and only in a language with all of their lists.
+    pub fn is_mixed_list_available(&self) -> bool {
+        self.current_game_mode == GameMode::Quadruple
+            && neluli::MIXED_WORD_LISTS
+                .iter()
+                .all(|word_list| self.word_language.word_lists().contains(word_list))
+    }
+
+    // Daily and shared words come with their length and list, neither can be changed
+    fn is_word_fixed(&self) -> bool {
+        matches!(
+            self.current_game_mode,
+            GameMode::DailyWord(_) | GameMode::Shared
+        )
+    }
+
+    fn show_word_fixed_message(&mut self) {
+        let message = Text::WordIsFixed.get(self.language).to_owned();
         if let Some(game) = self.game.as_mut() {
-            game.reveal_hidden_tiles();
+            game.set_message(message);
         }
     }
 
-    pub fn reset_game(&mut self) {
-        if let Some(game) = self.game.as_mut() {
-            game.reset();
+    pub fn change_game_mode(&mut self, new_mode: GameMode) {
+        if self.current_game_mode == new_mode {
+            return;
         }
+
+        if self.is_daily_word_unfinished() {
+            // Leaving is easy to regret, the word may change before the game is continued
+            self.pending_game_mode = Some(new_mode);
+            return;
+        }
+
+        self.switch_game_mode(new_mode);
     }
 
-    fn persist(&self) -> Result<(), StorageError> {
-        if matches!(self.current_game_mode, GameMode::Shared) {
-            // Never persist shared games
-            return Ok(());
+    pub fn pending_game_mode(&self) -> Option<GameMode> {
+        self.pending_game_mode
+    }
+
+    pub fn confirm_pending_game_mode(&mut self) {
+        if let Some(new_mode) = self.pending_game_mode.take() {
+            self.switch_game_mode(new_mode);
         }
+    }
 
-        LocalStorage::set("settings", self)
+    pub fn cancel_pending_game_mode(&mut self) {
+        self.pending_game_mode = None;
     }
 
-    fn rehydrate() -> Result<Self, StorageError> {
-        let mut manager: Self = LocalStorage::get("settings")?;
-        manager.word_lists = parse_all_words();
-        Ok(manager)
+    fn is_daily_word_unfinished(&self) -> bool {
+        matches!(self.current_game_mode, GameMode::DailyWord(_))
+            && self.game.as_ref().is_some_and(|game| {
+                game.is_guessing() && game.boards().iter().any(|board| board.current_guess > 0)
+            })
+    }
+
+    fn switch_game_mode(&mut self, new_mode: GameMode) {
+        self.pending_game_mode = None;
+
+        if matches!(
+            self.current_game_mode,
+            GameMode::DailyWord(_) | GameMode::Hotseat
+        ) {
+            self.current_word_list = self.previous_game.1;
+            self.current_word_length = self.previous_game.2;
+        }
+
+        self.hotseat_entry = None;
+
+        if matches!(new_mode, GameMode::DailyWord(_)) {
+            self.current_word_list = WordList::Daily;
+            self.current_word_length = DAILY_WORD_LEN;
+        } else if new_mode == GameMode::Hotseat {
+            // Any word goes, both for the secret word and for the guesses
+            self.current_word_list = WordList::Full;
+        } else if self.current_word_list == WordList::Daily {
+            // Prevent getting stuck in non-daily word gamemode with
+            // daily list somehow, for instance by having a daily game as
+            // the previous game in manager state
+            self.current_word_list = WordList::default();
+        } else if self.current_word_list == WordList::Mixed && new_mode != GameMode::Quadruple {
+            // Only the four boards can be mixed
+            self.current_word_list = WordList::default();
+        }
+
+        self.current_game_mode = new_mode;
+        self.is_new_daily_available = false;
+        self.switch_active_game();
+        self.persist();
+        self.persist_game();
+
+        if new_mode == GameMode::Hotseat {
+            self.start_hotseat_entry();
+        }
+    }
+
+    /// Words of each list and length of the word language, as parsed from the lists.
+    pub fn word_counts(&self) -> Vec<WordCount> {
+        let language = self.word_language;
+
+        language
+            .word_lists()
+            .iter()
+            .flat_map(|word_list| {
+                WORD_LENGTHS.iter().map(move |word_length| {
+                    let words = self.word_lists.get(&(language, *word_list, *word_length));
+                    let profanities = if self.allow_profanities {
+                        None
+                    } else {
+                        self.word_lists
+                            .get(&(language, WordList::Profanities, *word_length))
+                    };
+
+                    let count = words.map_or(0, |words| {
+                        words
+                            .iter()
+                            .filter(|word| !profanities.is_some_and(|p| p.contains(*word)))
+                            .count()
+                    });
+
+                    WordCount {
+                        word_list: *word_list,
+                        word_length: *word_length,
+                        count,
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Games other than the current one that have been started, most recently played first.
+    pub fn suspended_games(&self) -> Vec<SuspendedGame> {
+        let current = self
+            .game
+            .as_ref()
+            .map(|game| (*game.game_mode(), *game.word_list(), game.word_length()));
+
+        let mut suspended = self
+            .background_order
+            .iter()
+            .rev()
+            .filter_map(|key| {
+                let game = self.background_games.get(key)?;
+                Some(SuspendedGame::new(*key, game.as_ref()))
+            })
+            .collect::<Vec<_>>();
+
+        let mut stored_games = self.stored_games.borrow_mut();
+        let stored_games = stored_games.get_or_insert_with(|| self.read_stored_games());
+        for (stored_key, stored) in stored_games.iter() {
+            // Games of the other languages are continued after switching to them
+            match game::parse_storage_key(stored_key) {
+                Some((word_language, key))
+                    if word_language == self.word_language
+                        && !self.background_games.contains_key(&key) =>
+                {
+                    suspended.push(stored.clone())
+                }
+                _ => continue,
+            }
+        }
+
+        // Untouched games have nothing to continue
+        suspended.retain(|game| {
+            Some(game.game) != current
+                && !matches!(game.game.0, GameMode::Shared | GameMode::Hotseat)
+                && (game.is_finished || game.guesses_used > 0)
+        });
+        suspended
+    }
+
+    // Rehydrates every stored game once, for the summaries of suspended_games
+    fn read_stored_games(&self) -> BTreeMap<String, SuspendedGame> {
+        self.persistence
+            .keys_with_prefix("game|")
+            .into_iter()
+            .filter_map(|stored_key| {
+                let (_, key) = game::parse_storage_key(&stored_key)?;
+                let snapshot = self.persistence.get(&stored_key).ok()?;
+                let game = self.rehydrate_game(key.0, snapshot).ok()?;
+                Some((stored_key, SuspendedGame::new(key, game.as_ref())))
+            })
+            .collect()
+    }
+
+    fn remove_stored_game(&self, key: &str) {
+        self.persistence.remove(key);
+        if let Some(stored_games) = self.stored_games.borrow_mut().as_mut() {
+            stored_games.remove(key);
+        }
+    }
+
+    pub fn continue_game(&mut self, game: (GameMode, WordList, usize)) {
+        let (game_mode, word_list, word_length) = game;
+
+        self.hotseat_entry = None;
+        self.current_game_mode = game_mode;
+        self.current_word_list = word_list;
+        self.current_word_length = word_length;
+        self.is_new_daily_available = false;
+
+        self.switch_active_game();
+        self.persist();
+        self.persist_game();
+    }
+
+    pub fn change_word_list(&mut self, new_list: WordList) {
+        if self.current_word_list == new_list {
+            return;
+        }
+        if new_list == WordList::Mixed && !self.is_mixed_list_available() {
+            return;
+        }
+        if self.is_word_fixed() {
+            self.show_word_fixed_message();
+            return;
+        }
+
+        self.current_word_list = new_list;
+        self.switch_active_game();
+
+        self.persist();
+        self.persist_game();
+    }
+
+    /// Switches to the words of another language. The games of the current language are
+    /// saved and wait there for switching back.
+    pub fn change_word_language(&mut self, word_language: WordLanguage) {
+        if self.word_language == word_language {
+            return;
+        }
+
+        if matches!(self.current_game_mode, GameMode::Shared | GameMode::Hotseat) {
+            self.switch_game_mode(GameMode::default());
+        }
+
+        // Suspended games are told apart by game only, so they can't wait in memory
+        self.flush();
+        let background_games = mem::take(&mut self.background_games);
+        for game in background_games.values() {
+            self.persist_snapshot(game.as_ref());
+        }
+        self.background_order.clear();
+
+        self.word_language = word_language;
+
+        // Lists missing from the language fall back to the default list
+        let is_missing = |word_list: WordList| {
+            word_list != WordList::Daily && !word_language.word_lists().contains(&word_list)
+        };
+        if is_missing(self.current_word_list) {
+            self.current_word_list = WordList::default();
+        }
+        if is_missing(self.previous_game.1) {
+            self.previous_game.1 = WordList::default();
+        }
+
+        self.game = Some(self.open_game((
+            self.current_game_mode,
+            self.current_word_list,
+            self.current_word_length,
+        )));
+        self.persist();
+        self.persist_game();
+        self.update_daily_pending();
+    }
+
+    pub fn change_previous_game_mode(&mut self) {
+        let (game_mode, word_list, word_length) = self.previous_game;
+
+        if matches!(game_mode, GameMode::DailyWord(_))
+            && matches!(self.current_game_mode, GameMode::DailyWord(_))
+        {
+            // Force the user to reset to the base game
+            self.current_game_mode = GameMode::default();
+            self.current_word_list = WordList::default();
+            self.current_word_length = DEFAULT_WORD_LENGTH;
+        } else {
+            self.current_game_mode = game_mode;
+            self.current_word_list = word_list;
+            self.current_word_length = word_length;
+        }
+
+        self.is_new_daily_available = false;
+        self.hotseat_entry = None;
+        self.switch_active_game();
+
+        self.persist();
+        self.persist_game();
+    }
+
+    pub fn change_allow_profanities(&mut self, is_allowed: bool) {
+        self.allow_profanities = is_allowed;
+        self.game
+            .as_mut()
+            .unwrap()
+            .set_allow_profanities(self.allow_profanities);
+        self.background_games.values_mut().for_each(|game| {
+            game.set_allow_profanities(self.allow_profanities);
+        });
+        self.persist();
+    }
+
+    pub fn change_weighted_word_selection(&mut self, is_weighted: bool) {
+        self.weighted_word_selection = is_weighted;
+        if let Some(game) = self.game.as_mut() {
+            game.set_weighted_selection(self.weighted_word_selection);
+        }
+        self.background_games.values_mut().for_each(|game| {
+            game.set_weighted_selection(self.weighted_word_selection);
+        });
+        self.persist();
+        self.persist_game();
+    }
+
+    pub fn change_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.persist();
+    }
+
+    pub fn change_language(&mut self, language: Language) {
+        self.apply_language(language);
+        self.persist();
+    }
+
+    // Messages already shown stay in the previous language until the next one
+    fn apply_language(&mut self, language: Language) {
+        self.language = language;
+        if let Some(game) = self.game.as_mut() {
+            game.set_language(language);
+        }
+        self.background_games.values_mut().for_each(|game| {
+            game.set_language(language);
+        });
+    }
+
+    pub fn mark_help_seen(&mut self) {
+        if self.has_seen_help {
+            return;
+        }
+
+        self.has_seen_help = true;
+        self.persist();
+    }
+
+    pub fn change_keyboard_layout(&mut self, keyboard_layout: KeyboardLayout) {
+        self.keyboard_layout = keyboard_layout;
+        self.persist();
+    }
+
+    pub fn change_detailed_key_colors(&mut self, is_detailed: bool) {
+        self.detailed_key_colors = is_detailed;
+        self.persist();
+    }
+
+    pub fn change_show_unused_letters(&mut self, is_visible: bool) {
+        self.show_unused_letters = is_visible;
+        self.persist();
+    }
+
+    pub fn change_beginner_help(&mut self, is_enabled: bool) {
+        self.beginner_help = is_enabled;
+        self.persist();
+    }
+
+    // Starter words to offer, only until the first letter of the word is typed
+    pub fn starter_words(&self) -> &'static [&'static str] {
+        match self.game.as_ref() {
+            Some(game)
+                if self.beginner_help
+                    && self.hotseat_entry.is_none()
+                    && !game.is_spectating()
+                    && game.is_untouched() =>
+            {
+                starters::starter_words(game.word_language(), game.word_length())
+            }
+            _ => &[],
+        }
+    }
+
+    pub fn change_auto_submit(&mut self, is_enabled: bool) {
+        self.auto_submit = is_enabled;
+        self.persist();
+    }
+
+    // The typed guess is ready to be submitted without waiting for enter
+    pub fn is_auto_submit_ready(&self) -> bool {
+        self.auto_submit
+            && self.hotseat_entry.is_none()
+            && self.game.as_ref().is_some_and(|game| game.is_guess_ready())
+    }
+
+    pub fn change_hide_typing_hints(&mut self, is_hidden: bool) {
+        self.apply_hide_typing_hints(is_hidden);
+        self.persist();
+        self.persist_game();
+    }
+
+    fn apply_hide_typing_hints(&mut self, is_hidden: bool) {
+        self.hide_typing_hints = is_hidden;
+        if let Some(game) = self.game.as_mut() {
+            game.set_hide_typing_hints(is_hidden);
+        }
+        self.background_games.values_mut().for_each(|game| {
+            game.set_hide_typing_hints(is_hidden);
+        });
+    }
+
+    pub fn change_warn_contradictions(&mut self, is_warned: bool) {
+        self.apply_warn_contradictions(is_warned);
+        self.persist();
+    }
+
+    fn apply_warn_contradictions(&mut self, is_warned: bool) {
+        self.warn_contradictions = is_warned;
+        if let Some(game) = self.game.as_mut() {
+            game.set_warn_contradictions(is_warned);
+        }
+        self.background_games.values_mut().for_each(|game| {
+            game.set_warn_contradictions(is_warned);
+        });
+    }
+
+    pub fn change_show_candidates(&mut self, is_shown: bool) {
+        self.apply_show_candidates(is_shown);
+        self.persist();
+    }
+
+    fn apply_show_candidates(&mut self, is_shown: bool) {
+        self.show_candidates = is_shown;
+        if let Some(game) = self.game.as_mut() {
+            game.set_show_candidates(is_shown);
+        }
+        self.background_games.values_mut().for_each(|game| {
+            game.set_show_candidates(is_shown);
+        });
+    }
+
+    pub fn change_highlight_new_information(&mut self, is_highlighted: bool) {
+        self.highlight_new_information = is_highlighted;
+        self.persist();
+    }
+
+    pub fn change_emoji_set(&mut self, emoji_set: EmojiSet) {
+        self.emoji_set = emoji_set;
+        if let Some(game) = self.game.as_mut() {
+            game.set_emoji_set(self.emoji_set);
+        }
+        self.background_games.values_mut().for_each(|game| {
+            game.set_emoji_set(self.emoji_set);
+        });
+        self.persist();
+    }
+
+    pub fn change_share_solve_time(&mut self, is_shared: bool) {
+        self.share_solve_time = is_shared;
+        self.persist();
+    }
+
+    pub fn change_accessible_share(&mut self, is_accessible: bool) {
+        self.accessible_share = is_accessible;
+        self.persist();
+    }
+
+    /// Returns the settings to their defaults through the same changes the menu makes.
+    /// The interface language was picked from the browser and stays as it is, as do the
+    /// statistics and the games.
+    pub fn reset_settings(&mut self) {
+        let defaults = Settings::default();
+
+        self.change_word_language(defaults.word_language);
+        if self.is_word_fixed() {
+            // The daily or shared word keeps its own list, the reset applies after it
+            self.previous_game.1 = defaults.word_list;
+            self.previous_game.2 = defaults.word_length;
+        } else {
+            self.change_word_list(defaults.word_list);
+            self.change_word_length(defaults.word_length);
+        }
+        self.change_allow_profanities(defaults.allow_profanities);
+        self.change_weighted_word_selection(defaults.weighted_word_selection);
+        self.change_theme(defaults.theme);
+        self.change_keyboard_layout(defaults.keyboard_layout);
+        self.change_detailed_key_colors(defaults.detailed_key_colors);
+        self.change_show_unused_letters(defaults.show_unused_letters);
+        self.change_highlight_new_information(defaults.highlight_new_information);
+        self.change_hide_typing_hints(defaults.hide_typing_hints);
+        self.change_warn_contradictions(defaults.warn_contradictions);
+        self.change_show_candidates(defaults.show_candidates);
+        self.change_beginner_help(defaults.beginner_help);
+        self.change_hide_finished_word(defaults.hide_finished_word);
+        self.change_auto_submit(defaults.auto_submit);
+        self.change_emoji_set(defaults.emoji_set);
+        self.change_share_solve_time(defaults.share_solve_time);
+        self.change_accessible_share(defaults.accessible_share);
+    }
+
+    /// Turning the tag on keeps the one used before, or makes up a new one.
+    pub fn enable_sharer_tag(&mut self, is_enabled: bool) {
+        if !is_enabled {
+            self.sharer_tag = None;
+        } else if self.sharer_tag.is_none() {
+            self.sharer_tag = Some(share::random_sharer_tag());
+        }
+        self.persist();
+    }
+
+    // An edit that leaves too few letters keeps the previous tag
+    pub fn change_sharer_tag(&mut self, tag: &str) {
+        if let Some(tag) = share::sanitize_sharer_tag(tag) {
+            self.sharer_tag = Some(tag);
+            self.persist();
+        }
+    }
+
+    pub fn share_options(&self) -> ShareOptions {
+        ShareOptions {
+            show_solve_time: self.share_solve_time,
+            describe_rows: self.accessible_share,
+        }
+    }
+
+    pub fn pause_timer(&mut self) {
+        if let Some(game) = self.game.as_mut() {
+            game.pause_timer();
+        }
+        self.persist_game();
+    }
+
+    pub fn resume_timer(&mut self) {
+        if let Some(game) = self.game.as_mut() {
+            game.resume_timer();
+        }
+    }
+
+    pub fn change_hide_finished_word(&mut self, is_hidden: bool) {
+        self.hide_finished_word = is_hidden;
+        self.persist();
+    }
+
+    fn switch_active_game(&mut self) {
+        // The active game is saved only while it's active
+        self.flush();
+
+        let next_game = (
+            self.current_game_mode,
+            self.current_word_list,
+            self.current_word_length,
+        );
+
+        let previous = match mem::take(&mut self.game) {
+            Some(game) => game,
+            None => Box::new(Sanuli::default()) as Box<dyn Game>,
+        };
+
+        let previous_game = (
+            *previous.game_mode(),
+            *previous.word_list(),
+            previous.word_length(),
+        );
+
+        if next_game.0 == previous_game.0
+            && next_game.1 == previous_game.1
+            && next_game.2 == previous_game.2
+        {
+            return;
+        }
+
+        self.previous_game = previous_game;
+
+        self.game = Some(self.resume_game(next_game));
+        self.suspend_game(previous_game, previous);
+    }
+
+    // Restore a suspended game or create a new one
+    fn resume_game(&mut self, key: (GameMode, WordList, usize)) -> Box<dyn Game> {
+        self.background_order.retain(|suspended| *suspended != key);
+
+        match self.background_games.remove(&key) {
+            Some(game) => game,
+            None => self.open_game(key),
+        }
+    }
+
+    fn suspend_game(&mut self, key: (GameMode, WordList, usize), mut game: Box<dyn Game>) {
+        // An animation cut short by switching games isn't continued when switching back
+        game.clear_reset();
+        self.background_order.retain(|suspended| *suspended != key);
+        self.background_order.push(key);
+        self.background_games.insert(key, game);
+
+        // Evict the least recently used games, persisting them first so that nothing is lost
+        while self.background_order.len() > MAX_BACKGROUND_GAMES {
+            let evicted = self.background_order.remove(0);
+            if let Some(game) = self.background_games.remove(&evicted) {
+                self.persist_snapshot(game.as_ref());
+            }
+        }
+    }
+
+    fn update_game_statistics(&mut self, is_winner: bool, streak: usize, guesses_used: usize) {
+        // Count on top of what other tabs have saved meanwhile
+        self.adopt_newer_settings();
+
+        self.total_played += 1;
+        self.last_played = Some(Local::now().date_naive());
+
+        if is_winner {
+            self.total_solved += 1;
+
+            if self.guess_distribution.len() < guesses_used {
+                self.guess_distribution.resize(guesses_used, 0);
+            }
+            self.guess_distribution[guesses_used - 1] += 1;
+
+            if streak > self.max_streak {
+                self.max_streak = streak;
+            }
+
+            if let Some(milestone) = game::streak_milestone(streak) {
+                self.best_milestone = self.best_milestone.max(milestone);
+            }
+        }
+        self.persist();
+    }
+
+    // Remembers the word that ended a streak. A lost Neluli doesn't tell which of its words
+    // were missed, so only games of a single word are remembered.
+    fn update_broken_streaks(&mut self, result: &GameResult, previous_streak: usize) {
+        if result.is_winner || result.streak > 0 || previous_streak == 0 {
+            return;
+        }
+        if let [word] = &result.words[..] {
+            self.broken_streaks.push(BrokenStreak {
+                streak: previous_streak,
+                word: word.iter().collect(),
+                word_language: self.word_language,
+                date: Local::now().date_naive(),
+            });
+            let excess = self.broken_streaks.len().saturating_sub(MAX_BROKEN_STREAKS);
+            self.broken_streaks.drain(..excess);
+            self.persist();
+        }
+    }
+
+    // Only the first result of each daily is kept, a daily played again in another language isn't
+    fn update_daily_history(&mut self, date: NaiveDate, result: &GameResult) {
+        self.daily_history
+            .entry(game::daily_word_index(date))
+            .or_insert(result.is_winner.then_some(result.guesses_used));
+        self.persist();
+    }
+
+    fn update_daily_streak(&mut self, date: NaiveDate, is_winner: bool) {
+        self.cover_missed_dailies(date);
+
+        if self.last_daily_streak_date >= Some(date) {
+            return;
+        }
+
+        if is_winner {
+            self.daily_streak += 1;
+            self.max_daily_streak = self.max_daily_streak.max(self.daily_streak);
+            self.last_daily_streak_date = Some(date);
+
+            if self.daily_streak.is_multiple_of(DAILY_FREEZE_INTERVAL) {
+                self.daily_freezes += 1;
+            }
+        } else {
+            self.daily_streak = 0;
+        }
+        self.persist();
+    }
+
+    // The streak survives a missed day while there are freezes left to cover it
+    fn check_missed_dailies(&mut self) {
+        if self.cover_missed_dailies(Local::now().date_naive()) {
+            self.persist();
+        }
+    }
+
+    // Accounts for the days before `until` that nobody solved, returns whether anything changed
+    fn cover_missed_dailies(&mut self, until: NaiveDate) -> bool {
+        let mut is_changed = false;
+
+        while self.daily_streak > 0 {
+            let missed = match self.last_daily_streak_date.and_then(|date| date.succ_opt()) {
+                Some(missed) if missed < until => missed,
+                _ => break,
+            };
+
+            if self.daily_freezes > 0 {
+                self.daily_freezes -= 1;
+                self.frozen_days.push(missed);
+                self.last_daily_streak_date = Some(missed);
+            } else {
+                self.daily_streak = 0;
+            }
+            is_changed = true;
+        }
+
+        is_changed
+    }
+
+    #[cfg(web_sys_unstable_apis)]
+    pub fn share_emojis(&self) -> Option<String> {
+        self.game
+            .as_ref()?
+            .share_emojis(self.theme, self.share_options())
+    }
+
+    #[cfg(web_sys_unstable_apis)]
+    pub fn share_emoji_counts(&self) -> Option<String> {
+        self.game
+            .as_ref()?
+            .share_emoji_counts(self.theme, self.share_options())
+    }
+
+    #[cfg(web_sys_unstable_apis)]
+    pub fn share_link(&self) -> Option<String> {
+        let payload = self
+            .game
+            .as_ref()?
+            .share_payload(self.sharer_tag.as_deref())?;
+
+        let window: Window = window().expect("window not available");
+        let base_url = window.location().origin().ok()?;
+
+        Some(format!("{}/?peli={}", base_url, payload))
+    }
+
+    #[cfg(web_sys_unstable_apis)]
+    pub fn share_progress_link(&self) -> Option<String> {
+        let payload = self
+            .game
+            .as_ref()?
+            .progress_payload(self.sharer_tag.as_deref())?;
+
+        let window: Window = window().expect("window not available");
+        let base_url = window.location().origin().ok()?;
+
+        Some(format!("{}/?peli={}", base_url, payload))
+    }
+
+    pub fn reveal_hidden_tiles(&mut self) {
+        if let Some(game) = self.game.as_mut() {
+            game.reveal_hidden_tiles();
+        }
+    }
+
+    pub fn clear_reset(&mut self) {
+        if let Some(game) = self.game.as_mut() {
+            game.clear_reset();
+        }
+    }
+
+    pub fn reset_game(&mut self) {
+        if let Some(game) = self.game.as_mut() {
+            game.reset();
+        }
+    }
+
+    fn new_or_rehydrate_game(&self, game: (GameMode, WordList, usize)) -> Box<dyn Game> {
+        self.rehydrate_stored_game(game)
+            .unwrap_or_else(|| self.create_game(game))
+    }
+
+    // Like new_or_rehydrate_game for a game that is played next, a new word gets recorded
+    fn open_game(&self, game: (GameMode, WordList, usize)) -> Box<dyn Game> {
+        self.rehydrate_stored_game(game).unwrap_or_else(|| {
+            let game = self.create_game(game);
+            self.record_drawn_words(game.as_ref());
+            game
+        })
+    }
+
+    fn rehydrate_stored_game(&self, game: (GameMode, WordList, usize)) -> Option<Box<dyn Game>> {
+        let (game_mode, word_list, word_length) = game;
+        let key = game::storage_key(self.word_language, &game_mode, &word_list, word_length);
+
+        let snapshot = self.persistence.get(&key).ok()?;
+        match self.rehydrate_game(game_mode, snapshot) {
+            Ok(mut game) => {
+                game.set_emoji_set(self.emoji_set);
+                game.set_language(self.language);
+                game.set_weighted_selection(self.weighted_word_selection);
+                game.set_hide_typing_hints(self.hide_typing_hints);
+                game.set_warn_contradictions(self.warn_contradictions);
+                game.set_show_candidates(self.show_candidates);
+                Some(game)
+            }
+            Err(err) => {
+                log::warn!("Discarding stored game {}: {}", key, err);
+                self.remove_stored_game(&key);
+                None
+            }
+        }
+    }
+
+    fn create_game(&self, game: (GameMode, WordList, usize)) -> Box<dyn Game> {
+        let (game_mode, word_list, word_length) = game;
+        let key = game::storage_key(self.word_language, &game_mode, &word_list, word_length);
+
+        // The game was lost or unreadable, but the streak it had is still known
+        let lost_streak = self.streaks.get(&key).copied().filter(|streak| *streak > 0);
+
+        let mut game: Box<dyn Game> = match game_mode {
+            // Two-player games get their real word once it has been typed in
+            GameMode::Classic
+            | GameMode::Relay
+            | GameMode::DailyWord(_)
+            | GameMode::Shared
+            | GameMode::Hotseat
+            | GameMode::Practice(_) => Box::new(Sanuli::new(
+                self.word_language,
+                game_mode,
+                word_list,
+                word_length,
+                DEFAULT_MAX_GUESSES,
+                self.allow_profanities,
+                self.word_lists.clone(),
+            )),
+            GameMode::Quadruple => Box::new(Neluli::new(
+                self.word_language,
+                word_list,
+                word_length,
+                self.allow_profanities,
+                self.word_lists.clone(),
+            )),
+        };
+        game.set_emoji_set(self.emoji_set);
+        game.set_language(self.language);
+        game.set_weighted_selection(self.weighted_word_selection);
+        game.set_hide_typing_hints(self.hide_typing_hints);
+        game.set_warn_contradictions(self.warn_contradictions);
+        game.set_show_candidates(self.show_candidates);
+        if let GameMode::DailyWord(date) = game_mode {
+            if date < Local::now().date_naive() {
+                game.mark_late();
+            }
+        }
+        if let Some(streak) = lost_streak {
+            game.restore_streak(streak);
+        }
+        game
+    }
+
+    // Only words drawn for a game are recorded, never a daily word ahead of its day or a
+    // word that was given by another player
+    fn record_drawn_words(&self, game: &dyn Game) {
+        if !self.is_debug {
+            return;
+        }
+        match *game.game_mode() {
+            GameMode::Shared | GameMode::Hotseat => return,
+            GameMode::DailyWord(date) if date > Local::now().date_naive() => return,
+            _ => {}
+        }
+
+        let mut drawn_words = self.drawn_words.borrow_mut();
+        for board in game.boards() {
+            drawn_words.push_back(DrawnWord {
+                drawn_at: Utc::now(),
+                word_language: game.word_language(),
+                game_mode: *game.game_mode(),
+                word_list: *game.word_list(),
+                word_length: game.word_length(),
+                word: board.word.iter().collect(),
+            });
+        }
+        while drawn_words.len() > MAX_DRAWN_WORDS {
+            drawn_words.pop_front();
+        }
+    }
+
+    pub fn is_debug(&self) -> bool {
+        self.is_debug
+    }
+
+    /// The latest drawn words as text to paste into a bug report, only in debug mode.
+    pub fn drawn_words_text(&self) -> Option<String> {
+        if !self.is_debug {
+            return None;
+        }
+
+        let lines = self
+            .drawn_words
+            .borrow()
+            .iter()
+            .map(|drawn| {
+                format!(
+                    "{} {} {} {} {} {}",
+                    drawn.drawn_at.format("%Y-%m-%d %H:%M:%S"),
+                    drawn.word_language.code(),
+                    drawn.game_mode,
+                    drawn.word_list,
+                    drawn.word_length,
+                    drawn.word
+                )
+            })
+            .collect::<Vec<_>>();
+        Some(lines.join("\n"))
+    }
+
+    fn rehydrate_game(
+        &self,
+        game_mode: GameMode,
+        snapshot: Value,
+    ) -> serde_json::Result<Box<dyn Game>> {
+        match game_mode {
+            GameMode::Quadruple => {
+                Neluli::rehydrate(snapshot, self.allow_profanities, self.word_lists.clone())
+                    .map(|game| Box::new(game) as Box<dyn Game>)
+            }
+            _ => Sanuli::rehydrate(snapshot, self.allow_profanities, self.word_lists.clone())
+                .map(|game| Box::new(game) as Box<dyn Game>),
+        }
+    }
+
+    // Marks the active game to be saved on the next flush
+    fn persist_game(&self) {
+        self.has_unsaved_game.set(true);
+    }
+
+    // Marks the settings to be saved on the next flush
+    fn persist(&self) {
+        self.has_unsaved_settings.set(true);
+    }
+
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.has_unsaved_settings.get() || self.has_unsaved_game.get()
+    }
+
+    /// Writes the changes marked by persist and persist_game. Saving is batched, the app
+    /// flushes after a quiet moment and when the page is hidden or closed.
+    pub fn flush(&mut self) {
+        if self.has_unsaved_settings.take() {
+            self.save_settings();
+        }
+        if self.has_unsaved_game.take() {
+            if let Some(game) = self.game.as_ref() {
+                self.persist_snapshot(game.as_ref());
+            }
+        }
+    }
+
+    fn persist_snapshot(&self, game: &dyn Game) {
+        if let Some(key) = game.storage_key() {
+            let result = game
+                .snapshot()
+                .map_err(StorageError::SerdeError)
+                .and_then(|snapshot| self.store(&key, snapshot));
+            if result.is_ok() {
+                if let Some(stored_games) = self.stored_games.borrow_mut().as_mut() {
+                    let summary = (*game.game_mode(), *game.word_list(), game.word_length());
+                    stored_games.insert(key.clone(), SuspendedGame::new(summary, game));
+                }
+            }
+            self.record_storage_result(&key, result);
+        }
+    }
+
+    fn save_settings(&mut self) {
+        if matches!(self.current_game_mode, GameMode::Shared | GameMode::Hotseat) {
+            // Never persist shared or two-player games
+            return;
+        }
+
+        let stored_revision = self
+            .persistence
+            .get("settings")
+            .ok()
+            .and_then(|settings| settings.get("revision")?.as_u64())
+            .unwrap_or(0);
+
+        if stored_revision > self.revision.get() {
+            log::warn!(
+                "Settings revision {} was saved by another tab, merging with it",
+                stored_revision
+            );
+            if let Some(merged) = self.merge_newer_settings() {
+                self.adopt_settings(merged);
+            }
+        }
+        self.revision
+            .set(stored_revision.max(self.revision.get()) + 1);
+
+        let result = serde_json::to_value(&*self)
+            .map_err(StorageError::SerdeError)
+            .and_then(|settings| {
+                self.store("settings", settings.clone())?;
+                Ok(settings)
+            });
+        let result = result.map(|settings| self.saved_settings = Some(settings));
+        self.record_storage_result("settings", result);
+    }
+
+    fn store(&self, key: &str, value: Value) -> Result<(), StorageError> {
+        match self.persistence.set(key, value.clone()) {
+            Err(err) if is_quota_exceeded(&err) => {
+                // Drop every old daily game and try once more
+                self.cleanup_daily_games(0);
+                self.persistence.set(key, value)
+            }
+            result => result,
+        }
+    }
+
+    fn record_storage_result(&self, key: &str, result: Result<(), StorageError>) {
+        let failure = match result {
+            Ok(()) => {
+                self.storage_failures.borrow_mut().remove(key);
+                return;
+            }
+            Err(err) if is_quota_exceeded(&err) => StorageFailure::QuotaExceeded,
+            Err(StorageError::SerdeError(err)) => {
+                log::error!("Failed to serialize {}: {}", key, err);
+                StorageFailure::Serialization
+            }
+            Err(err) => {
+                log::warn!("Failed to save {}: {}", key, err);
+                StorageFailure::Unavailable
+            }
+        };
+
+        self.storage_failures
+            .borrow_mut()
+            .insert(key.to_owned(), failure);
+        self.is_storage_warning_dismissed.set(false);
+    }
+
+    fn rehydrate(persistence: Rc<dyn GamePersistence>) -> Result<Self, StorageError> {
+        let settings = persistence.get("settings")?;
+        Self::from_settings(settings, persistence)
+    }
+
+    fn from_settings(
+        settings: Value,
+        persistence: Rc<dyn GamePersistence>,
+    ) -> Result<Self, StorageError> {
+        // Without the migrations, settings too old to deserialize are started over
+        #[cfg(feature = "migration")]
+        let settings = migration::migrate(settings);
+        let mut manager: Self =
+            serde_json::from_value(settings.clone()).map_err(StorageError::SerdeError)?;
+        manager.persistence = persistence;
+        manager.saved_settings = Some(settings);
+        Ok(manager)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Days;
+
+    use super::*;
+    use crate::persistence::MemoryPersistence;
+
+    // A manager with all the words, playing the given game and saving into memory
+    fn test_manager(game_mode: GameMode, word_list: WordList, word_length: usize) -> Manager {
+        test_manager_with_persistence(
+            Rc::new(MemoryPersistence::default()),
+            game_mode,
+            word_list,
+            word_length,
+        )
+    }
+
+    fn test_manager_with_persistence(
+        persistence: Rc<dyn GamePersistence>,
+        game_mode: GameMode,
+        word_list: WordList,
+        word_length: usize,
+    ) -> Manager {
+        test_manager_with_words(
+            parse_all_words(),
+            persistence,
+            game_mode,
+            word_list,
+            word_length,
+        )
+    }
+
+    fn test_manager_with_words(
+        word_lists: Rc<WordLists>,
+        persistence: Rc<dyn GamePersistence>,
+        game_mode: GameMode,
+        word_list: WordList,
+        word_length: usize,
+    ) -> Manager {
+        let mut manager = Manager {
+            word_lists,
+            persistence,
+            ..Manager::default()
+        };
+        manager.game = Some(manager.new_or_rehydrate_game((game_mode, word_list, word_length)));
+        manager
+    }
+
+    // Finnish common and full lists of just the given words
+    fn fixture_words(words: &[&str]) -> Rc<WordLists> {
+        let mut word_lists: WordLists = HashMap::new();
+        for word in words {
+            let chars: Vec<char> = word.chars().collect();
+            for list in [WordList::Full, WordList::Common] {
+                word_lists
+                    .entry((WordLanguage::Finnish, list, chars.len()))
+                    .or_default()
+                    .insert(chars.clone());
+            }
+        }
+        Rc::new(word_lists)
+    }
+
+    #[test]
+    fn dirty_word_list_lines_are_skipped() {
+        let words = "KISSA\n\n   \nKOIRA \r\nkissa\nTAL-VI\nMÜSLI\nVÄRIT\n\n";
+        let parsed = parse_word_list(words)
+            .map(|word| word.iter().collect::<String>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(parsed, vec!["KISSA", "KOIRA", "VÄRIT"]);
+        assert_eq!(parse_word_list("\n \t\n").count(), 0);
+    }
+
+    #[test]
+    fn word_lists_have_no_empty_words() {
+        let word_lists = parse_all_words();
+        assert!(word_lists
+            .keys()
+            .all(|(_, _, word_length)| *word_length > 0));
+        assert!(word_lists.values().flatten().all(|word| !word.is_empty()));
+    }
+
+    #[test]
+    fn key_states_combine_any_number_of_boards() {
+        let states = [TileState::Correct, TileState::Absent, TileState::Present];
+
+        assert!(KeyState::combine(&[]) == KeyState::Single(TileState::Unknown));
+        assert!(KeyState::combine(&states[..1]) == KeyState::Single(TileState::Correct));
+        assert!(KeyState::combine(&states).states() == states);
+
+        let many = [TileState::Present; MAX_KEY_SEGMENTS + 1];
+        assert_eq!(KeyState::combine(&many).states().len(), MAX_KEY_SEGMENTS);
+
+        let key_state = KeyState::combine(&states);
+        let stored = serde_json::to_value(key_state).unwrap();
+        assert!(serde_json::from_value::<KeyState>(stored).unwrap() == key_state);
+    }
+
+    #[test]
+    fn key_color_is_the_most_informative_state() {
+        let combine = |states: &[TileState]| KeyState::combine(states).best();
+
+        assert!(
+            combine(&[
+                TileState::Absent,
+                TileState::Absent,
+                TileState::Correct,
+                TileState::Absent
+            ]) == TileState::Correct
+        );
+        assert!(combine(&[TileState::Absent, TileState::Present]) == TileState::Present);
+        assert!(combine(&[TileState::Absent, TileState::Unknown]) == TileState::Unknown);
+        assert!(combine(&[TileState::Absent; 4]) == TileState::Absent);
+    }
+
+    #[test]
+    fn every_game_mode_has_its_own_name() {
+        let date = NaiveDate::from_ymd_opt(2022, 1, 7).unwrap();
+        let modes = [
+            GameMode::Classic,
+            GameMode::Relay,
+            GameMode::DailyWord(date),
+            GameMode::Shared,
+            GameMode::Quadruple,
+            GameMode::Hotseat,
+            GameMode::Practice(None),
+            GameMode::Practice(Some('K')),
+        ];
+
+        // A new game mode doesn't compile here before it's added to the list above
+        for mode in &modes {
+            match mode {
+                GameMode::Classic
+                | GameMode::Relay
+                | GameMode::DailyWord(_)
+                | GameMode::Shared
+                | GameMode::Quadruple
+                | GameMode::Hotseat
+                | GameMode::Practice(_) => {}
+            }
+        }
+
+        let names = modes
+            .iter()
+            .map(|mode| mode.to_string())
+            .collect::<HashSet<_>>();
+        assert_eq!(names.len(), modes.len());
+        assert!(!names.contains(""));
+    }
+
+    #[test]
+    fn background_games_are_bounded() {
+        let persistence = Rc::new(MemoryPersistence::default());
+        let mut manager = test_manager_with_persistence(
+            persistence.clone(),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+
+        let epoch = NaiveDate::from_ymd_opt(2022, 1, 7).unwrap();
+        let first_daily = GameMode::DailyWord(epoch);
+
+        manager.change_game_mode(first_daily);
+        for character in "KOIRA".chars() {
+            manager.push_character(character);
+        }
+
+        for day in 1..=MAX_BACKGROUND_GAMES as u64 + 2 {
+            manager.change_game_mode(GameMode::DailyWord(epoch + Days::new(day)));
+        }
+
+        assert_eq!(manager.background_games.len(), MAX_BACKGROUND_GAMES);
+        assert!(!manager.background_games.contains_key(&(
+            first_daily,
+            WordList::Daily,
+            DAILY_WORD_LEN
+        )));
+
+        // The evicted game was persisted with its unfinished guess
+        manager.change_game_mode(first_daily);
+        let game = manager.game.as_ref().unwrap();
+        assert!(*game.game_mode() == first_daily);
+        assert_eq!(game.last_guess(), "KOIRA");
+    }
+
+    #[test]
+    fn entry_animation_is_not_replayed_after_switching_back() {
+        let mut manager = test_manager(GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+
+        manager.next_word();
+        assert!(manager.game.as_ref().unwrap().is_reset());
+
+        manager.change_game_mode(GameMode::Relay);
+        assert!(!manager.game.as_ref().unwrap().is_reset());
+        manager.change_game_mode(GameMode::Classic);
+        assert!(!manager.game.as_ref().unwrap().is_reset());
+
+        manager.next_word();
+        manager.clear_reset();
+        assert!(!manager.game.as_ref().unwrap().is_reset());
+    }
+
+    #[derive(Default)]
+    struct FlakyPersistence {
+        items: MemoryPersistence,
+        is_failing: Cell<bool>,
+    }
+
+    impl GamePersistence for FlakyPersistence {
+        fn get(&self, key: &str) -> Result<Value, StorageError> {
+            self.items.get(key)
+        }
+
+        fn set(&self, key: &str, value: Value) -> Result<(), StorageError> {
+            if self.is_failing.get() {
+                return Err(StorageError::KeyNotFound(key.to_owned()));
+            }
+            self.items.set(key, value)
+        }
+
+        fn remove(&self, key: &str) {
+            self.items.remove(key)
+        }
+
+        fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+            self.items.keys_with_prefix(prefix)
+        }
+    }
+
+    #[test]
+    fn storage_failures_are_surfaced_and_retried() {
+        let persistence = Rc::new(FlakyPersistence::default());
+        let mut manager = test_manager_with_persistence(
+            persistence.clone(),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+
+        persistence.is_failing.set(true);
+        manager.change_theme(Theme::Colorblind);
+        manager.flush();
+        assert!(manager.storage_failure() == Some(StorageFailure::Unavailable));
+
+        manager.dismiss_storage_warning();
+        assert!(manager.storage_failure().is_none());
+
+        persistence.is_failing.set(false);
+        manager.retry_failed_saves();
+        assert!(manager.storage_failures.borrow().is_empty());
+
+        let settings: Manager =
+            crate::persistence::get_as(persistence.as_ref(), "settings").unwrap();
+        assert!(settings.theme == Theme::Colorblind);
+    }
+
+    #[test]
+    fn saves_wait_for_a_flush_until_the_game_ends() {
+        let persistence = Rc::new(MemoryPersistence::default());
+        let mut manager = test_manager_with_persistence(
+            persistence.clone(),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+        let key = game::storage_key(
+            WordLanguage::Finnish,
+            &GameMode::Classic,
+            &WordList::Common,
+            5,
+        );
+
+        manager.change_theme(Theme::Colorblind);
+        manager.change_emoji_set(EmojiSet::Off);
+        assert!(manager.has_unsaved_changes());
+        assert!(persistence.get("settings").is_err());
+
+        manager.flush();
+        assert!(!manager.has_unsaved_changes());
+        assert!(persistence.get("settings").is_ok());
+
+        let word = manager.game.as_ref().unwrap().word();
+        for character in word {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+        assert!(!manager.has_unsaved_changes());
+        assert!(persistence.get(&key).is_ok());
+
+        let settings: Manager =
+            crate::persistence::get_as(persistence.as_ref(), "settings").unwrap();
+        assert_eq!(settings.total_solved, 1);
+    }
+
+    #[test]
+    fn corrupt_stored_games_are_replaced() {
+        let persistence = Rc::new(MemoryPersistence::default());
+        let key = game::storage_key(
+            WordLanguage::Finnish,
+            &GameMode::Classic,
+            &WordList::Common,
+            5,
+        );
+        persistence
+            .set(&key, serde_json::json!({ "current_guess": 99 }))
+            .unwrap();
+
+        let manager = Manager {
+            word_lists: parse_all_words(),
+            persistence: persistence.clone(),
+            ..Manager::default()
+        };
+        let game = manager.new_or_rehydrate_game((GameMode::Classic, WordList::Common, 5));
+
+        assert!(game.is_guessing());
+        assert!(persistence.get(&key).is_err());
+    }
+
+    #[test]
+    fn streak_outlives_a_lost_game() {
+        let persistence = Rc::new(MemoryPersistence::default());
+        let mut manager = test_manager_with_persistence(
+            persistence.clone(),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+        let key = game::storage_key(
+            WordLanguage::Finnish,
+            &GameMode::Classic,
+            &WordList::Common,
+            5,
+        );
+
+        let word = manager.game.as_ref().unwrap().word();
+        for character in word {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+        assert_eq!(manager.streaks.get(&key), Some(&1));
+
+        persistence
+            .set(&key, serde_json::json!({ "current_guess": 99 }))
+            .unwrap();
+        let game = manager.new_or_rehydrate_game((GameMode::Classic, WordList::Common, 5));
+
+        assert!(game.is_guessing());
+        assert_eq!(game.streak(), 1);
+        assert!(!game.message().is_empty());
+    }
+
+    #[test]
+    fn statistics_from_other_tabs_are_kept() {
+        let persistence = Rc::new(MemoryPersistence::default());
+        let new_tab = || {
+            test_manager_with_persistence(
+                persistence.clone(),
+                GameMode::Classic,
+                WordList::Common,
+                DEFAULT_WORD_LENGTH,
+            )
+        };
+        let mut first = new_tab();
+        let mut second = new_tab();
+
+        first.update_game_statistics(true, 12, 3);
+        first.flush();
+        second.update_game_statistics(false, 0, DEFAULT_MAX_GUESSES);
+        assert_eq!(second.total_played, 2);
+        assert_eq!(second.total_solved, 1);
+        assert_eq!(second.guess_distribution, vec![0, 0, 1]);
+        assert_eq!(second.best_milestone, 10);
+
+        second.change_theme(Theme::Colorblind);
+        second.change_keyboard_layout(KeyboardLayout::Alphabetical);
+        second.flush();
+        first.sync_external_change("settings");
+        assert!(first.theme == Theme::Colorblind);
+        assert!(first.keyboard_layout == KeyboardLayout::Alphabetical);
+        assert_eq!(first.total_played, 2);
+    }
+
+    #[test]
+    fn settings_saved_meanwhile_by_another_tab_are_kept() {
+        let persistence = Rc::new(MemoryPersistence::default());
+        let mut first = test_manager_with_persistence(
+            persistence.clone(),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+        first.persist();
+        first.flush();
+        let mut second = Manager::rehydrate(persistence.clone()).unwrap();
+
+        first.change_theme(Theme::Colorblind);
+        first.flush();
+        // The second tab saves before hearing of the change
+        second.change_keyboard_layout(KeyboardLayout::Alphabetical);
+        second.flush();
+        assert!(second.theme == Theme::Colorblind);
+
+        let stored = Manager::rehydrate(persistence.clone()).unwrap();
+        assert!(stored.theme == Theme::Colorblind);
+        assert!(stored.keyboard_layout == KeyboardLayout::Alphabetical);
+    }
+
+    #[test]
+    fn finished_word_stays_hidden_until_revealed() {
+        let mut manager = test_manager(GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+        manager.change_hide_finished_word(true);
+
+        let word = manager.game.as_ref().unwrap().word();
+        for character in word {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+
+        let game = manager.game.as_ref().unwrap();
+        assert!(game.is_hidden());
+        assert!(game
+            .share_emojis(manager.theme, manager.share_options())
+            .is_some());
+
+        manager.reveal_hidden_tiles();
+        let game = manager.game.as_ref().unwrap();
+        assert!(!game.is_hidden());
+        assert!(game.message().starts_with("Löysit sanan!"));
+    }
+
+    #[test]
+    fn hotseat_game_uses_the_entered_word() {
+        let persistence = Rc::new(MemoryPersistence::default());
+        let mut manager = test_manager_with_persistence(
+            persistence.clone(),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+        manager.change_language(Language::English);
+        manager.change_game_mode(GameMode::Hotseat);
+        assert_eq!(
+            manager.hotseat_entry().unwrap().message,
+            "Type in the secret word and hand the device to the guesser"
+        );
+
+        for character in "KISSX".chars() {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+        assert_eq!(
+            manager.hotseat_entry().unwrap().message,
+            "Not in the word list."
+        );
+
+        manager.pop_character();
+        manager.push_character('A');
+        manager.submit_guess();
+        assert!(manager.hotseat_entry().is_none());
+
+        let game = manager.game.as_ref().unwrap();
+        assert!(*game.game_mode() == GameMode::Hotseat);
+        assert_eq!(game.word(), vec!['K', 'I', 'S', 'S', 'A']);
+
+        for character in "KISSA".chars() {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+        assert!(manager.game.as_ref().unwrap().is_winner());
+        assert_eq!(manager.total_played, 0);
+        assert!(persistence.keys_with_prefix("game|\"Hotseat").is_empty());
+
+        manager.next_word();
+        assert!(manager.hotseat_entry().is_some());
+
+        manager.change_previous_game_mode();
+        assert!(manager.current_game_mode == GameMode::Classic);
+        assert!(manager.current_word_list == WordList::Common);
+        assert!(manager.hotseat_entry().is_none());
+    }
+
+    #[test]
+    fn practice_word_contains_the_chosen_letter() {
+        let mut manager = test_manager_with_words(
+            fixture_words(&["KISSA", "KOIRA", "TALVI"]),
+            Rc::new(MemoryPersistence::default()),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+        assert!(manager.practice_letters().contains(&'V'));
+        assert!(!manager.practice_letters().contains(&'Ö'));
+
+        manager.change_game_mode(GameMode::Practice(Some('V')));
+        assert_eq!(
+            manager.game.as_ref().unwrap().word(),
+            vec!['T', 'A', 'L', 'V', 'I']
+        );
+
+        manager.change_game_mode(GameMode::Practice(Some('Ö')));
+        let game = manager.game.as_ref().unwrap();
+        assert_eq!(game.message(), "Yhdessäkään sanulissa ei ole kirjainta Ö");
+        assert_eq!(game.word().len(), DEFAULT_WORD_LENGTH);
+
+        for character in game.word() {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+        assert!(manager.game.as_ref().unwrap().is_winner());
+        assert_eq!(manager.total_played, 0);
+    }
+
+    #[test]
+    fn themed_words_are_accepted_as_guesses() {
+        let mut manager = test_manager(GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+        manager.change_word_list(WordList::Animals);
+        manager.change_word_length(6);
+
+        let game = manager.game.as_ref().unwrap();
+        assert!(
+            manager.word_lists[&(WordLanguage::Finnish, WordList::Animals, 6)]
+                .contains(&game.word())
+        );
+
+        for character in "MAJAVA".chars() {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+        assert!(!manager.game.as_ref().unwrap().is_unknown());
+
+        let settings = serde_json::to_value(&manager).unwrap();
+        assert_eq!(settings["current_word_list"], "Animals");
+    }
+
+    #[test]
+    fn suspended_games_can_be_continued() {
+        let words = fixture_words(&["KISSA", "TALVI", "KISSAT"]);
+        let persistence = Rc::new(MemoryPersistence::default());
+        let mut manager = test_manager_with_words(
+            words.clone(),
+            persistence.clone(),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+
+        for character in "TALVI".chars() {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+        let first_game = (GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+
+        manager.change_word_length(6);
+        manager.change_word_list(WordList::Full);
+        assert!(
+            manager.suspended_games()
+                == vec![SuspendedGame {
+                    game: first_game,
+                    guesses_used: 1,
+                    is_finished: manager.background_games[&first_game].is_winner(),
+                }]
+        );
+
+        // A fresh page only knows about the stored games
+        let mut manager =
+            test_manager_with_words(words, persistence, GameMode::Classic, WordList::Full, 6);
+        assert_eq!(manager.suspended_games().len(), 1);
+
+        manager.continue_game(first_game);
+        let game = manager.game.as_ref().unwrap();
+        assert_eq!(game.word_length(), DEFAULT_WORD_LENGTH);
+        assert!(*game.word_list() == WordList::Common);
+        assert_eq!(game.boards()[0].guesses[0][0].0, 'T');
+        assert!(manager.suspended_games().is_empty());
+    }
+
+    #[test]
+    fn games_saved_by_another_tab_are_suspended_after_the_change() {
+        let words = fixture_words(&["KISSA", "TALVI", "KISSAT"]);
+        let persistence = Rc::new(MemoryPersistence::default());
+        let mut other_tab = test_manager_with_words(
+            words.clone(),
+            persistence.clone(),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+        let mut manager =
+            test_manager_with_words(words, persistence, GameMode::Classic, WordList::Full, 6);
+        assert!(manager.suspended_games().is_empty());
+
+        for character in "TALVI".chars() {
+            other_tab.push_character(character);
+        }
+        other_tab.submit_guess();
+        other_tab.flush();
+        assert!(manager.suspended_games().is_empty());
+
+        let key = game::storage_key(
+            WordLanguage::Finnish,
+            &GameMode::Classic,
+            &WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+        manager.sync_external_change(&key);
+        assert_eq!(manager.suspended_games().len(), 1);
+    }
+
+    #[test]
+    fn leaving_an_unfinished_daily_word_is_confirmed() {
+        let mut manager = test_manager_with_words(
+            fixture_words(&["KISSA", "KOIRA"]),
+            Rc::new(MemoryPersistence::default()),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+
+        let daily = GameMode::DailyWord(NaiveDate::from_ymd_opt(2022, 1, 7).unwrap());
+        manager.change_game_mode(daily);
+        manager.change_game_mode(GameMode::Relay);
+        assert!(manager.current_game_mode == GameMode::Relay);
+
+        manager.change_game_mode(daily);
+        let word = manager.game.as_ref().unwrap().word();
+        let guess = if word.iter().collect::<String>() == "KOIRA" {
+            "KISSA"
+        } else {
+            "KOIRA"
+        };
+        for character in guess.chars() {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+
+        manager.change_game_mode(GameMode::Classic);
+        assert!(manager.current_game_mode == daily);
+        assert!(manager.pending_game_mode() == Some(GameMode::Classic));
+
+        manager.cancel_pending_game_mode();
+        manager.confirm_pending_game_mode();
+        assert!(manager.current_game_mode == daily);
+
+        manager.change_game_mode(GameMode::Classic);
+        manager.confirm_pending_game_mode();
+        assert!(manager.current_game_mode == GameMode::Classic);
+        assert!(manager.pending_game_mode().is_none());
+    }
+
+    #[test]
+    fn contradicting_guess_is_confirmed_through_the_manager() {
+        let mut manager = test_manager(GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+        manager.change_warn_contradictions(true);
+        manager.change_game_mode(GameMode::Hotseat);
+        for character in "TALVI".chars() {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+
+        let current_guess =
+            |manager: &Manager| manager.game.as_ref().unwrap().boards()[0].current_guess;
+        for character in "KOIRA".chars() {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+        assert_eq!(current_guess(&manager), 1);
+
+        // K was found not to be in the word
+        for character in "KISSA".chars() {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+        assert_eq!(current_guess(&manager), 1);
+        assert!(manager.game.as_ref().unwrap().is_contradiction_pending());
+
+        manager.submit_guess();
+        assert_eq!(current_guess(&manager), 2);
+        assert!(!manager.game.as_ref().unwrap().is_contradiction_pending());
+    }
+
+    #[test]
+    fn submission_after_the_final_row_is_ignored() {
+        let mut manager = test_manager_with_words(
+            fixture_words(&["KISSA", "KOIRA"]),
+            Rc::new(MemoryPersistence::default()),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+        let word = manager.game.as_ref().unwrap().word();
+        let guess = if word.iter().collect::<String>() == "KOIRA" {
+            "KISSA"
+        } else {
+            "KOIRA"
+        };
+
+        for _ in 0..DEFAULT_MAX_GUESSES {
+            for character in guess.chars() {
+                manager.push_character(character);
+            }
+            manager.submit_guess();
+        }
+        // Enter and ARVAA pressed at once on the final row submit it twice
+        let message = manager.game.as_ref().unwrap().message();
+        manager.submit_guess();
+        assert_eq!(manager.game.as_ref().unwrap().message(), message);
+
+        let game = manager.game.as_ref().unwrap();
+        assert!(!game.is_guessing());
+        assert_eq!(game.guesses_used(), Some(DEFAULT_MAX_GUESSES));
+        assert_eq!(manager.total_played, 1);
+    }
+
+    #[test]
+    #[cfg(feature = "swedish")]
+    fn word_languages_keep_games_of_their_own() {
+        let mut manager = test_manager(GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+        let finnish_word = manager.game.as_ref().unwrap().word();
+        for character in "KISSA".chars() {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+
+        manager.change_word_language(WordLanguage::Swedish);
+        let game = manager.game.as_ref().unwrap();
+        assert!(game.word_language() == WordLanguage::Swedish);
+        assert!(
+            manager.word_lists[&(WordLanguage::Swedish, WordList::Common, 5)]
+                .contains(&game.word())
+        );
+        assert!(game.boards()[0].guesses[0].is_empty());
+
+        manager.change_word_language(WordLanguage::Finnish);
+        let game = manager.game.as_ref().unwrap();
+        assert_eq!(game.word(), finnish_word);
+        assert_eq!(game.boards()[0].guesses[0].len(), 5);
+
+        // Themed lists are only in Finnish
+        manager.change_word_list(WordList::Animals);
+        manager.change_word_language(WordLanguage::Swedish);
+        assert!(manager.current_word_list == WordList::Common);
+    }
+
+    #[test]
+    fn language_change_reaches_the_current_game() {
+        let mut manager = test_manager(GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+
+        manager.change_language(Language::English);
+        manager.push_character('K');
+        manager.submit_guess();
+
+        assert_eq!(
+            manager.game.as_ref().unwrap().message(),
+            "Not enough letters!"
+        );
+        assert!(manager.language == Language::English);
+    }
+
+    #[test]
+    fn yesterdays_daily_word_is_played_late() {
+        let persistence = Rc::new(MemoryPersistence::default());
+        let mut manager = test_manager_with_persistence(
+            persistence.clone(),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+        manager.check_date();
+        assert!(manager.is_late_daily_available());
+
+        let yesterday = Local::now().date_naive().pred_opt().unwrap();
+        manager.change_game_mode(GameMode::DailyWord(yesterday));
+        let game = manager.game.as_ref().unwrap();
+        assert!(game.title().ends_with("(myöhässä)"));
+
+        for character in game.word() {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+
+        let game = manager.game.as_ref().unwrap();
+        let share = game
+            .share_emojis(Theme::Dark, ShareOptions::default())
+            .unwrap();
+        assert!(share.lines().next().unwrap().contains("(myöhässä)"));
+        assert_eq!(manager.total_solved, 1);
+        assert_eq!(manager.max_streak, 0);
+        assert!(!manager.is_late_daily_available());
+
+        manager.check_date();
+        assert!(!manager.is_new_daily_available());
+
+        // A new page finds the finished daily in storage
+        let mut manager = test_manager_with_persistence(
+            persistence,
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+        manager.check_date();
+        assert!(!manager.is_late_daily_available());
+        assert!(manager.is_daily_pending());
+    }
+
+    #[test]
+    fn freezes_cover_missed_daily_words() {
+        let mut manager = Manager {
+            persistence: Rc::new(MemoryPersistence::default()),
+            ..Manager::default()
+        };
+        let epoch = NaiveDate::from_ymd_opt(2022, 1, 7).unwrap();
+
+        for day in 0..DAILY_FREEZE_INTERVAL as u64 {
+            manager.update_daily_streak(epoch + Days::new(day), true);
+        }
+        assert_eq!(manager.daily_freezes, 1);
+
+        // The eighth day is missed and covered by the freeze
+        manager.update_daily_streak(epoch + Days::new(8), true);
+        assert_eq!(manager.daily_streak, 8);
+        assert_eq!(manager.daily_freezes, 0);
+        assert!(manager.frozen_days == vec![epoch + Days::new(7)]);
+
+        manager.cover_missed_dailies(epoch + Days::new(11));
+        assert_eq!(manager.daily_streak, 0);
+        assert_eq!(manager.max_daily_streak, 8);
+    }
+
+    #[test]
+    fn new_game_is_not_available_for_daily_words() {
+        let mut manager = test_manager(GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+        manager.push_character('K');
+        manager.new_game();
+        assert!(manager.game.as_ref().unwrap().last_guess().is_empty());
+
+        let daily = GameMode::DailyWord(NaiveDate::from_ymd_opt(2022, 1, 7).unwrap());
+        manager.change_game_mode(daily);
+        manager.push_character('K');
+        manager.new_game();
+        assert_eq!(manager.game.as_ref().unwrap().last_guess(), "K");
+    }
+
+    #[test]
+    fn emoji_set_is_used_and_persisted() {
+        let persistence = Rc::new(MemoryPersistence::default());
+        let mut manager = test_manager_with_persistence(
+            persistence.clone(),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+        manager.change_emoji_set(EmojiSet::Off);
+
+        let word = manager.game.as_ref().unwrap().word();
+        for character in word {
+            manager.push_character(character);
+        }
+        manager.submit_guess();
+        assert_eq!(manager.game.as_ref().unwrap().message(), "Löysit sanan!");
+
+        let rehydrated = Manager::rehydrate(persistence).unwrap();
+        assert!(rehydrated.emoji_set == EmojiSet::Off);
+    }
+
+    #[test]
+    fn clear_all_data_keeps_other_apps_keys() {
+        let persistence = Rc::new(MemoryPersistence::default());
+        persistence.set("other-app", Value::Bool(true)).unwrap();
+
+        let mut manager = test_manager_with_persistence(
+            persistence.clone(),
+            GameMode::Classic,
+            WordList::Common,
+            DEFAULT_WORD_LENGTH,
+        );
+        manager.change_theme(Theme::Colorblind);
+        manager.change_game_mode(GameMode::Relay);
+        manager.update_game_statistics(true, 3, 2);
+
+        manager.clear_all_data();
+
+        assert!(manager.current_game_mode == GameMode::Classic);
+        assert!(manager.theme == Theme::Dark);
+        assert_eq!(manager.total_played, 0);
+        assert!(persistence.get("other-app").is_ok());
+
+        let mut keys = persistence::owned_keys(persistence.as_ref());
+        keys.sort();
+        let classic_key = game::storage_key(
+            WordLanguage::Finnish,
+            &GameMode::Classic,
+            &WordList::Common,
+            5,
+        );
+        assert_eq!(keys, vec![classic_key, "settings".to_owned()]);
+    }
+
+    #[test]
+    fn old_daily_games_are_cleaned_up() {
+        let persistence = Rc::new(MemoryPersistence::default());
+        let today = Local::now().date_naive();
+        let daily_key = |days_ago: u64| {
+            let date = today - Days::new(days_ago);
+            game::storage_key(
+                WordLanguage::Finnish,
+                &GameMode::DailyWord(date),
+                &WordList::Daily,
+                DAILY_WORD_LEN,
+            )
+        };
+        let classic_key = game::storage_key(
+            WordLanguage::Finnish,
+            &GameMode::Classic,
+            &WordList::Common,
+            5,
+        );
+
+        for key in [daily_key(0), daily_key(5), daily_key(40), daily_key(400)] {
+            persistence.set(&key, Value::Null).unwrap();
+        }
+        persistence.set(&classic_key, Value::Null).unwrap();
+
+        let manager = Manager {
+            current_game_mode: GameMode::DailyWord(today - Days::new(400)),
+            current_word_list: WordList::Daily,
+            persistence: persistence.clone(),
+            ..Manager::default()
+        };
+        manager.cleanup_daily_games(30);
+
+        let mut keys = persistence.keys_with_prefix("game|");
+        keys.sort();
+        let mut expected = vec![daily_key(0), daily_key(5), daily_key(400), classic_key];
+        expected.sort();
+        assert_eq!(keys, expected);
+    }
+
+    #[test]
+    fn only_revealed_tiles_have_glyphs() {
+        assert_eq!(TileState::Correct.glyph(), Some('✓'));
+        assert_eq!(TileState::Present.glyph(), Some('~'));
+        assert_eq!(TileState::Absent.glyph(), Some('·'));
+        assert_eq!(TileState::Unknown.glyph(), None);
+    }
+
+    #[test]
+    fn first_result_of_each_daily_is_kept() {
+        let mut manager = Manager {
+            persistence: Rc::new(MemoryPersistence::default()),
+            ..Manager::default()
+        };
+        let epoch = NaiveDate::from_ymd_opt(2022, 1, 7).unwrap();
+        let result = |days: u64, is_winner: bool, guesses_used: usize| GameResult {
+            game_mode: GameMode::DailyWord(epoch + Days::new(days)),
+            words: Vec::new(),
+            is_winner,
+            guesses_used,
+            streak: 0,
+            is_late: false,
+        };
+
+        manager.record_result(&result(0, true, 3), 0);
+        manager.record_result(&result(0, true, 1), 0);
+        manager.record_result(&result(2, false, DEFAULT_MAX_GUESSES), 0);
+
+        assert_eq!(manager.daily_history.get(&0), Some(&Some(3)));
+        assert_eq!(manager.daily_history.get(&2), Some(&None));
+        assert_eq!(manager.daily_history.len(), 2);
+    }
+
+    #[test]
+    fn lost_words_that_end_a_streak_are_remembered() {
+        let mut manager = Manager {
+            persistence: Rc::new(MemoryPersistence::default()),
+            ..Manager::default()
+        };
+        let result = |word: &str, is_winner: bool, streak: usize| GameResult {
+            game_mode: GameMode::Classic,
+            words: vec![word.chars().collect()],
+            is_winner,
+            guesses_used: DEFAULT_MAX_GUESSES,
+            streak,
+            is_late: false,
+        };
+
+        manager.record_result(&result("KISSA", true, 1), 0);
+        // Losing without a streak has nothing to remember
+        manager.record_result(&result("KOIRA", false, 0), 0);
+        assert!(manager.broken_streaks.is_empty());
+
+        for streak in 1..=MAX_BROKEN_STREAKS + 1 {
+            manager.record_result(&result("VIHTA", false, 0), streak);
+        }
+
+        let streaks = manager
+            .broken_streaks
+            .iter()
+            .map(|broken| broken.streak)
+            .collect::<Vec<_>>();
+        assert_eq!(streaks, (2..=MAX_BROKEN_STREAKS + 1).collect::<Vec<_>>());
+        assert!(manager
+            .broken_streaks
+            .iter()
+            .all(|broken| broken.word == "VIHTA"));
+    }
+
+    #[test]
+    fn returning_players_are_shown_what_they_left() {
+        let today = Local::now().date_naive();
+        let classic = (GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+        let key = game::storage_key(WordLanguage::Finnish, &classic.0, &classic.1, classic.2);
+        let manager = |last_played: Option<NaiveDate>| Manager {
+            current_game_mode: GameMode::DailyWord(today),
+            current_word_list: WordList::Daily,
+            previous_game: classic,
+            streaks: HashMap::from([(key.clone(), 4)]),
+            is_late_daily_available: true,
+            last_played,
+            ..Manager::default()
+        };
+
+        let summary = manager(Some(today - Days::new(10)))
+            .summarize_return(today)
+            .unwrap();
+        assert_eq!(summary.streak, 4);
+        assert!(summary.is_daily_missed);
+        assert!(summary.game == classic);
+
+        assert!(manager(Some(today - Days::new(2)))
+            .summarize_return(today)
+            .is_none());
+        assert!(manager(None).summarize_return(today).is_none());
+    }
+
+    #[test]
+    fn daily_word_length_and_list_cannot_be_changed() {
+        let mut manager = test_manager(GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+
+        let daily = GameMode::DailyWord(NaiveDate::from_ymd_opt(2022, 1, 7).unwrap());
+        manager.change_game_mode(daily);
+        manager.change_word_length(6);
+        manager.change_word_list(WordList::Full);
+
+        let game = manager.game.as_ref().unwrap();
+        let key = (*game.game_mode(), *game.word_list(), game.word_length());
+        assert!(key == (daily, WordList::Daily, DAILY_WORD_LEN));
+        assert!(
+            (
+                manager.current_game_mode,
+                manager.current_word_list,
+                manager.current_word_length
+            ) == key
+        );
+        assert_eq!(game.message(), "Tämän pelin sana on jo valittu");
+    }
+
+    #[test]
+    fn word_counts_leave_out_filtered_profanities() {
+        let words = |words: &[&str]| {
+            words
+                .iter()
+                .map(|word| word.chars().collect())
+                .collect::<HashSet<Vec<char>>>()
+        };
+        let language = WordLanguage::Finnish;
+        let word_lists = HashMap::from([
+            ((language, WordList::Common, 5), words(&["KISSA", "PASKA"])),
+            ((language, WordList::Profanities, 5), words(&["PASKA"])),
+        ]);
+        let mut manager = Manager {
+            word_lists: Rc::new(word_lists),
+            persistence: Rc::new(MemoryPersistence::default()),
+            ..Manager::default()
+        };
+
+        let common_count = |manager: &Manager| {
+            manager
+                .word_counts()
+                .into_iter()
+                .find(|count| count.word_list == WordList::Common && count.word_length == 5)
+                .unwrap()
+                .count
+        };
+
+        manager.allow_profanities = false;
+        assert_eq!(common_count(&manager), 1);
+        manager.allow_profanities = true;
+        assert_eq!(common_count(&manager), 2);
+
+        // Lists that didn't load show up empty
+        assert!(manager
+            .word_counts()
+            .iter()
+            .any(|count| count.word_list == WordList::Easy && count.count == 0));
+    }
+
+    #[test]
+    fn reset_settings_keeps_statistics_and_language() {
+        let mut manager = test_manager(GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+        manager.max_streak = 3;
+
+        manager.change_word_length(6);
+        manager.change_word_list(WordList::Full);
+        manager.change_weighted_word_selection(true);
+        manager.change_theme(Theme::Colorblind);
+        manager.change_auto_submit(true);
+        manager.change_hide_typing_hints(true);
+        manager.change_warn_contradictions(true);
+        manager.change_show_candidates(true);
+        manager.change_beginner_help(true);
+        manager.change_language(Language::English);
+
+        manager.reset_settings();
+
+        let defaults = Settings::default();
+        assert_eq!(manager.current_word_length, defaults.word_length);
+        assert!(manager.current_word_list == defaults.word_list);
+        assert!(manager.weighted_word_selection == defaults.weighted_word_selection);
+        assert!(manager.theme == defaults.theme);
+        assert!(manager.auto_submit == defaults.auto_submit);
+        assert!(manager.hide_typing_hints == defaults.hide_typing_hints);
+        assert!(manager.warn_contradictions == defaults.warn_contradictions);
+        assert!(manager.show_candidates == defaults.show_candidates);
+        assert!(manager.beginner_help == defaults.beginner_help);
+        assert!(manager.language == Language::English);
+        assert_eq!(manager.max_streak, 3);
+        assert_eq!(
+            manager.game.as_ref().unwrap().word().len(),
+            defaults.word_length
+        );
+    }
+
+    #[test]
+    fn starter_words_are_offered_until_the_word_is_typed_on() {
+        let mut manager = test_manager(GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+        assert!(manager.starter_words().is_empty());
+
+        manager.change_beginner_help(true);
+        let starters = starters::starter_words(WordLanguage::Finnish, DEFAULT_WORD_LENGTH);
+        assert!(!starters.is_empty());
+        assert_eq!(manager.starter_words(), starters);
+
+        // Erasing the letter doesn't bring them back
+        manager.push_character('K');
+        manager.pop_character();
+        assert!(manager.starter_words().is_empty());
+
+        manager.new_game();
+        assert_eq!(manager.starter_words(), starters);
+    }
+
+    #[test]
+    fn debug_mode_keeps_the_latest_drawn_words() {
+        let mut manager = test_manager(GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+        assert!(manager.drawn_words_text().is_none());
+
+        manager.is_debug = true;
+        for _ in 0..MAX_DRAWN_WORDS + 2 {
+            manager.new_game();
+        }
+        let text = manager.drawn_words_text().unwrap();
+        assert_eq!(text.lines().count(), MAX_DRAWN_WORDS);
+
+        let word = manager
+            .game
+            .as_ref()
+            .unwrap()
+            .word()
+            .iter()
+            .collect::<String>();
+        assert!(text
+            .lines()
+            .last()
+            .unwrap()
+            .ends_with(&format!(" {} {}", DEFAULT_WORD_LENGTH, word)));
+
+        // Tomorrow's daily word is never recorded, and neither are games only looked into
+        let today = Local::now().date_naive();
+        manager.open_game((
+            GameMode::DailyWord(today + Days::new(1)),
+            WordList::Daily,
+            DAILY_WORD_LEN,
+        ));
+        assert!(!manager.is_daily_finished(today));
+        assert_eq!(manager.drawn_words_text().unwrap(), text);
+
+        manager.change_game_mode(GameMode::Relay);
+        let text = manager.drawn_words_text().unwrap();
+        assert!(text
+            .lines()
+            .last()
+            .unwrap()
+            .contains(&GameMode::Relay.to_string()));
+    }
+
+    #[test]
+    fn auto_submit_waits_for_a_full_accepted_word() {
+        let mut manager = test_manager(GameMode::Classic, WordList::Common, DEFAULT_WORD_LENGTH);
+
+        for character in "QQQQQ".chars() {
+            manager.push_character(character);
+        }
+        manager.change_auto_submit(true);
+        assert!(!manager.is_auto_submit_ready());
+
+        for _ in 0..DEFAULT_WORD_LENGTH {
+            manager.pop_character();
+        }
+        let word = manager.game.as_ref().unwrap().word();
+        for character in &word[..DEFAULT_WORD_LENGTH - 1] {
+            manager.push_character(*character);
+        }
+        assert!(!manager.is_auto_submit_ready());
+
+        manager.push_character(word[DEFAULT_WORD_LENGTH - 1]);
+        assert!(manager.is_auto_submit_ready());
+
+        manager.change_auto_submit(false);
+        assert!(!manager.is_auto_submit_ready());
     }
 }
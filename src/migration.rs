@@ -0,0 +1,119 @@
+use serde_json::{json, Map, Value};
+
+use crate::manager::SCHEMA_VERSION;
+
+type Migration = fn(&mut Map<String, Value>);
+
+// MIGRATIONS[n] upgrades settings from version n to n + 1
+const MIGRATIONS: [Migration; SCHEMA_VERSION as usize] = [v0_unversioned];
+
+/// Upgrades stored settings to the current schema before typed deserialization.
+/// Settings written by a newer version are left as they are.
+pub fn migrate(mut settings: Value) -> Value {
+    if let Some(fields) = settings.as_object_mut() {
+        let version = fields
+            .get("schema_version")
+            .and_then(Value::as_u64)
+            .unwrap_or(0) as usize;
+
+        if version < MIGRATIONS.len() {
+            for migration in &MIGRATIONS[version..] {
+                migration(fields);
+            }
+            fields.insert("schema_version".to_owned(), json!(SCHEMA_VERSION));
+        }
+    }
+
+    settings
+}
+
+// Settings from before versioning may predate some of the required fields.
+// Fill them in so that the statistics that do exist survive deserialization.
+fn v0_unversioned(fields: &mut Map<String, Value>) {
+    let defaults = [
+        ("current_game_mode", json!("Classic")),
+        ("current_word_list", json!("Common")),
+        ("current_word_length", json!(5)),
+        ("allow_profanities", json!(false)),
+        ("theme", json!("Dark")),
+        ("max_streak", json!(0)),
+        ("total_played", json!(0)),
+        ("total_solved", json!(0)),
+    ];
+
+    for (field, default) in defaults {
+        fields.entry(field).or_insert(default);
+    }
+
+    if !fields.contains_key("previous_game") {
+        let previous_game = json!([
+            fields["current_game_mode"],
+            fields["current_word_list"],
+            fields["current_word_length"],
+        ]);
+        fields.insert("previous_game".to_owned(), previous_game);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manager::{GameMode, Manager, Theme, WordList};
+
+    fn rehydrate(settings: Value) -> Manager {
+        serde_json::from_value(migrate(settings)).unwrap()
+    }
+
+    #[test]
+    fn unversioned_settings_keep_statistics() {
+        let manager = rehydrate(json!({
+            "current_game_mode": "Relay",
+            "current_word_list": "Full",
+            "current_word_length": 6,
+            "allow_profanities": true,
+            "previous_game": ["Classic", "Common", 5],
+            "theme": "Colorblind",
+            "max_streak": 12,
+            "total_played": 40,
+            "total_solved": 31
+        }));
+
+        assert_eq!(manager.schema_version, SCHEMA_VERSION);
+        assert!(manager.current_game_mode == GameMode::Relay);
+        assert!(manager.theme == Theme::Colorblind);
+        assert_eq!(manager.max_streak, 12);
+        assert_eq!(manager.total_played, 40);
+        assert_eq!(manager.total_solved, 31);
+        assert!(manager.has_seen_help);
+    }
+
+    #[test]
+    fn settings_missing_required_fields_are_filled_in() {
+        let manager = rehydrate(json!({
+            "current_game_mode": "Classic",
+            "current_word_list": "Easy",
+            "current_word_length": 5,
+            "max_streak": 3,
+            "total_played": 7,
+            "total_solved": 5
+        }));
+
+        assert!(manager.previous_game == (GameMode::Classic, WordList::Easy, 5));
+        assert!(manager.theme == Theme::Dark);
+        assert!(!manager.allow_profanities);
+        assert_eq!(manager.max_streak, 3);
+        assert_eq!(manager.total_played, 7);
+    }
+
+    #[test]
+    fn current_settings_are_unchanged() {
+        let settings = serde_json::to_value(Manager::default()).unwrap();
+        assert_eq!(migrate(settings.clone()), settings);
+    }
+
+    #[test]
+    fn newer_settings_are_left_alone() {
+        let settings = json!({ "schema_version": SCHEMA_VERSION + 1, "future": true });
+        assert_eq!(migrate(settings.clone()), settings);
+    }
+}
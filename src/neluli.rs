@@ -1,33 +1,57 @@
-use rand::seq::SliceRandom;
 use std::collections::HashMap;
 use std::rc::Rc;
 
-use gloo_storage::{errors::StorageError, LocalStorage, Storage};
 use serde::{Deserialize, Serialize};
-
-use crate::game::{Board, Game, DEFAULT_ALLOW_PROFANITIES, DEFAULT_WORD_LENGTH, SUCCESS_EMOJIS};
-use crate::manager::{GameMode, KeyState, Theme, TileState, WordList, WordLists};
+use serde_json::Value;
+
+use crate::game;
+use crate::game::{
+    Board, BoardProgress, Game, GameResult, GuessAnalysis, ShareOptions, SharedGuesses,
+    ALLOWED_KEYS, DEFAULT_ALLOW_PROFANITIES, DEFAULT_WORD_LENGTH,
+};
+use crate::manager::{
+    EmojiSet, GameMode, KeyState, Theme, TileState, WordLanguage, WordList, WordLists,
+};
 use crate::sanuli::Sanuli;
+use crate::strings::{self, Language, Text};
 
 const MAX_GUESSES: usize = 9;
+// The list of each board with the mixed list, the difficulty ramps up towards the last board
+pub const MIXED_WORD_LISTS: [WordList; 4] = [
+    WordList::Easy,
+    WordList::Common,
+    WordList::Common,
+    WordList::Full,
+];
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Neluli {
+    #[serde(default)]
+    word_language: WordLanguage,
     word_list: WordList,
     word_length: usize,
     boards: Vec<Sanuli>,
     streak: usize,
     message: String,
+    #[serde(skip)]
+    rejected_guess: String,
 
     #[serde(skip)]
     allow_profanities: bool,
     #[serde(skip)]
+    emoji_set: EmojiSet,
+    #[serde(skip)]
+    language: Language,
+    #[serde(skip)]
     word_lists: Rc<WordLists>,
+    #[serde(skip)]
+    keyboard: HashMap<char, KeyState>,
 }
 
 impl Default for Neluli {
     fn default() -> Self {
         Neluli::new(
+            WordLanguage::default(),
             WordList::default(),
             DEFAULT_WORD_LENGTH,
             DEFAULT_ALLOW_PROFANITIES,
@@ -38,47 +62,29 @@ impl Default for Neluli {
 
 impl Neluli {
     pub fn new(
+        word_language: WordLanguage,
         word_list: WordList,
         word_length: usize,
         allow_profanities: bool,
         word_lists: Rc<WordLists>,
     ) -> Self {
-        let boards = vec![
-            Sanuli::new(
-                GameMode::Quadruple,
-                word_list,
-                word_length,
-                MAX_GUESSES,
-                allow_profanities,
-                word_lists.clone(),
-            ),
-            Sanuli::new(
-                GameMode::Quadruple,
-                word_list,
-                word_length,
-                MAX_GUESSES,
-                allow_profanities,
-                word_lists.clone(),
-            ),
-            Sanuli::new(
-                GameMode::Quadruple,
-                word_list,
-                word_length,
-                MAX_GUESSES,
-                allow_profanities,
-                word_lists.clone(),
-            ),
-            Sanuli::new(
-                GameMode::Quadruple,
-                word_list,
-                word_length,
-                MAX_GUESSES,
-                allow_profanities,
-                word_lists.clone(),
-            ),
-        ];
-
-        Self {
+        let boards = Self::board_word_lists(word_list)
+            .into_iter()
+            .map(|board_list| {
+                Sanuli::new(
+                    word_language,
+                    GameMode::Quadruple,
+                    board_list,
+                    word_length,
+                    MAX_GUESSES,
+                    allow_profanities,
+                    word_lists.clone(),
+                )
+            })
+            .collect();
+
+        let mut game = Self {
+            word_language,
             word_list,
             word_length,
 
@@ -86,44 +92,37 @@ impl Neluli {
             streak: 0,
 
             message: String::new(),
+            rejected_guess: String::new(),
 
             allow_profanities: DEFAULT_ALLOW_PROFANITIES,
+            emoji_set: EmojiSet::default(),
+            language: Language::default(),
             word_lists,
-        }
-    }
+            keyboard: HashMap::new(),
+        };
 
-    pub fn new_or_rehydrate(
-        word_list: WordList,
-        word_length: usize,
-        allow_profanities: bool,
-        word_lists: Rc<WordLists>,
-    ) -> Self {
-        if let Ok(game) = Self::rehydrate(
-            word_list,
-            word_length,
-            allow_profanities,
-            word_lists.clone(),
-        ) {
-            game
-        } else {
-            Self::new(word_list, word_length, allow_profanities, word_lists)
-        }
+        game.update_keyboard();
+
+        game
     }
 
-    fn rehydrate(
-        word_list: WordList,
-        word_length: usize,
+    pub fn rehydrate(
+        snapshot: Value,
         allow_profanities: bool,
         word_lists: Rc<WordLists>,
-    ) -> Result<Self, StorageError> {
-        let game_key = &format!(
-            "game|{}|{}|{}",
-            serde_json::to_string(&GameMode::Quadruple).unwrap(),
-            serde_json::to_string(&word_list).unwrap(),
-            word_length
-        );
+    ) -> serde_json::Result<Self> {
+        let mut game: Self = serde_json::from_value(snapshot)?;
+
+        if game.boards.len() != 4 {
+            return Err(serde::de::Error::custom(format!(
+                "expected 4 boards, found {}",
+                game.boards.len()
+            )));
+        }
 
-        let mut game: Self = LocalStorage::get(game_key)?;
+        for board in game.boards.iter_mut() {
+            board.validate().map_err(serde::de::Error::custom)?;
+        }
 
         for board in game.boards.iter_mut() {
             board.set_word_lists(word_lists.clone());
@@ -138,28 +137,82 @@ impl Neluli {
         Ok(game)
     }
 
+    fn board_word_lists(word_list: WordList) -> [WordList; 4] {
+        if word_list == WordList::Mixed {
+            MIXED_WORD_LISTS
+        } else {
+            [word_list; 4]
+        }
+    }
+
+    fn update_keyboard(&mut self) {
+        self.keyboard = ALLOWED_KEYS
+            .iter()
+            .map(|key| {
+                let states = self
+                    .boards
+                    .iter()
+                    .flat_map(|board| {
+                        board
+                            .keyboard_state()
+                            .get(key)
+                            .map_or(&[TileState::Unknown][..], KeyState::states)
+                    })
+                    .copied()
+                    .collect::<Vec<_>>();
+                (*key, KeyState::combine(&states))
+            })
+            .collect();
+    }
+
+    pub fn solved_count(&self) -> usize {
+        self.boards.iter().filter(|board| board.is_winner()).count()
+    }
+
+    /// Guesses left for the boards still being played. They have all taken every guess so
+    /// far, the boards that finished earlier stopped on rows of their own.
+    pub fn remaining_guesses(&self) -> usize {
+        self.boards
+            .iter()
+            .filter(|board| board.is_guessing())
+            .map(|board| MAX_GUESSES - board.boards()[0].current_guess)
+            .min()
+            .unwrap_or(0)
+    }
+
     fn is_game_ended(&self) -> bool {
         self.boards.iter().all(|board| !board.is_guessing())
     }
 
+    fn has_message(&self) -> bool {
+        !self.message.is_empty() || !self.rejected_guess.is_empty()
+    }
+
     fn clear_message(&mut self) {
         self.message = String::new();
+        self.rejected_guess = String::new();
     }
 
     fn set_game_end_message(&mut self) {
         if self.is_winner() {
-            self.message = format!(
-                "Löysit sanulit! {}",
-                SUCCESS_EMOJIS.choose(&mut rand::thread_rng()).unwrap()
-            );
+            let celebration = game::celebration(self.emoji_set);
+            self.message = strings::msg_win(self.language, GameMode::Quadruple, &celebration);
         } else {
-            let words: Vec<_> = self
-                .boards
+            // The words of each board are listed below the message
+            self.message = Text::NotAllWordsFound.get(self.language).to_owned();
+        }
+
+        if self.word_list == WordList::Mixed {
+            if let Some(board) = MIXED_WORD_LISTS
                 .iter()
-                .filter(|game| !game.is_winner())
-                .map(|game| game.word().iter().collect::<String>())
-                .collect();
-            self.message = format!("Löytämättä jäi: \"{}\"", words.join("\", \""));
+                .position(|word_list| *word_list == WordList::Full)
+            {
+                self.message = format!(
+                    "{} {}",
+                    self.message,
+                    strings::msg_hardest_board(self.language, board + 1)
+                );
+            }
         }
     }
 }
@@ -168,6 +221,9 @@ impl Game for Neluli {
     fn game_mode(&self) -> &GameMode {
         &GameMode::Quadruple
     }
+    fn word_language(&self) -> WordLanguage {
+        self.word_language
+    }
     fn word_list(&self) -> &WordList {
         &self.word_list
     }
@@ -188,9 +244,18 @@ impl Game for Neluli {
         self.streak
     }
     fn last_guess(&self) -> String {
-        String::new()
+        self.rejected_guess.clone()
     }
 
+    fn guesses_used(&self) -> Option<usize> {
+        // The boards are played together, so the game took as long as the slowest board
+        self.boards
+            .iter()
+            .map(|board| board.guesses_used())
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .max()
+    }
     fn is_guessing(&self) -> bool {
         self.boards.iter().any(|board| board.is_guessing())
     }
@@ -204,76 +269,179 @@ impl Game for Neluli {
         false
     }
     fn is_unknown(&self) -> bool {
+        !self.rejected_guess.is_empty()
+    }
+    fn is_contradiction_pending(&self) -> bool {
+        false
+    }
+    fn is_untouched(&self) -> bool {
+        self.boards.iter().all(|board| board.is_untouched())
+    }
+    fn remaining_candidates(&self) -> Option<usize> {
+        None
+    }
+    fn guess_analysis(&self) -> Option<Vec<GuessAnalysis>> {
+        None
+    }
+    fn is_late(&self) -> bool {
         false
     }
     fn message(&self) -> String {
         self.message.clone()
     }
-    fn previous_guesses(&self) -> Vec<Vec<(char, TileState)>> {
-        Vec::new()
+    fn previous_guesses(&self) -> SharedGuesses {
+        Rc::default()
     }
 
     fn set_allow_profanities(&mut self, is_allowed: bool) {
         self.allow_profanities = is_allowed;
+        for board in self.boards.iter_mut() {
+            board.set_allow_profanities(is_allowed);
+        }
     }
 
-    fn title(&self) -> String {
-        if self.streak > 0 {
-            format!("Neluli — Putki: {}", self.streak)
-        } else {
-            "Neluli".to_owned()
+    fn set_weighted_selection(&mut self, is_weighted: bool) {
+        for board in self.boards.iter_mut() {
+            board.set_weighted_selection(is_weighted);
+        }
+    }
+
+    fn pause_timer(&mut self) {
+        for board in self.boards.iter_mut() {
+            board.pause_timer();
+        }
+    }
+
+    fn resume_timer(&mut self) {
+        for board in self.boards.iter_mut() {
+            board.resume_timer();
+        }
+    }
+
+    fn set_emoji_set(&mut self, emoji_set: EmojiSet) {
+        self.emoji_set = emoji_set;
+    }
+
+    fn set_language(&mut self, language: Language) {
+        self.language = language;
+        for board in self.boards.iter_mut() {
+            board.set_language(language);
+        }
+    }
+
+    fn mark_late(&mut self) {}
+
+    fn restore_streak(&mut self, streak: usize) {
+        self.streak = streak;
+        self.message = Text::GameNotRestored.get(self.language).to_owned();
+    }
+
+    fn set_message(&mut self, message: String) {
+        self.message = message;
+    }
+
+    fn set_hide_typing_hints(&mut self, is_hidden: bool) {
+        for board in self.boards.iter_mut() {
+            board.set_hide_typing_hints(is_hidden);
         }
     }
 
+    // A letter missing from one word can still be on another board, so no guess contradicts
+    fn set_warn_contradictions(&mut self, _is_warned: bool) {}
+
+    // The count is for a single board, four of them wouldn't be discreet anymore
+    fn set_show_candidates(&mut self, _is_shown: bool) {}
+
+    fn is_hint_assisted(&self) -> bool {
+        self.boards.iter().any(|board| board.is_hint_assisted())
+    }
+
+    fn is_spectating(&self) -> bool {
+        false
+    }
+
+    fn word_source(&self) -> Option<WordList> {
+        None
+    }
+
+    fn is_guess_ready(&self) -> bool {
+        self.is_guessing()
+            && self
+                .boards
+                .iter()
+                .filter(|board| board.is_guessing())
+                .all(|board| board.is_guess_ready())
+    }
+
+    fn board_progress(&self) -> Option<BoardProgress> {
+        Some(BoardProgress {
+            solved: self.solved_count(),
+            boards: self.boards.len(),
+            guesses_left: self.remaining_guesses(),
+        })
+    }
+
+    fn cycle_tile_marker(&mut self, board: usize, row: usize, column: usize) -> bool {
+        self.boards
+            .get_mut(board)
+            .is_some_and(|game| game.cycle_tile_marker(0, row, column))
+    }
+
+    fn title(&self) -> String {
+        game::streak_title(
+            self.language,
+            "Neluli",
+            self.word_length,
+            self.word_list,
+            self.streak,
+        )
+    }
+
     fn next_word(&mut self) {
         for board in self.boards.iter_mut() {
             board.next_word();
         }
         self.clear_message();
+        self.update_keyboard();
+    }
+
+    fn prepare_next_word(&mut self) {
+        for board in self.boards.iter_mut() {
+            board.prepare_next_word();
+        }
+    }
 
-        let _res = self.persist();
+    fn is_next_word_prepared(&self) -> bool {
+        self.boards
+            .iter()
+            .all(|board| board.is_next_word_prepared())
     }
 
-    fn keyboard_tilestate(&self, key: &char) -> KeyState {
-        KeyState::Quadruple([
-            if let KeyState::Single(state) = self.boards[0].keyboard_tilestate(key) {
-                state
-            } else {
-                TileState::Unknown
-            },
-            if let KeyState::Single(state) = self.boards[1].keyboard_tilestate(key) {
-                state
-            } else {
-                TileState::Unknown
-            },
-            if let KeyState::Single(state) = self.boards[2].keyboard_tilestate(key) {
-                state
-            } else {
-                TileState::Unknown
-            },
-            if let KeyState::Single(state) = self.boards[3].keyboard_tilestate(key) {
-                state
-            } else {
-                TileState::Unknown
-            },
-        ])
+    fn keyboard_state(&self) -> &HashMap<char, KeyState> {
+        &self.keyboard
     }
 
-    fn submit_guess(&mut self) {
-        for board in self.boards.iter_mut() {
-            if board.is_guessing() {
-                if !board.is_guess_correct_length() {
-                    self.message = "Liian vähän kirjaimia!".to_owned();
-                    return;
-                }
-
-                if !board.is_guess_accepted_word() {
-                    self.message = "Ei sanulistalla.".to_owned();
-                    return;
-                }
-
-                board.submit_guess();
+    fn submit_guess(&mut self) -> Option<GameResult> {
+        if !self.is_guessing() {
+            return None;
+        }
+
+        // Check every board first, a guess rejected by one must not advance the others
+        for board in self.boards.iter().filter(|board| board.is_guessing()) {
+            if !board.is_guess_correct_length() {
+                self.message = Text::TooFewLetters.get(self.language).to_owned();
+                return None;
             }
+
+            if !board.is_guess_accepted_word() {
+                self.message = Text::NotInWordList.get(self.language).to_owned();
+                self.rejected_guess = board.last_guess();
+                return None;
+            }
+        }
+
+        for board in self.boards.iter_mut().filter(|board| board.is_guessing()) {
+            board.submit_guess();
         }
 
         if self.is_game_ended() {
@@ -288,38 +456,65 @@ impl Game for Neluli {
             self.clear_message();
         }
 
-        let _res = self.persist();
+        self.update_keyboard();
+
+        // The boards finish on their own, but the game only ends with the last of them
+        self.guesses_used().map(|guesses_used| GameResult {
+            game_mode: GameMode::Quadruple,
+            words: self.boards.iter().map(|board| board.word()).collect(),
+            is_winner: self.is_winner(),
+            guesses_used,
+            streak: self.streak,
+            is_late: false,
+        })
     }
 
-    fn push_character(&mut self, character: char) {
+    fn push_character(&mut self, character: char) -> bool {
         if !self.is_guessing() {
-            return;
+            return false;
         }
 
+        let mut is_changed = self.has_message();
         self.clear_message();
 
         for board in self.boards.iter_mut() {
-            board.push_character(character);
+            is_changed |= board.push_character(character);
         }
+        is_changed
     }
 
-    fn pop_character(&mut self) {
+    fn pop_character(&mut self) -> bool {
         if !self.is_guessing() {
-            return;
+            return false;
         }
 
+        let mut is_changed = self.has_message();
         self.clear_message();
 
         for board in self.boards.iter_mut() {
-            board.pop_character();
+            is_changed |= board.pop_character();
         }
+        is_changed
     }
 
-    fn share_emojis(&self, _theme: Theme) -> Option<String> {
+    fn share_emojis(&self, _theme: Theme, _options: ShareOptions) -> Option<String> {
         unimplemented!()
     }
 
-    fn share_link(&self) -> Option<String> {
+    // The counts are only offered for daily words, there is no daily Neluli
+    fn share_emoji_counts(&self, _theme: Theme, _options: ShareOptions) -> Option<String> {
+        None
+    }
+
+    fn share_payload(&self, _sharer_tag: Option<&str>) -> Option<String> {
+        None
+    }
+
+    fn progress_payload(&self, _sharer_tag: Option<&str>) -> Option<String> {
+        None
+    }
+
+    fn hide_word(&mut self) {
         unimplemented!()
     }
 
@@ -331,20 +526,237 @@ impl Game for Neluli {
         unimplemented!()
     }
 
+    fn clear_reset(&mut self) {}
+
     fn refresh(&mut self) {
         for board in self.boards.iter_mut() {
             board.refresh();
         }
+
+        self.update_keyboard();
+    }
+
+    fn storage_key(&self) -> Option<String> {
+        Some(game::storage_key(
+            self.word_language,
+            &GameMode::Quadruple,
+            &self.word_list,
+            self.word_length,
+        ))
+    }
+
+    fn snapshot(&self) -> serde_json::Result<Value> {
+        serde_json::to_value(self)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word_lists(words: &[&str]) -> Rc<WordLists> {
+        let mut word_lists: WordLists = HashMap::new();
 
-    fn persist(&self) -> Result<(), StorageError> {
-        let game_key = &format!(
-            "game|{}|{}|{}",
-            serde_json::to_string(&GameMode::Quadruple).unwrap(),
-            serde_json::to_string(&self.word_list).unwrap(),
-            self.word_length
+        for word in words {
+            let chars: Vec<char> = word.chars().collect();
+            for list in [WordList::Full, WordList::Common] {
+                word_lists
+                    .entry((WordLanguage::Finnish, list, chars.len()))
+                    .or_default()
+                    .insert(chars.clone());
+            }
+        }
+
+        Rc::new(word_lists)
+    }
+
+    #[test]
+    fn rejected_guess_leaves_every_board_untouched() {
+        let mut game = Neluli::new(
+            WordLanguage::Finnish,
+            WordList::Common,
+            5,
+            true,
+            word_lists(&["KISSA", "KOIRA", "TALVI", "KARIT"]),
+        );
+
+        for character in "KISSÄ".chars() {
+            game.push_character(character);
+        }
+        game.submit_guess();
+
+        assert!(game.is_unknown());
+        assert_eq!(game.last_guess(), "KISSÄ");
+        for board in game.boards() {
+            assert_eq!(board.current_guess, 0);
+            assert!(board.is_guessing);
+            assert_eq!(board.guesses[0].len(), 5);
+            assert!(board.guesses[0]
+                .iter()
+                .all(|(_, state)| *state == TileState::Unknown));
+        }
+    }
+
+    #[test]
+    fn result_is_returned_once_every_board_is_done() {
+        let mut game = Neluli::new(
+            WordLanguage::Finnish,
+            WordList::Common,
+            5,
+            true,
+            word_lists(&["KISSA", "KOIRA", "TALVI", "KARIT"]),
+        );
+        let words = game
+            .boards()
+            .into_iter()
+            .map(|board| board.word)
+            .collect::<Vec<_>>();
+        // Boards may share a word, one guess solves them all
+        let mut guesses: Vec<Vec<char>> = Vec::new();
+        for word in &words {
+            if !guesses.contains(word) {
+                guesses.push(word.clone());
+            }
+        }
+
+        for (index, guess) in guesses.iter().enumerate() {
+            for character in guess {
+                game.push_character(*character);
+            }
+            let result = game.submit_guess();
+
+            if index < guesses.len() - 1 {
+                assert!(result.is_none());
+            } else {
+                assert!(
+                    result
+                        == Some(GameResult {
+                            game_mode: GameMode::Quadruple,
+                            words: words.clone(),
+                            is_winner: true,
+                            guesses_used: guesses.len(),
+                            streak: 1,
+                            is_late: false,
+                        })
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_board_can_be_lost_on_the_last_row() {
+        let words = ["KISSA", "KOIRA", "TALVI", "KARIT", "PERHE"];
+        let mut game = Neluli::new(
+            WordLanguage::Finnish,
+            WordList::Common,
+            5,
+            true,
+            word_lists(&words),
         );
+        let board_words = game
+            .boards()
+            .into_iter()
+            .map(|board| board.word)
+            .collect::<Vec<_>>();
+        let wrong_word = words
+            .iter()
+            .map(|word| word.chars().collect::<Vec<_>>())
+            .find(|word| !board_words.contains(word))
+            .unwrap();
+
+        let mut result = None;
+        for _ in 0..MAX_GUESSES {
+            for character in &wrong_word {
+                game.push_character(*character);
+            }
+            result = game.submit_guess();
+        }
+
+        assert!(result.is_some_and(|result| !result.is_winner));
+        assert!(!game.is_guessing());
+        game.refresh();
+    }
 
-        LocalStorage::set(game_key, self)
+    #[test]
+    fn mixed_boards_ramp_up_in_difficulty() {
+        let mut lists: WordLists = HashMap::new();
+        let chars = |word: &str| word.chars().collect::<Vec<_>>();
+        for (list, words) in [
+            (WordList::Easy, &["KISSA"][..]),
+            (WordList::Common, &["KOIRA"][..]),
+            (WordList::Full, &["KISSA", "KOIRA", "TALVI"][..]),
+        ] {
+            for word in words {
+                lists
+                    .entry((WordLanguage::Finnish, list, 5))
+                    .or_default()
+                    .insert(chars(word));
+            }
+        }
+
+        let mut game = Neluli::new(
+            WordLanguage::Finnish,
+            WordList::Mixed,
+            5,
+            true,
+            Rc::new(lists),
+        );
+
+        let words = game
+            .boards()
+            .into_iter()
+            .map(|board| board.word)
+            .collect::<Vec<_>>();
+        assert_eq!(words[0], chars("KISSA"));
+        assert_eq!(words[1], chars("KOIRA"));
+        assert_eq!(words[2], chars("KOIRA"));
+        assert!(game.storage_key().unwrap().contains("Mixed"));
+
+        for guess in ["KISSA", "KOIRA", "TALVI"] {
+            if !game.is_guessing() {
+                break;
+            }
+            for character in guess.chars() {
+                game.push_character(character);
+            }
+            game.submit_guess();
+        }
+
+        assert!(game.is_winner());
+        assert!(game
+            .message()
+            .ends_with(&strings::msg_hardest_board(Language::Finnish, 4)));
+    }
+
+    #[test]
+    fn progress_follows_the_boards_still_played() {
+        let mut game = Neluli::new(
+            WordLanguage::Finnish,
+            WordList::Common,
+            5,
+            true,
+            word_lists(&["KISSA", "KOIRA", "TALVI", "KARIT"]),
+        );
+        let words = game
+            .boards()
+            .into_iter()
+            .map(|board| board.word)
+            .collect::<Vec<_>>();
+        assert_eq!(game.solved_count(), 0);
+        assert_eq!(game.remaining_guesses(), MAX_GUESSES);
+
+        // The first board is solved on the first row, the others keep going
+        for character in &words[0] {
+            game.push_character(*character);
+        }
+        game.submit_guess();
+
+        let solved = words.iter().filter(|word| **word == words[0]).count();
+        assert_eq!(game.solved_count(), solved);
+        if solved < words.len() {
+            assert_eq!(game.remaining_guesses(), MAX_GUESSES - 1);
+        } else {
+            assert_eq!(game.remaining_guesses(), 0);
+        }
     }
 }
@@ -0,0 +1,147 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use gloo_storage::{errors::StorageError, LocalStorage, Storage};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+pub trait GamePersistence {
+    fn get(&self, key: &str) -> Result<Value, StorageError>;
+    fn set(&self, key: &str, value: Value) -> Result<(), StorageError>;
+    fn remove(&self, key: &str);
+    fn keys_with_prefix(&self, prefix: &str) -> Vec<String>;
+}
+
+// Everything sanuli stores. Other apps on the same origin may have their own keys.
+const KEYS: [&str; 1] = ["settings"];
+const KEY_PREFIXES: [&str; 1] = ["game|"];
+
+/// Lists the stored keys that belong to sanuli.
+pub fn owned_keys(persistence: &dyn GamePersistence) -> Vec<String> {
+    let keys = KEYS
+        .iter()
+        .filter(|key| persistence.get(key).is_ok())
+        .map(|key| key.to_string());
+
+    let prefixed = KEY_PREFIXES
+        .iter()
+        .flat_map(|prefix| persistence.keys_with_prefix(prefix));
+
+    keys.chain(prefixed).collect()
+}
+
+pub fn get_as<T: DeserializeOwned>(
+    persistence: &dyn GamePersistence,
+    key: &str,
+) -> Result<T, StorageError> {
+    let value = persistence.get(key)?;
+    serde_json::from_value(value).map_err(StorageError::SerdeError)
+}
+
+pub fn set_as<T: Serialize>(
+    persistence: &dyn GamePersistence,
+    key: &str,
+    value: &T,
+) -> Result<(), StorageError> {
+    let value = serde_json::to_value(value).map_err(StorageError::SerdeError)?;
+    persistence.set(key, value)
+}
+
+#[derive(Default)]
+pub struct LocalStoragePersistence;
+
+impl GamePersistence for LocalStoragePersistence {
+    fn get(&self, key: &str) -> Result<Value, StorageError> {
+        LocalStorage::get(key)
+    }
+
+    fn set(&self, key: &str, value: Value) -> Result<(), StorageError> {
+        LocalStorage::set(key, value)
+    }
+
+    fn remove(&self, key: &str) {
+        LocalStorage::delete(key);
+    }
+
+    fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let storage = LocalStorage::raw();
+        let length = storage.length().unwrap_or(0);
+
+        (0..length)
+            .filter_map(|index| storage.key(index).ok().flatten())
+            .filter(|key| key.starts_with(prefix))
+            .collect()
+    }
+}
+
+// Keeps everything in memory, for tests and environments without a browser
+#[derive(Default)]
+pub struct MemoryPersistence {
+    items: RefCell<HashMap<String, Value>>,
+}
+
+impl GamePersistence for MemoryPersistence {
+    fn get(&self, key: &str) -> Result<Value, StorageError> {
+        self.items
+            .borrow()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| StorageError::KeyNotFound(key.to_owned()))
+    }
+
+    fn set(&self, key: &str, value: Value) -> Result<(), StorageError> {
+        self.items.borrow_mut().insert(key.to_owned(), value);
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) {
+        self.items.borrow_mut().remove(key);
+    }
+
+    fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.items
+            .borrow()
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_persistence_round_trip() {
+        let persistence = MemoryPersistence::default();
+
+        set_as(&persistence, "game|a", &vec![1, 2, 3]).unwrap();
+        set_as(&persistence, "game|b", &"sanuli").unwrap();
+        set_as(&persistence, "settings", &true).unwrap();
+
+        let numbers: Vec<usize> = get_as(&persistence, "game|a").unwrap();
+        assert_eq!(numbers, vec![1, 2, 3]);
+
+        let mut keys = persistence.keys_with_prefix("game|");
+        keys.sort();
+        assert_eq!(keys, vec!["game|a".to_owned(), "game|b".to_owned()]);
+
+        persistence.remove("game|a");
+        assert!(get_as::<Vec<usize>>(&persistence, "game|a").is_err());
+    }
+
+    #[test]
+    fn owned_keys_skip_other_apps() {
+        let persistence = MemoryPersistence::default();
+
+        set_as(&persistence, "settings", &true).unwrap();
+        set_as(&persistence, "game|a", &1).unwrap();
+        set_as(&persistence, "settings-other-app", &1).unwrap();
+        set_as(&persistence, "other|game|a", &1).unwrap();
+
+        let mut keys = owned_keys(&persistence);
+        keys.sort();
+        assert_eq!(keys, vec!["game|a".to_owned(), "settings".to_owned()]);
+    }
+}
@@ -0,0 +1,27 @@
+// Bindings to the install prompt shim in static/install.js. Without the shim the app is never
+// installable, calling it fails quietly.
+
+use wasm_bindgen::prelude::*;
+
+/// Fired on the window when the install prompt becomes available or is used up.
+pub const INSTALLABLE_EVENT: &str = "sanuli-installable";
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(catch, js_name = sanuliIsInstallable)]
+    fn is_installable_js() -> Result<bool, JsValue>;
+
+    #[wasm_bindgen(catch, js_name = sanuliPromptInstall)]
+    fn prompt_install_js() -> Result<(), JsValue>;
+}
+
+/// Whether the browser offered to install the app and the offer hasn't been used yet.
+pub fn is_installable() -> bool {
+    is_installable_js().unwrap_or(false)
+}
+
+pub fn prompt_install() {
+    if let Err(err) = prompt_install_js() {
+        log::warn!("Install prompt failed: {:?}", err);
+    }
+}
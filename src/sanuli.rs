@@ -1,30 +1,66 @@
 use rand::seq::SliceRandom;
+use rand::Rng;
 use std::collections::HashMap;
 use std::mem;
 use std::rc::Rc;
 
-use chrono::NaiveDate;
-use gloo_storage::{errors::StorageError, LocalStorage, Storage};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use web_sys::{window, Window};
-
-pub type KnownStates = HashMap<(char, usize), CharacterState>;
-pub type KnownCounts = HashMap<char, CharacterCount>;
+use serde_json::Value;
 
 use crate::game;
 use crate::game::{
-    Board, Game, DEFAULT_ALLOW_PROFANITIES, DEFAULT_MAX_GUESSES, DEFAULT_WORD_LENGTH,
-    SUCCESS_EMOJIS,
+    Board, BoardProgress, Game, GameResult, GuessAnalysis, KnownCounts, KnownStates, ShareOptions,
+    SharedGuesses, TileMarkers, ALLOWED_KEYS, DEFAULT_ALLOW_PROFANITIES, DEFAULT_MAX_GUESSES,
+    DEFAULT_WORD_LENGTH, STREAK_MILESTONES,
 };
 use crate::manager::{
-    CharacterCount, CharacterState, GameMode, KeyState, Theme, TileState, WordList, WordLists,
+    self, EmojiSet, GameMode, KeyState, Theme, TileMarker, TileState, WordLanguage, WordList,
+    WordLists,
 };
+use crate::share;
+use crate::solver;
+use crate::strings::{self, Language, Text};
 
 const DAILY_WORDS: &str = include_str!("../daily-words.txt");
+#[cfg(feature = "swedish")]
+const SWEDISH_DAILY_WORDS: &str = include_str!("../swedish-daily-words.txt");
+// Chance of drawing a word from the full list that is also on the common list
+const COMMON_WORD_WEIGHT: f64 = 0.7;
+// Time to submit a contradicting guess again to accept it
+const CONTRADICTION_CONFIRM_MS: i64 = 4000;
+
+// JSON keys can only be strings, so the markers are stored as a list of entries instead
+mod markers_as_entries {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::game::TileMarkers;
+    use crate::manager::TileMarker;
+
+    pub fn serialize<S: Serializer>(
+        markers: &TileMarkers,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        markers.iter().collect::<Vec<_>>().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<TileMarkers, D::Error> {
+        let entries = Vec::<((usize, usize), TileMarker)>::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
+// Everything a random draw depends on: language, game mode, list, length, profanities and weighting
+type WordDraw = (WordLanguage, GameMode, WordList, usize, bool, bool);
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Sanuli {
     game_mode: GameMode,
+    // Games stored before there were other languages are Finnish
+    #[serde(default)]
+    word_language: WordLanguage,
     word_list: WordList,
     word_length: usize,
     max_guesses: usize,
@@ -37,28 +73,100 @@ pub struct Sanuli {
     is_guessing: bool,
     is_winner: bool,
     is_unknown: bool,
+    // A restored game doesn't replay the entry animation
+    #[serde(skip)]
     is_reset: bool,
     #[serde(skip)]
     is_hidden: bool,
+    // A finished daily word stays finished, it can't be reset and solved again
+    #[serde(default)]
+    is_completed: bool,
+    #[serde(default)]
+    is_late: bool,
+    // A submitted guess had tiles colored by the hints while it was typed
+    #[serde(default)]
+    is_hint_assisted: bool,
+    #[serde(default, with = "markers_as_entries")]
+    markers: TileMarkers,
+    // The narrowest list the word is on, None when it wasn't drawn from a list
+    #[serde(default)]
+    word_source: Option<WordList>,
+    // A friend's unfinished game opened from a progress link, its word was left out of the link
+    #[serde(skip)]
+    is_spectating: bool,
+    // Sharer tag of the link the game was opened from
+    #[serde(default)]
+    shared_by: Option<String>,
+    // A shared game that submitted the same guess twice in a row, a sign of a crafted link
+    #[serde(skip)]
+    is_guess_repeated: bool,
+    // Guesses each word of the current relay chain took, None for the word that broke it
+    #[serde(default)]
+    chain: Vec<Option<usize>>,
 
     message: String,
+    // The guess that was rejected as unknown, kept while its message is shown
+    #[serde(skip)]
+    rejected_guess: String,
+    // Chosen when the word is found, the daily share repeats it
+    #[serde(default)]
+    celebration: String,
+    // Active time spent on the word, stored so that a refresh pauses the timer instead of losing it
+    #[serde(default)]
+    elapsed_ms: i64,
+    #[serde(skip)]
+    timer_started_at: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    is_timer_paused: bool,
 
     #[serde(skip)]
-    previous_guesses: Vec<Vec<(char, TileState)>>,
+    previous_guesses: SharedGuesses,
+    #[serde(skip)]
+    shared_guesses: SharedGuesses,
 
     #[serde(skip)]
     allow_profanities: bool,
     #[serde(skip)]
+    is_weighted_selection: bool,
+    #[serde(skip)]
+    hide_typing_hints: bool,
+    #[serde(skip)]
+    warn_contradictions: bool,
+    // The contradicting guess waiting to be submitted again, and when it was warned about
+    #[serde(skip)]
+    pending_contradiction: Option<(String, DateTime<Utc>)>,
+    #[serde(skip)]
+    show_candidates: bool,
+    // Words of the full list that still fit everything known, narrowed down guess by guess
+    #[serde(skip)]
+    candidates: Option<Vec<Vec<char>>>,
+    // Words left before the first guess and after each submitted row, for the analysis
+    #[serde(skip)]
+    candidate_counts: Vec<usize>,
+    #[serde(skip)]
+    emoji_set: EmojiSet,
+    #[serde(skip)]
+    language: Language,
+    #[serde(skip)]
     word_lists: Rc<WordLists>,
     #[serde(skip)]
     known_states: Vec<KnownStates>,
     #[serde(skip)]
     known_counts: Vec<KnownCounts>,
+    // Which tiles of each guess revealed something new, rebuilt along with the known states
+    #[serde(skip)]
+    new_information: Vec<Vec<bool>>,
+    #[serde(skip)]
+    keyboard: HashMap<char, KeyState>,
+    // The next word, drawn ahead of time with the settings it was drawn with
+    #[serde(skip)]
+    prepared_word: Option<(WordDraw, Vec<char>)>,
 }
 
 impl Default for Sanuli {
     fn default() -> Self {
         Sanuli::new(
+            WordLanguage::default(),
             GameMode::default(),
             WordList::default(),
             DEFAULT_WORD_LENGTH,
@@ -71,6 +179,7 @@ impl Default for Sanuli {
 
 impl Sanuli {
     pub fn new(
+        word_language: WordLanguage,
         game_mode: GameMode,
         word_list: WordList,
         word_length: usize,
@@ -78,62 +187,137 @@ impl Sanuli {
         allow_profanities: bool,
         word_lists: Rc<WordLists>,
     ) -> Self {
-        let guesses = std::iter::repeat(Vec::with_capacity(word_length))
-            .take(max_guesses)
-            .collect::<Vec<_>>();
-
-        let known_states = std::iter::repeat(HashMap::new())
-            .take(max_guesses)
-            .collect::<Vec<_>>();
-
-        let known_counts = std::iter::repeat(HashMap::new())
-            .take(max_guesses)
-            .collect::<Vec<_>>();
-
         let word = if word_lists.is_empty() {
             // Default initialization runs into this
             vec!['X'; word_length]
         } else {
             Self::get_word(
+                word_language,
                 game_mode,
                 word_list,
                 word_length,
                 allow_profanities,
+                false,
                 &word_lists,
             )
         };
 
-        Self {
+        let mut game = Self::with_word(
+            word_language,
+            game_mode,
+            word_list,
+            word,
+            max_guesses,
+            allow_profanities,
+            word_lists,
+        );
+        game.check_practice_letter();
+        game
+    }
+
+    // A game for a word chosen by someone else instead of picked from the list
+    pub fn with_word(
+        word_language: WordLanguage,
+        game_mode: GameMode,
+        word_list: WordList,
+        word: Vec<char>,
+        max_guesses: usize,
+        allow_profanities: bool,
+        word_lists: Rc<WordLists>,
+    ) -> Self {
+        let word_length = word.len();
+
+        let guesses = std::iter::repeat(Vec::with_capacity(word_length))
+            .take(max_guesses)
+            .collect::<Vec<_>>();
+
+        let known_states = std::iter::repeat(KnownStates::default())
+            .take(max_guesses)
+            .collect::<Vec<_>>();
+
+        let known_counts = std::iter::repeat(HashMap::new())
+            .take(max_guesses)
+            .collect::<Vec<_>>();
+
+        let mut game = Self {
             game_mode,
+            word_language,
             word_list,
             word_lists,
             word_length,
             max_guesses,
             word,
             allow_profanities,
+            is_weighted_selection: false,
+            hide_typing_hints: false,
+            warn_contradictions: false,
+            pending_contradiction: None,
+            show_candidates: false,
+            candidates: None,
+            candidate_counts: Vec::new(),
+            emoji_set: EmojiSet::default(),
+            language: Language::default(),
             is_guessing: true,
             is_winner: false,
             is_unknown: false,
             is_reset: false,
             is_hidden: false,
+            is_completed: false,
+            is_late: false,
+            is_hint_assisted: false,
+            is_spectating: false,
+            shared_by: None,
+            is_guess_repeated: false,
+            word_source: None,
+            markers: HashMap::new(),
+            chain: Vec::new(),
             message: String::new(),
+            rejected_guess: String::new(),
+            celebration: String::new(),
+            elapsed_ms: 0,
+            timer_started_at: None,
+            is_timer_paused: false,
             known_states,
             known_counts,
+            new_information: Vec::new(),
             guesses,
-            previous_guesses: Vec::new(),
+            previous_guesses: Rc::default(),
+            shared_guesses: Rc::default(),
             current_guess: 0,
             streak: 0,
-        }
+            keyboard: HashMap::new(),
+            prepared_word: None,
+        };
+
+        game.word_source = game.find_word_source();
+        game.update_shared_guesses();
+        game.update_keyboard();
+
+        game
     }
 
     pub fn from_shared_link(game_str: &str, word_lists: Rc<WordLists>) -> Option<Self> {
+        // Progress links leave the word out
+        if let Some(progress_str) = game_str.strip_prefix('|') {
+            return Self::from_progress_link(progress_str, word_lists);
+        }
+
         let max_guesses = DEFAULT_MAX_GUESSES;
 
         let mut parts = game_str.split("|");
         let word = parts.next()?.chars().collect::<Vec<_>>();
         let word_length = word.len();
+        if word_length == 0 {
+            return None;
+        }
 
         let guesses_str = parts.next()?;
+        // Links to Finnish games were shared before there were other languages
+        let word_language = match parts.next() {
+            Some(code) => WordLanguage::from_code(code)?,
+            None => WordLanguage::Finnish,
+        };
+        let shared_by = parts.next().and_then(share::sanitize_sharer_tag);
 
         let mut guesses = guesses_str
             .chars()
@@ -143,11 +327,121 @@ impl Sanuli {
             .map(|chunk| chunk.to_vec())
             .collect::<Vec<_>>();
 
+        let current_guess = guesses.len().checked_sub(1)?;
+
+        guesses.resize(max_guesses, Vec::with_capacity(word_length));
+
+        let mut game = Self::shared(
+            word_language,
+            word,
+            word_length,
+            guesses,
+            current_guess,
+            word_lists,
+        );
+
+        if let Err(err) = game.validate() {
+            log::warn!("Ignoring an invalid shared game: {}", err);
+            return None;
+        }
+
+        game.shared_by = shared_by;
+        game.make_plausible();
+        game.refresh();
+
+        return Some(game);
+    }
+
+    /// A friend's unfinished game from a link made with `encode_progress_payload`. There's no word
+    /// to color the guesses against, so they keep the colors they came with and can't be played on.
+    pub fn from_progress_link(game_str: &str, word_lists: Rc<WordLists>) -> Option<Self> {
+        let max_guesses = DEFAULT_MAX_GUESSES;
+
+        let mut parts = game_str.split('|');
+        let word_length = parts.next()?.parse::<usize>().ok()?;
+        let letters = parts.next()?.chars().collect::<Vec<_>>();
+        let states = parts
+            .next()?
+            .chars()
+            .map(share::tile_state_from_code)
+            .collect::<Option<Vec<_>>>()?;
+        let word_language = WordLanguage::from_code(parts.next()?)?;
+        let shared_by = parts.next().and_then(share::sanitize_sharer_tag);
+
+        if word_length == 0
+            || letters.is_empty()
+            || letters.len() != states.len()
+            || letters.len() % word_length != 0
+            || letters.len() / word_length >= max_guesses
+        {
+            return None;
+        }
+
+        let mut guesses = letters
+            .into_iter()
+            .zip(states)
+            .collect::<Vec<_>>()
+            .chunks(word_length)
+            .map(|chunk| chunk.to_vec())
+            .collect::<Vec<_>>();
+
         let current_guess = guesses.len() - 1;
 
         guesses.resize(max_guesses, Vec::with_capacity(word_length));
 
-        let known_states = std::iter::repeat(HashMap::new())
+        let mut game = Self::shared(
+            word_language,
+            Vec::new(),
+            word_length,
+            guesses,
+            current_guess,
+            word_lists,
+        );
+        game.is_hidden = false;
+        game.is_spectating = true;
+        game.shared_by = shared_by;
+        game.message = Text::GameInProgress.get(game.language).to_owned();
+
+        game.refresh();
+
+        Some(game)
+    }
+
+    // A link can be crafted to have any guesses. Nothing is played after the word was found,
+    // and the same guess submitted twice in a row is pointed out instead of shown as is.
+    fn make_plausible(&mut self) {
+        let letters = |guess: &Vec<(char, TileState)>| guess.iter().map(|(c, _)| *c).collect();
+        let submitted = self.guesses[..=self.current_guess]
+            .iter()
+            .map(letters)
+            .collect::<Vec<Vec<char>>>();
+
+        if let Some(row) = submitted.iter().position(|guess| *guess == self.word) {
+            for guess in &mut self.guesses[row + 1..] {
+                guess.clear();
+            }
+            self.current_guess = row;
+            self.is_winner = true;
+        }
+
+        let submitted = &submitted[..=self.current_guess];
+        if submitted.windows(2).any(|rows| rows[0] == rows[1]) {
+            self.is_guess_repeated = true;
+            self.message = Text::SharedGuessRepeated.get(self.language).to_owned();
+        }
+    }
+
+    fn shared(
+        word_language: WordLanguage,
+        word: Vec<char>,
+        word_length: usize,
+        guesses: Vec<Vec<(char, TileState)>>,
+        current_guess: usize,
+        word_lists: Rc<WordLists>,
+    ) -> Self {
+        let max_guesses = DEFAULT_MAX_GUESSES;
+
+        let known_states = std::iter::repeat(KnownStates::default())
             .take(max_guesses)
             .collect::<Vec<_>>();
 
@@ -155,112 +449,251 @@ impl Sanuli {
             .take(max_guesses)
             .collect::<Vec<_>>();
 
-        let mut game = Self {
+        Self {
             game_mode: GameMode::Shared,
+            word_language,
             word_list: WordList::Full,
             word_lists,
             word_length,
             max_guesses,
             word,
             allow_profanities: true,
+            is_weighted_selection: false,
+            hide_typing_hints: false,
+            warn_contradictions: false,
+            pending_contradiction: None,
+            show_candidates: false,
+            candidates: None,
+            candidate_counts: Vec::new(),
+            emoji_set: EmojiSet::default(),
+            language: Language::default(),
             is_guessing: false,
             is_winner: false,
             is_unknown: false,
             is_reset: false,
             is_hidden: true,
+            is_completed: false,
+            is_late: false,
+            is_hint_assisted: false,
+            is_spectating: false,
+            shared_by: None,
+            is_guess_repeated: false,
+            word_source: None,
+            markers: HashMap::new(),
+            chain: Vec::new(),
             message: String::new(),
+            rejected_guess: String::new(),
+            celebration: String::new(),
+            elapsed_ms: 0,
+            timer_started_at: None,
+            is_timer_paused: false,
             known_states,
             known_counts,
+            new_information: Vec::new(),
             guesses,
-            previous_guesses: Vec::new(),
+            previous_guesses: Rc::default(),
+            shared_guesses: Rc::default(),
             current_guess,
             streak: 0,
-        };
-
-        game.refresh();
-
-        return Some(game);
-    }
-
-    pub fn new_or_rehydrate(
-        game_mode: GameMode,
-        word_list: WordList,
-        word_length: usize,
-        allow_profanities: bool,
-        word_lists: Rc<WordLists>,
-    ) -> Self {
-        if let Ok(game) = Self::rehydrate(
-            game_mode,
-            word_list,
-            word_length,
-            allow_profanities,
-            word_lists.clone(),
-        ) {
-            game
-        } else {
-            Self::new(
-                game_mode,
-                word_list,
-                word_length,
-                DEFAULT_MAX_GUESSES,
-                allow_profanities,
-                word_lists,
-            )
+            keyboard: HashMap::new(),
+            prepared_word: None,
         }
     }
 
     pub fn set_word_lists(&mut self, word_lists: Rc<WordLists>) {
         self.word_lists = word_lists;
+        self.prepared_word = None;
+    }
+
+    fn word_draw(&self) -> WordDraw {
+        (
+            self.word_language,
+            self.game_mode,
+            self.word_list,
+            self.word_length,
+            self.allow_profanities,
+            self.is_weighted_selection,
+        )
     }
 
     fn get_word(
+        word_language: WordLanguage,
         game_mode: GameMode,
         word_list: WordList,
         word_length: usize,
         allow_profanities: bool,
+        is_weighted: bool,
         word_lists: &Rc<WordLists>,
     ) -> Vec<char> {
-        if let GameMode::DailyWord(date) = game_mode {
-            Self::get_daily_word(date)
-        } else {
-            Self::get_random_word(word_list, word_length, allow_profanities, word_lists)
+        let any_word = |_: &Vec<char>| true;
+
+        match game_mode {
+            GameMode::DailyWord(date) => Self::get_daily_word(word_language, date),
+            GameMode::Practice(Some(letter)) => Self::get_random_word(
+                word_language,
+                word_list,
+                word_length,
+                allow_profanities,
+                is_weighted,
+                word_lists,
+                |word| word.contains(&letter),
+            )
+            // Without any word containing the letter, practice with any word at all
+            .or_else(|| {
+                Self::get_random_word(
+                    word_language,
+                    word_list,
+                    word_length,
+                    allow_profanities,
+                    is_weighted,
+                    word_lists,
+                    any_word,
+                )
+            })
+            .unwrap(),
+            _ => Self::get_random_word(
+                word_language,
+                word_list,
+                word_length,
+                allow_profanities,
+                is_weighted,
+                word_lists,
+                any_word,
+            )
+            .unwrap(),
         }
     }
 
-    fn get_random_word(
+    /// Picks a random word from the list among the words accepted by `predicate`.
+    /// A weighted draw from the full list favors the words that are also on the common list.
+    pub fn get_random_word(
+        word_language: WordLanguage,
         word_list: WordList,
         word_length: usize,
         allow_profanities: bool,
+        is_weighted: bool,
         word_lists: &Rc<WordLists>,
-    ) -> Vec<char> {
+        predicate: impl Fn(&Vec<char>) -> bool,
+    ) -> Option<Vec<char>> {
         let mut words = word_lists
-            .get(&(word_list, word_length))
-            .unwrap()
+            .get(&(word_language, word_list, word_length))?
             .iter()
+            .filter(|word| predicate(word))
             .collect::<Vec<_>>();
 
         if !allow_profanities {
-            if let Some(profanities) = word_lists.get(&(WordList::Profanities, word_length)) {
+            if let Some(profanities) =
+                word_lists.get(&(word_language, WordList::Profanities, word_length))
+            {
                 words.retain(|word| !profanities.contains(*word));
             }
         }
 
-        let chosen = words.choose(&mut rand::thread_rng()).unwrap();
-        (*chosen).clone()
+        let mut rng = rand::thread_rng();
+
+        if is_weighted && word_list == WordList::Full {
+            if let Some(common) = word_lists.get(&(word_language, WordList::Common, word_length)) {
+                let (common_words, rare_words): (Vec<_>, Vec<_>) =
+                    words.into_iter().partition(|word| common.contains(*word));
+
+                words = if common_words.is_empty() {
+                    rare_words
+                } else if rare_words.is_empty() || rng.gen_bool(COMMON_WORD_WEIGHT) {
+                    common_words
+                } else {
+                    rare_words
+                };
+            }
+        }
+
+        words.choose(&mut rng).map(|chosen| (*chosen).clone())
+    }
+
+    // The list used for the draw is known, only a word from the full list is looked up
+    // on the smaller lists to tell whether it's a rare one
+    fn find_word_source(&self) -> Option<WordList> {
+        match self.game_mode {
+            GameMode::DailyWord(_) => Some(WordList::Daily),
+            GameMode::Shared | GameMode::Hotseat => None,
+            _ if self.word_list == WordList::Full => [WordList::Easy, WordList::Common]
+                .into_iter()
+                .find(|list| {
+                    self.word_lists
+                        .get(&(self.word_language, *list, self.word_length))
+                        .is_some_and(|words| words.contains(&self.word))
+                })
+                .or(Some(WordList::Full)),
+            _ => Some(self.word_list),
+        }
     }
 
-    fn get_daily_word_index(date: NaiveDate) -> usize {
-        let epoch = NaiveDate::from_ymd_opt(2022, 1, 7).unwrap(); // Epoch of the daily word mode, index 0
-        date.signed_duration_since(epoch).num_days() as usize
+    fn check_practice_letter(&mut self) {
+        if let GameMode::Practice(Some(letter)) = self.game_mode {
+            if !self.word.contains(&letter) {
+                self.message = strings::msg_practice_letter_missing(self.language, letter);
+            }
+        }
     }
 
-    fn get_daily_word(date: NaiveDate) -> Vec<char> {
-        DAILY_WORDS
-            .lines()
-            .nth(Self::get_daily_word_index(date))
+    // Each language has daily words of its own
+    fn get_daily_word(word_language: WordLanguage, date: NaiveDate) -> Vec<char> {
+        let daily_words = match word_language {
+            WordLanguage::Finnish => DAILY_WORDS,
+            #[cfg(feature = "swedish")]
+            WordLanguage::Swedish => SWEDISH_DAILY_WORDS,
+            #[cfg(not(feature = "swedish"))]
+            WordLanguage::Swedish => unreachable!("Swedish can't be chosen without its word lists"),
+        };
+
+        manager::parse_word_list(daily_words)
+            .nth(game::daily_word_index(date))
             .unwrap()
-            .chars()
-            .collect()
+    }
+
+    pub fn encode_share_payload(&self, sharer_tag: Option<&str>) -> String {
+        let mut game_str = format!(
+            "{}|{}",
+            self.word.iter().collect::<String>(),
+            self.guesses
+                .iter()
+                .flat_map(|guess| guess.iter().map(|(c, _)| c))
+                .collect::<String>(),
+        );
+        // The tag comes last, the language before it can't be left out then
+        if self.word_language != WordLanguage::Finnish || sharer_tag.is_some() {
+            game_str = format!("{}|{}", game_str, self.word_language.code());
+        }
+        if let Some(tag) = sharer_tag {
+            game_str = format!("{}|{}", game_str, tag);
+        }
+
+        share::encode_payload(&game_str)
+    }
+
+    /// Payload of the guesses submitted so far, for asking a friend for help in the middle of a
+    /// game. The word is left out, so the colors of the guesses are sent instead.
+    pub fn encode_progress_payload(&self, sharer_tag: Option<&str>) -> String {
+        let submitted = &self.guesses[..self.current_guess];
+        let mut game_str = format!(
+            "|{}|{}|{}|{}",
+            self.word_length,
+            submitted
+                .iter()
+                .flat_map(|guess| guess.iter().map(|(c, _)| c))
+                .collect::<String>(),
+            submitted
+                .iter()
+                .flat_map(|guess| guess
+                    .iter()
+                    .map(|(_, state)| share::tile_state_code(*state)))
+                .collect::<String>(),
+            self.word_language.code(),
+        );
+        if let Some(tag) = sharer_tag {
+            game_str = format!("{}|{}", game_str, tag);
+        }
+
+        share::encode_payload(&game_str)
     }
 
     pub fn is_guess_correct_length(&self) -> bool {
@@ -278,62 +711,284 @@ impl Sanuli {
             .map(|(c, _)| *c)
             .collect();
 
-        match self.word_lists.get(&(WordList::Full, self.word_length)) {
+        match self
+            .word_lists
+            .get(&(self.word_language, WordList::Full, self.word_length))
+        {
             Some(list) => list.contains(word),
             None => false,
         }
     }
 
-    fn is_correct_word(&self) -> bool {
-        self.guesses[self.current_guess]
-            .iter()
-            .map(|(c, _)| *c)
-            .collect::<Vec<char>>()
-            == self.word
+    // A hint of the tile state based on already known information, unless hints are hidden
+    fn typing_hint(&self, character: char, index: usize) -> TileState {
+        if self.hide_typing_hints {
+            return TileState::Unknown;
+        }
+
+        game::hint_tile_state(
+            character,
+            index,
+            self.current_guess,
+            &self.known_states,
+            &self.known_counts,
+        )
     }
 
-    pub fn is_game_ended(&self) -> bool {
-        self.is_winner || self.current_guess == self.max_guesses - 1
+    fn is_current_row_hinted(&self) -> bool {
+        self.is_guessing
+            && self.guesses[self.current_guess]
+                .iter()
+                .any(|(_, state)| matches!(state, TileState::Correct | TileState::Present))
     }
 
-    fn clear_message(&mut self) {
-        self.is_unknown = false;
-        self.message = String::new();
+    fn update_known_information(&mut self, guess_index: usize) {
+        let new_information = game::update_known_information(
+            &mut self.known_states,
+            &mut self.known_counts,
+            &mut self.guesses[guess_index],
+            guess_index,
+            &self.word,
+        );
+        self.set_new_information(guess_index, new_information);
     }
 
-    fn set_game_end_message(&mut self) {
-        if self.is_winner {
-            if let GameMode::DailyWord(_) = self.game_mode {
-                self.message = format!(
-                    "Löysit päivän sanulin! {}",
-                    SUCCESS_EMOJIS.choose(&mut rand::thread_rng()).unwrap()
-                );
-            } else {
-                self.message = format!(
-                    "Löysit sanan! {}",
-                    SUCCESS_EMOJIS.choose(&mut rand::thread_rng()).unwrap()
-                );
-            }
-        } else {
-            self.message = format!("Sana oli \"{}\"", self.word.iter().collect::<String>());
+    // Colors a full row against the word as the guess at guess_index, recording what it reveals
+    fn evaluate_row(&mut self, row: &[char], guess_index: usize) -> Vec<(char, TileState)> {
+        let mut guess = row
+            .iter()
+            .map(|c| (*c, TileState::Unknown))
+            .collect::<Vec<_>>();
+        let new_information = game::update_known_information(
+            &mut self.known_states,
+            &mut self.known_counts,
+            &mut guess,
+            guess_index,
+            &self.word,
+        );
+        self.set_new_information(guess_index, new_information);
+
+        guess
+    }
+
+    fn set_new_information(&mut self, guess_index: usize, new_information: Vec<bool>) {
+        if self.new_information.len() <= guess_index {
+            self.new_information.resize(guess_index + 1, Vec::new());
         }
+        self.new_information[guess_index] = new_information;
     }
 
-    fn rehydrate(
-        game_mode: GameMode,
-        word_list: WordList,
-        word_length: usize,
+    // The row whose knowledge is the latest, None before anything has been guessed
+    fn last_submitted_row(&self) -> Option<usize> {
+        if self.is_guessing {
+            self.current_guess.checked_sub(1)
+        } else {
+            Some(self.current_guess)
+        }
+    }
+
+    // Replays the submitted rows one by one, so every row gets its count again
+    fn rebuild_candidates(&mut self) {
+        self.candidates = None;
+        self.candidate_counts = Vec::new();
+        if let Some(last_row) = self.last_submitted_row() {
+            for row in 0..=last_row {
+                self.narrow_candidates(row);
+            }
+        }
+    }
+
+    // Filters the previous survivors with the knowledge of the row, or the full list if there are none
+    fn narrow_candidates(&mut self, row: usize) {
+        if !self.show_candidates || self.is_spectating {
+            return;
+        }
+
+        let candidates = self.candidates.get_or_insert_with(|| {
+            self.word_lists
+                .get(&(self.word_language, WordList::Full, self.word_length))
+                .map(|words| words.iter().cloned().collect())
+                .unwrap_or_default()
+        });
+        if self.candidate_counts.is_empty() {
+            self.candidate_counts.push(candidates.len());
+        }
+        solver::retain_candidates(candidates, &self.known_states[row], &self.known_counts[row]);
+        self.candidate_counts.push(candidates.len());
+    }
+
+    fn update_shared_guesses(&mut self) {
+        self.shared_guesses = Rc::new(self.guesses.clone());
+    }
+
+    fn update_keyboard(&mut self) {
+        if self.is_spectating {
+            self.keyboard = game::keyboard_from_guesses(&self.guesses);
+            return;
+        }
+
+        self.keyboard = ALLOWED_KEYS
+            .iter()
+            .map(|key| {
+                let state = game::keyboard_tile_state(
+                    key,
+                    self.current_guess,
+                    &self.known_states,
+                    &self.known_counts,
+                );
+                (*key, KeyState::Single(state))
+            })
+            .collect();
+    }
+
+    fn is_correct_word(&self) -> bool {
+        self.guesses[self.current_guess]
+            .iter()
+            .map(|(c, _)| *c)
+            .collect::<Vec<char>>()
+            == self.word
+    }
+
+    pub fn is_game_ended(&self) -> bool {
+        self.is_winner || self.current_guess == self.max_guesses - 1
+    }
+
+    // A contradicting guess is held back with a warning until it's submitted again in time
+    fn is_contradiction_confirmed(&mut self) -> bool {
+        if !self.warn_contradictions {
+            return true;
+        }
+        let contradiction = match game::find_contradiction(
+            &self.guesses[self.current_guess],
+            self.current_guess,
+            &self.known_states,
+            &self.known_counts,
+        ) {
+            Some(contradiction) => contradiction,
+            None => return true,
+        };
+
+        let guess = self.last_guess();
+        let now = Utc::now();
+        if let Some((pending_guess, warned_at)) = &self.pending_contradiction {
+            let is_in_time = (now - *warned_at).num_milliseconds() <= CONTRADICTION_CONFIRM_MS;
+            if *pending_guess == guess && is_in_time {
+                return true;
+            }
+        }
+
+        self.message = strings::msg_contradiction(self.language, contradiction);
+        self.pending_contradiction = Some((guess, now));
+        false
+    }
+
+    fn clear_message(&mut self) {
+        self.pending_contradiction = None;
+        self.is_unknown = false;
+        self.message = String::new();
+        self.rejected_guess = String::new();
+    }
+
+    fn set_game_end_message(&mut self) {
+        if self.is_winner {
+            self.celebration = game::celebration(self.emoji_set);
+
+            let is_milestone = matches!(self.game_mode, GameMode::Classic | GameMode::Relay)
+                && STREAK_MILESTONES.contains(&self.streak);
+
+            if is_milestone {
+                self.message = strings::msg_streak_milestone(self.language, self.streak);
+            } else {
+                self.message = strings::msg_win(self.language, self.game_mode, &self.celebration);
+            }
+
+            if let Some(solve_time) = self.solve_time() {
+                self.message = strings::msg_solve_time(self.language, &self.message, &solve_time);
+            }
+        } else {
+            self.message = strings::msg_word_was(self.language, &self.word);
+        }
+    }
+
+    // The daily result with each guessed row formatted by `format_guess`
+    fn format_share(
+        &self,
+        options: ShareOptions,
+        format_guess: impl Fn(&[(char, TileState)]) -> String,
+    ) -> String {
+        let mut message = String::new();
+
+        if let GameMode::DailyWord(date) = self.game_mode {
+            let index = game::daily_word_index(date) + 1;
+            let guess_count = match self.guesses_used() {
+                Some(guesses_used) if self.is_winner => format!("{}", guesses_used),
+                _ => "X".to_owned(),
+            };
+
+            message += &format!("Sanuli #{} {}/{}", index, guess_count, self.max_guesses);
+            if self.is_late {
+                message += " ";
+                message += Text::Late.get(self.language);
+            }
+            if self.is_winner && !self.celebration.is_empty() {
+                message += " ";
+                message += &self.celebration;
+            }
+            if let Some(solve_time) = self.solve_time().filter(|_| options.show_solve_time) {
+                message += &format!(" ⏱️ {}", solve_time);
+            }
+            message += "\n\n";
+
+            for guess in self.guesses.iter() {
+                if guess.is_empty() {
+                    continue;
+                }
+
+                message += &format_guess(guess);
+                message += "\n";
+            }
+
+            if options.describe_rows {
+                message += "\n";
+                message += &share::describe_rows(self.language, &self.guesses);
+                message += "\n";
+            }
+        }
+
+        message
+    }
+
+    fn start_timer(&mut self) {
+        if self.timer_started_at.is_none() {
+            self.timer_started_at = Some(Utc::now());
+            self.is_timer_paused = false;
+        }
+    }
+
+    fn stop_timer(&mut self) {
+        if let Some(started_at) = self.timer_started_at.take() {
+            self.elapsed_ms += (Utc::now() - started_at).num_milliseconds().max(0);
+        }
+    }
+
+    fn clear_timer(&mut self) {
+        self.elapsed_ms = 0;
+        self.timer_started_at = None;
+        self.is_timer_paused = false;
+    }
+
+    // Words solved in under a second were hardly timed at all
+    fn solve_time(&self) -> Option<String> {
+        (self.elapsed_ms >= 1000).then(|| game::format_duration(self.elapsed_ms))
+    }
+
+    pub fn rehydrate(
+        snapshot: Value,
         allow_profanities: bool,
         word_lists: Rc<WordLists>,
-    ) -> Result<Self, StorageError> {
-        let game_key = &format!(
-            "game|{}|{}|{}",
-            serde_json::to_string(&game_mode).unwrap(),
-            serde_json::to_string(&word_list).unwrap(),
-            word_length
-        );
-
-        let mut game: Self = LocalStorage::get(game_key)?;
+    ) -> serde_json::Result<Self> {
+        let mut game: Self = serde_json::from_value(snapshot)?;
+        game.validate().map_err(serde::de::Error::custom)?;
         game.allow_profanities = allow_profanities;
         game.word_lists = word_lists;
 
@@ -341,12 +996,68 @@ impl Sanuli {
 
         Ok(game)
     }
+
+    // Stored and shared games can't be trusted, check the invariants that indexing relies on
+    pub fn validate(&mut self) -> Result<(), String> {
+        if self.word.len() != self.word_length {
+            return Err(format!(
+                "word has {} letters instead of {}",
+                self.word.len(),
+                self.word_length
+            ));
+        }
+
+        if self.current_guess >= self.max_guesses {
+            return Err(format!(
+                "current guess {} is out of {} guesses",
+                self.current_guess, self.max_guesses
+            ));
+        }
+
+        if let Some(row) = self
+            .guesses
+            .iter()
+            .position(|guess| guess.len() > self.word_length)
+        {
+            return Err(format!(
+                "guess {} is longer than {} letters",
+                row + 1,
+                self.word_length
+            ));
+        }
+
+        let submitted = if self.is_guessing {
+            self.current_guess
+        } else {
+            self.current_guess + 1
+        };
+
+        if self.guesses.len() < submitted {
+            return Err(format!("{} submitted guesses are missing", submitted));
+        }
+
+        if let Some(row) = self.guesses[..submitted]
+            .iter()
+            .position(|guess| guess.len() != self.word_length)
+        {
+            return Err(format!("submitted guess {} is incomplete", row + 1));
+        }
+
+        // Missing or extra empty rows are harmless, fit them to the board
+        self.guesses
+            .resize(self.max_guesses, Vec::with_capacity(self.word_length));
+
+        Ok(())
+    }
 }
 
 impl Game for Sanuli {
     fn game_mode(&self) -> &GameMode {
         &self.game_mode
     }
+    fn word_language(&self) -> WordLanguage {
+        self.word_language
+    }
     fn word_list(&self) -> &WordList {
         &self.word_list
     }
@@ -358,9 +1069,13 @@ impl Game for Sanuli {
     }
     fn boards(&self) -> Vec<Board> {
         let board = Board {
-            guesses: self.guesses.clone(),
+            guesses: self.shared_guesses.clone(),
+            new_information: self.new_information.clone(),
             current_guess: self.current_guess,
             is_guessing: self.is_guessing,
+            is_winner: self.is_winner,
+            word: self.word.clone(),
+            markers: self.markers.clone(),
         };
 
         vec![board]
@@ -370,6 +1085,10 @@ impl Game for Sanuli {
     }
 
     fn last_guess(&self) -> String {
+        if self.is_unknown {
+            return self.rejected_guess.clone();
+        }
+
         match self.guesses.get(self.current_guess) {
             Some(guess) => guess.iter().map(|(c, _)| c).collect::<String>(),
             None => String::new(),
@@ -378,6 +1097,15 @@ impl Game for Sanuli {
     fn streak(&self) -> usize {
         self.streak
     }
+    fn guesses_used(&self) -> Option<usize> {
+        if self.is_guessing {
+            None
+        } else if self.is_winner {
+            Some(self.current_guess + 1)
+        } else {
+            Some(self.max_guesses)
+        }
+    }
 
     fn is_guessing(&self) -> bool {
         self.is_guessing
@@ -394,10 +1122,46 @@ impl Game for Sanuli {
     fn is_unknown(&self) -> bool {
         self.is_unknown
     }
+    fn is_contradiction_pending(&self) -> bool {
+        self.pending_contradiction.is_some()
+    }
+    // Typing starts the timer, so a word that was never timed was never typed on
+    fn is_untouched(&self) -> bool {
+        self.is_guessing
+            && self.current_guess == 0
+            && self.guesses[0].is_empty()
+            && self.elapsed_ms == 0
+            && self.timer_started_at.is_none()
+    }
+    fn remaining_candidates(&self) -> Option<usize> {
+        self.candidates.as_ref().map(|candidates| candidates.len())
+    }
+    fn guess_analysis(&self) -> Option<Vec<GuessAnalysis>> {
+        // A shared word isn't picked apart, and a hidden one waits until it's revealed
+        if self.is_guessing || self.is_hidden || self.game_mode == GameMode::Shared {
+            return None;
+        }
+
+        let analysis = self
+            .candidate_counts
+            .windows(2)
+            .zip(&self.guesses)
+            .map(|(counts, guess)| GuessAnalysis {
+                guess: guess.iter().map(|(c, _)| *c).collect(),
+                before: counts[0],
+                after: counts[1],
+            })
+            .collect::<Vec<_>>();
+
+        (!analysis.is_empty()).then_some(analysis)
+    }
+    fn is_late(&self) -> bool {
+        self.is_late
+    }
     fn message(&self) -> String {
         self.message.clone()
     }
-    fn previous_guesses(&self) -> Vec<Vec<(char, TileState)>> {
+    fn previous_guesses(&self) -> SharedGuesses {
         self.previous_guesses.clone()
     }
 
@@ -405,53 +1169,210 @@ impl Game for Sanuli {
         self.allow_profanities = is_allowed;
     }
 
+    fn set_weighted_selection(&mut self, is_weighted: bool) {
+        if self.is_weighted_selection == is_weighted {
+            return;
+        }
+        self.is_weighted_selection = is_weighted;
+
+        // An untouched board draws its word again so that the setting applies right away
+        let is_untouched = self.current_guess == 0
+            && self.guesses[0].is_empty()
+            && self.previous_guesses.is_empty();
+        let is_random_word = matches!(
+            self.game_mode,
+            GameMode::Classic | GameMode::Relay | GameMode::Quadruple | GameMode::Practice(_)
+        );
+
+        if is_untouched && is_random_word && self.word_list == WordList::Full {
+            self.word = Self::get_word(
+                self.word_language,
+                self.game_mode,
+                self.word_list,
+                self.word_length,
+                self.allow_profanities,
+                self.is_weighted_selection,
+                &self.word_lists,
+            );
+            self.word_source = self.find_word_source();
+            self.check_practice_letter();
+        }
+    }
+
+    fn pause_timer(&mut self) {
+        if self.timer_started_at.is_some() {
+            self.stop_timer();
+            self.is_timer_paused = true;
+        }
+    }
+
+    fn resume_timer(&mut self) {
+        if self.is_timer_paused && self.is_guessing {
+            self.start_timer();
+        }
+    }
+
+    fn set_emoji_set(&mut self, emoji_set: EmojiSet) {
+        self.emoji_set = emoji_set;
+    }
+
+    fn set_language(&mut self, language: Language) {
+        self.language = language;
+        if self.is_spectating {
+            self.message = Text::GameInProgress.get(language).to_owned();
+        }
+    }
+
+    fn mark_late(&mut self) {
+        self.is_late = matches!(self.game_mode, GameMode::DailyWord(_));
+    }
+
+    fn restore_streak(&mut self, streak: usize) {
+        self.streak = streak;
+        self.message = Text::GameNotRestored.get(self.language).to_owned();
+    }
+
+    fn set_message(&mut self, message: String) {
+        self.message = message;
+    }
+
+    fn set_show_candidates(&mut self, is_shown: bool) {
+        self.show_candidates = is_shown;
+        self.rebuild_candidates();
+    }
+
+    fn set_warn_contradictions(&mut self, is_warned: bool) {
+        self.warn_contradictions = is_warned;
+        if !is_warned && self.pending_contradiction.take().is_some() {
+            self.message = String::new();
+        }
+    }
+
+    fn set_hide_typing_hints(&mut self, is_hidden: bool) {
+        if self.hide_typing_hints == is_hidden {
+            return;
+        }
+        self.hide_typing_hints = is_hidden;
+
+        // Letters already typed follow the setting too
+        if self.is_guessing {
+            let row = self.guesses[self.current_guess].clone();
+            self.guesses[self.current_guess] = row
+                .into_iter()
+                .enumerate()
+                .map(|(index, (character, _))| (character, self.typing_hint(character, index)))
+                .collect();
+            self.update_shared_guesses();
+        }
+    }
+
+    fn is_hint_assisted(&self) -> bool {
+        self.is_hint_assisted || self.is_current_row_hinted()
+    }
+
+    fn is_spectating(&self) -> bool {
+        self.is_spectating
+    }
+
+    fn word_source(&self) -> Option<WordList> {
+        self.word_source
+    }
+
+    fn is_guess_ready(&self) -> bool {
+        self.is_guessing && self.is_guess_correct_length() && self.is_guess_accepted_word()
+    }
+
+    fn board_progress(&self) -> Option<BoardProgress> {
+        None
+    }
+
+    fn cycle_tile_marker(&mut self, board: usize, row: usize, column: usize) -> bool {
+        let is_submitted =
+            row < self.current_guess || (!self.is_guessing && row == self.current_guess);
+        if board != 0 || !is_submitted || column >= self.guesses[row].len() {
+            return false;
+        }
+
+        match TileMarker::cycle(self.markers.get(&(row, column)).copied()) {
+            Some(marker) => self.markers.insert((row, column), marker),
+            None => self.markers.remove(&(row, column)),
+        };
+        true
+    }
+
     fn title(&self) -> String {
         if let GameMode::DailyWord(date) = self.game_mode {
-            format!("Päivän sanuli #{}", Self::get_daily_word_index(date) + 1)
-        } else if self.game_mode == GameMode::Shared {
-            "Jaettu sanuli".to_owned()
-        } else if self.streak > 0 {
-            format!("Sanuli — Putki: {}", self.streak)
+            let number = game::daily_word_index(date) + 1;
+            let title = strings::daily_title(self.language, number);
+            if self.is_late {
+                format!("{} {}", title, Text::Late.get(self.language))
+            } else {
+                title
+            }
+        } else if let (GameMode::Shared, Some(tag)) = (self.game_mode, &self.shared_by) {
+            format!(
+                "{} ({})",
+                strings::game_mode_name(self.language, self.game_mode),
+                tag
+            )
+        } else if matches!(
+            self.game_mode,
+            GameMode::Shared | GameMode::Hotseat | GameMode::Practice(_)
+        ) {
+            strings::game_mode_name(self.language, self.game_mode)
         } else {
-            "Sanuli".to_owned()
+            game::streak_title(
+                self.language,
+                "Sanuli",
+                self.word_length,
+                self.word_list,
+                self.streak,
+            )
         }
     }
 
     fn next_word(&mut self) {
-        let next_word = Self::get_word(
-            self.game_mode,
-            self.word_list,
-            self.word_length,
-            self.allow_profanities,
-            &self.word_lists,
-        );
+        let next_word = match self.prepared_word.take() {
+            // A word drawn before the settings changed is drawn again
+            Some((draw, word)) if draw == self.word_draw() => word,
+            _ => Self::get_word(
+                self.word_language,
+                self.game_mode,
+                self.word_list,
+                self.word_length,
+                self.allow_profanities,
+                self.is_weighted_selection,
+                &self.word_lists,
+            ),
+        };
 
         let previous_word = mem::replace(&mut self.word, next_word);
+        self.word_source = self.find_word_source();
+        if !self.is_winner {
+            self.chain.clear();
+        }
 
+        let mut previous_guesses = mem::take(&mut self.guesses);
         if previous_word.len() <= self.word_length {
-            self.previous_guesses = mem::take(&mut self.guesses);
             if self.game_mode == GameMode::Relay && self.is_winner {
-                self.previous_guesses.truncate(self.current_guess);
+                previous_guesses.truncate(self.current_guess);
             } else {
-                self.previous_guesses.truncate(self.current_guess + 1);
+                previous_guesses.truncate(self.current_guess + 1);
             }
         } else {
-            let previous_guesses = mem::take(&mut self.guesses);
-            self.previous_guesses = previous_guesses
-                .into_iter()
-                .map(|guess| guess.into_iter().take(self.word_length).collect())
-                .collect();
-            self.previous_guesses.truncate(self.current_guess);
+            previous_guesses.truncate(self.current_guess);
         }
+        self.previous_guesses = Rc::new(fit_rows(previous_guesses, self.word_length));
 
         self.guesses = Vec::with_capacity(self.max_guesses);
 
-        self.known_states = std::iter::repeat(HashMap::new())
+        self.known_states = std::iter::repeat(KnownStates::default())
             .take(self.max_guesses)
             .collect::<Vec<_>>();
         self.known_counts = std::iter::repeat(HashMap::new())
             .take(self.max_guesses)
             .collect::<Vec<_>>();
+        self.new_information = Vec::new();
 
         if previous_word.len() == self.word_length
             && self.is_winner
@@ -461,24 +1382,10 @@ impl Game for Sanuli {
                 .take(self.max_guesses - 1)
                 .collect::<Vec<_>>();
 
-            self.guesses.push(
-                previous_word
-                    .iter()
-                    .map(|c| (*c, TileState::Unknown))
-                    .collect(),
-            );
+            // The word from previous round is carried over already colored against the new word
+            let carried_row = self.evaluate_row(&previous_word, 0);
+            self.guesses.push(carried_row);
             self.guesses.extend(empty_guesses);
-
-            self.current_guess = 0;
-            // Update the known states of the word from previous round
-            game::update_known_information(
-                &mut self.known_states,
-                &mut self.known_counts,
-                &mut self.guesses[self.current_guess],
-                self.current_guess,
-                &self.word,
-                self.max_guesses,
-            );
             self.current_guess = 1;
         } else {
             self.guesses = std::iter::repeat(Vec::with_capacity(self.word_length))
@@ -489,50 +1396,93 @@ impl Game for Sanuli {
 
         self.is_guessing = true;
         self.is_winner = false;
+        self.is_hint_assisted = false;
+        self.markers.clear();
         self.is_reset = true;
+        self.clear_timer();
         self.clear_message();
+        self.check_practice_letter();
+        self.rebuild_candidates();
+        self.update_shared_guesses();
+        self.update_keyboard();
+    }
+
+    fn prepare_next_word(&mut self) {
+        if self.is_next_word_prepared() {
+            return;
+        }
 
-        let _result = self.persist();
+        let word = Self::get_word(
+            self.word_language,
+            self.game_mode,
+            self.word_list,
+            self.word_length,
+            self.allow_profanities,
+            self.is_weighted_selection,
+            &self.word_lists,
+        );
+        self.prepared_word = Some((self.word_draw(), word));
     }
 
-    fn keyboard_tilestate(&self, key: &char) -> KeyState {
-        KeyState::Single(game::keyboard_tile_state(
-            key,
-            self.current_guess,
-            &self.known_states,
-            &self.known_counts,
-        ))
+    fn is_next_word_prepared(&self) -> bool {
+        // Only random words take time to draw
+        if matches!(
+            self.game_mode,
+            GameMode::DailyWord(_) | GameMode::Shared | GameMode::Hotseat
+        ) {
+            return true;
+        }
+
+        self.prepared_word
+            .as_ref()
+            .is_some_and(|(draw, _)| *draw == self.word_draw())
+    }
+
+    fn keyboard_state(&self) -> &HashMap<char, KeyState> {
+        &self.keyboard
     }
 
-    fn submit_guess(&mut self) {
+    fn submit_guess(&mut self) -> Option<GameResult> {
+        if !self.is_guessing {
+            return None;
+        }
         if !self.is_guess_correct_length() {
-            self.message = "Liian vähän kirjaimia!".to_owned();
-            return;
+            self.message = Text::TooFewLetters.get(self.language).to_owned();
+            return None;
         }
         if !self.is_guess_accepted_word() {
+            self.rejected_guess = self.last_guess();
             self.is_unknown = true;
-            self.message = "Ei sanulistalla.".to_owned();
-            return;
+            self.message = Text::NotInWordList.get(self.language).to_owned();
+            return None;
+        }
+        if !self.is_contradiction_confirmed() {
+            return None;
         }
 
         self.is_reset = false;
         self.clear_message();
 
+        self.is_hint_assisted |= self.is_current_row_hinted();
         self.is_winner = self.is_correct_word();
-        game::update_known_information(
-            &mut self.known_states,
-            &mut self.known_counts,
-            &mut self.guesses[self.current_guess],
-            self.current_guess,
-            &self.word,
-            self.max_guesses,
-        );
+        self.update_known_information(self.current_guess);
+        self.narrow_candidates(self.current_guess);
         if self.is_game_ended() {
             self.is_guessing = false;
+            self.stop_timer();
+            self.is_completed = matches!(self.game_mode, GameMode::DailyWord(_));
+            if self.game_mode == GameMode::Relay {
+                self.chain
+                    .push(self.guesses_used().filter(|_| self.is_winner));
+            }
 
             if matches!(
                 self.game_mode,
-                GameMode::DailyWord(_) | GameMode::Shared | GameMode::Quadruple
+                GameMode::DailyWord(_)
+                    | GameMode::Shared
+                    | GameMode::Quadruple
+                    | GameMode::Hotseat
+                    | GameMode::Practice(_)
             ) {
                 // Do nothing, don't update streaks
             } else if self.is_winner {
@@ -546,107 +1496,120 @@ impl Game for Sanuli {
             self.current_guess += 1;
         }
 
-        let _result = self.persist();
+        self.update_shared_guesses();
+        self.update_keyboard();
+
+        self.guesses_used().map(|guesses_used| GameResult {
+            game_mode: self.game_mode,
+            words: vec![self.word.clone()],
+            is_winner: self.is_winner,
+            guesses_used,
+            streak: self.streak,
+            is_late: self.is_late,
+        })
     }
 
-    fn push_character(&mut self, character: char) {
+    fn push_character(&mut self, character: char) -> bool {
         if !self.is_guessing || self.guesses[self.current_guess].len() >= self.word_length {
-            return;
+            return false;
+        }
+        // A physical keyboard can type letters the word language doesn't have
+        if !game::alphabet(self.word_language).any(|letter| letter == character) {
+            return false;
         }
 
         self.clear_message();
+        self.start_timer();
 
-        // Display a hint of the tile state based on already known information
-        let tile_state = game::hint_tile_state(
-            character,
-            self.guesses[self.current_guess].len(),
-            self.current_guess,
-            &self.known_states,
-            &self.known_counts,
-        );
+        let tile_state = self.typing_hint(character, self.guesses[self.current_guess].len());
         self.guesses[self.current_guess].push((character, tile_state));
+        self.update_shared_guesses();
+        true
     }
 
-    fn pop_character(&mut self) {
+    fn pop_character(&mut self) -> bool {
         if !self.is_guessing || self.guesses[self.current_guess].is_empty() {
-            return;
+            return false;
         }
 
         self.clear_message();
         self.guesses[self.current_guess].pop();
+        self.update_shared_guesses();
+        true
     }
 
-    fn share_emojis(&self, theme: Theme) -> Option<String> {
-        let mut message = String::new();
+    fn share_emojis(&self, theme: Theme, options: ShareOptions) -> Option<String> {
+        if self.game_mode == GameMode::Relay {
+            return Some(share::relay_chain(self.language, &self.chain));
+        }
 
-        if let GameMode::DailyWord(date) = self.game_mode {
-            let index = Self::get_daily_word_index(date) + 1;
-            let guess_count = if self.is_winner {
-                format!("{}", self.current_guess + 1)
-            } else {
-                "X".to_owned()
-            };
+        Some(self.format_share(options, |guess| {
+            guess
+                .iter()
+                .map(|(_, state)| share::tile_emoji(*state, theme))
+                .collect()
+        }))
+    }
 
-            message += &format!("Sanuli #{} {}/{}", index, guess_count, self.max_guesses);
-            message += "\n\n";
+    fn share_emoji_counts(&self, theme: Theme, options: ShareOptions) -> Option<String> {
+        Some(self.format_share(options, |guess| share::count_emojis(guess, theme)))
+    }
 
-            for guess in self.guesses.iter() {
-                if guess.is_empty() {
-                    continue;
-                }
-                let guess_string = guess
-                    .iter()
-                    .map(|(_, state)| match state {
-                        TileState::Correct => match theme {
-                            Theme::Colorblind => "🟧",
-                            _ => "🟩",
-                        },
-                        TileState::Present => match theme {
-                            Theme::Colorblind => "🟦",
-                            _ => "🟨",
-                        },
-                        TileState::Absent => "⬛",
-                        TileState::Unknown => "⬜",
-                    })
-                    .collect::<String>();
-
-                message += &guess_string;
-                message += "\n";
-            }
+    fn share_payload(&self, sharer_tag: Option<&str>) -> Option<String> {
+        if self.is_spectating {
+            return None;
         }
 
-        Some(message)
+        Some(self.encode_share_payload(sharer_tag))
     }
 
-    fn share_link(&self) -> Option<String> {
-        let game_str = format!(
-            "{}|{}",
-            self.word.iter().collect::<String>(),
-            self.guesses
-                .iter()
-                .flat_map(|guess| guess.iter().map(|(c, _)| c))
-                .collect::<String>(),
-        );
-        let window: Window = window().expect("window not available");
-        let share_str = window.btoa(&game_str).ok()?;
-
-        let base_url = window.location().origin().ok()?;
+    fn progress_payload(&self, sharer_tag: Option<&str>) -> Option<String> {
+        // Two-player words were picked by the friend, there's nobody to ask for help
+        if !self.is_guessing || self.current_guess == 0 || self.game_mode == GameMode::Hotseat {
+            return None;
+        }
 
-        // Replace +/= at the base64 with URL safe characters
-        let safe_str = share_str
-            .replace("+", "-")
-            .replace("/", ".")
-            .replace("=", "_");
+        Some(self.encode_progress_payload(sharer_tag))
+    }
 
-        return Some(format!("{}/?peli={}", base_url, safe_str));
+    fn hide_word(&mut self) {
+        self.is_hidden = true;
+        self.message = Text::GameEnded.get(self.language).to_owned();
     }
 
     fn reveal_hidden_tiles(&mut self) {
+        // The word of a spectated game isn't known, there's nothing to reveal
+        if self.is_spectating {
+            return;
+        }
+
         self.is_hidden = false;
-        self.message = format!("Sana oli \"{}\"", self.word.iter().collect::<String>());
+        if self.game_mode == GameMode::Shared {
+            self.message = strings::msg_word_was(self.language, &self.word);
+            if self.is_guess_repeated {
+                self.message = format!(
+                    "{}. {}",
+                    self.message,
+                    Text::SharedGuessRepeated.get(self.language)
+                );
+            }
+        } else {
+            self.set_game_end_message();
+        }
+    }
+
+    fn clear_reset(&mut self) {
+        self.is_reset = false;
     }
 
     fn reset(&mut self) {
+        if let GameMode::DailyWord(_) = self.game_mode {
+            if self.is_completed || !self.is_guessing {
+                self.message = Text::DailyAlreadyPlayed.get(self.language).to_owned();
+                return;
+            }
+        }
+
         self.guesses = std::iter::repeat(Vec::with_capacity(self.word_length))
             .take(self.max_guesses)
             .collect::<Vec<_>>();
@@ -655,70 +1618,1141 @@ impl Game for Sanuli {
 
         self.is_guessing = true;
         self.is_winner = false;
+        self.is_hint_assisted = false;
+        self.markers.clear();
         self.is_unknown = false;
         self.is_reset = false;
         self.is_hidden = false;
-        self.message = "Peli nollattu, arvaa sanuli!".to_owned();
+        self.message = Text::GameReset.get(self.language).to_owned();
+        self.clear_timer();
 
-        self.known_states = std::iter::repeat(HashMap::new())
+        self.known_states = std::iter::repeat(KnownStates::default())
             .take(self.max_guesses)
             .collect::<Vec<_>>();
 
         self.known_counts = std::iter::repeat(HashMap::new())
             .take(self.max_guesses)
             .collect::<Vec<_>>();
+        self.new_information = Vec::new();
+        self.candidates = None;
+        self.candidate_counts = Vec::new();
 
-        self.previous_guesses = Vec::new();
+        self.previous_guesses = Rc::default();
+        self.update_shared_guesses();
+        self.update_keyboard();
     }
 
     fn refresh(&mut self) {
-        self.known_states = std::iter::repeat(HashMap::new())
+        self.known_states = std::iter::repeat(KnownStates::default())
             .take(self.max_guesses)
             .collect::<Vec<_>>();
 
         self.known_counts = std::iter::repeat(HashMap::new())
             .take(self.max_guesses)
             .collect::<Vec<_>>();
+        self.new_information = Vec::new();
 
-        // Rerun the game to refresh known_states and known_counts
-        for guess_index in 0..self.current_guess {
-            game::update_known_information(
-                &mut self.known_states,
-                &mut self.known_counts,
-                &mut self.guesses[guess_index],
-                guess_index,
-                &self.word,
-                self.max_guesses,
-            );
+        // A spectated game has no word to rerun against, its guesses keep the colors of the link
+        if !self.is_spectating {
+            // Rerun the game to refresh known_states and known_counts
+            for guess_index in 0..self.current_guess {
+                self.update_known_information(guess_index);
+            }
+
+            // If the game is ended also update the current guess
+            if !self.is_guessing {
+                self.update_known_information(self.current_guess);
+            }
         }
 
-        // If the game is ended also update the current guess
-        if !self.is_guessing {
-            game::update_known_information(
-                &mut self.known_states,
-                &mut self.known_counts,
-                &mut self.guesses[self.current_guess],
-                self.current_guess,
-                &self.word,
-                self.max_guesses,
+        self.rebuild_candidates();
+        self.update_shared_guesses();
+        self.update_keyboard();
+    }
+
+    fn storage_key(&self) -> Option<String> {
+        if matches!(
+            self.game_mode,
+            GameMode::Shared | GameMode::Quadruple | GameMode::Hotseat
+        ) {
+            // Never persist shared, two-player or subgames within quadruple mode
+            return None;
+        }
+
+        Some(game::storage_key(
+            self.word_language,
+            &self.game_mode,
+            &self.word_list,
+            self.word_length,
+        ))
+    }
+
+    fn snapshot(&self) -> serde_json::Result<Value> {
+        serde_json::to_value(self)
+    }
+}
+
+// The previous board slides out at the width of the next word, so its rows are cut or padded
+fn fit_rows(rows: Vec<Vec<(char, TileState)>>, word_length: usize) -> Vec<Vec<(char, TileState)>> {
+    rows.into_iter()
+        .map(|mut row| {
+            row.resize(word_length, (game::EMPTY, TileState::Unknown));
+            row
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Contradiction;
+
+    fn word_lists(words: &[&str]) -> Rc<WordLists> {
+        let mut word_lists: WordLists = HashMap::new();
+
+        for word in words {
+            let chars: Vec<char> = word.chars().collect();
+            for list in [WordList::Full, WordList::Common] {
+                word_lists
+                    .entry((WordLanguage::Finnish, list, chars.len()))
+                    .or_default()
+                    .insert(chars.clone());
+            }
+        }
+
+        Rc::new(word_lists)
+    }
+
+    fn other_word(game: &Sanuli, words: &[&str]) -> Vec<char> {
+        words
+            .iter()
+            .map(|word| word.chars().collect::<Vec<_>>())
+            .find(|word| *word != game.word && word.len() == game.word_length)
+            .unwrap()
+    }
+
+    fn type_word(game: &mut Sanuli, word: &[char]) {
+        for character in word {
+            game.push_character(*character);
+        }
+    }
+
+    #[test]
+    fn contradicting_guess_is_accepted_when_submitted_again() {
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["SAUNA", "TALVI", "TASKU"]),
+        );
+        game.word = "TALVI".chars().collect();
+        game.set_warn_contradictions(true);
+
+        type_word(&mut game, &['S', 'A', 'U', 'N', 'A']);
+        game.submit_guess();
+        assert_eq!(game.current_guess, 1);
+
+        // S was found not to be in the word
+        type_word(&mut game, &['T', 'A', 'S', 'K', 'U']);
+        game.submit_guess();
+        assert_eq!(game.current_guess, 1);
+        assert!(game.is_contradiction_pending());
+        assert_eq!(
+            game.message(),
+            strings::msg_contradiction(Language::Finnish, Contradiction::AbsentLetter('S'))
+        );
+
+        // Editing the guess asks for a new confirmation
+        game.pop_character();
+        assert!(!game.is_contradiction_pending());
+        game.push_character('U');
+        game.submit_guess();
+        assert_eq!(game.current_guess, 1);
+
+        game.submit_guess();
+        assert_eq!(game.current_guess, 2);
+        assert!(!game.is_contradiction_pending());
+        assert!(game.message().is_empty());
+    }
+
+    #[test]
+    fn letters_of_other_word_languages_are_not_typed() {
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["TALVI"]),
+        );
+
+        assert!(!game.push_character('Å'));
+        assert!(game.push_character('Ä'));
+        assert_eq!(game.guesses[0].len(), 1);
+        assert_eq!(game.guesses[0][0].0, 'Ä');
+    }
+
+    #[test]
+    fn candidates_narrow_down_with_each_guess() {
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["SAUNA", "TALVI", "KALJA", "VALVE", "TASKU"]),
+        );
+        game.word = "TALVI".chars().collect();
+        assert_eq!(game.remaining_candidates(), None);
+
+        game.set_show_candidates(true);
+        assert_eq!(game.remaining_candidates(), None);
+
+        // Of SAUNA only a single A in the second position is left
+        type_word(&mut game, &['S', 'A', 'U', 'N', 'A']);
+        game.submit_guess();
+        assert_eq!(game.remaining_candidates(), Some(2));
+
+        type_word(&mut game, &['V', 'A', 'L', 'V', 'E']);
+        game.submit_guess();
+        assert_eq!(game.remaining_candidates(), Some(1));
+
+        // A restored game counts from the full list again
+        game.refresh();
+        assert_eq!(game.remaining_candidates(), Some(1));
+
+        game.set_show_candidates(false);
+        assert_eq!(game.remaining_candidates(), None);
+    }
+
+    #[test]
+    fn analysis_follows_the_finished_game() {
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["SAUNA", "TALVI", "KALJA", "VALVE", "TASKU"]),
+        );
+        game.word = "TALVI".chars().collect();
+        game.set_show_candidates(true);
+
+        for guess in ["VALVE", "TASKU", "TALVI"] {
+            type_word(&mut game, &guess.chars().collect::<Vec<_>>());
+            assert!(game.guess_analysis().is_none());
+            game.submit_guess();
+        }
+
+        let analysis = game.guess_analysis().unwrap();
+        let counts = analysis
+            .iter()
+            .map(|row| (row.guess.as_str(), row.before, row.after))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            counts,
+            vec![("VALVE", 5, 1), ("TASKU", 1, 1), ("TALVI", 1, 1)]
+        );
+        assert!(analysis.iter().all(|row| !row.is_weak()));
+
+        // A restored game works the counts out again
+        game.refresh();
+        assert_eq!(game.guess_analysis(), Some(analysis));
+
+        game.hide_word();
+        assert!(game.guess_analysis().is_none());
+    }
+
+    #[test]
+    fn relay_carries_the_solved_word_over() {
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Relay,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["KISSA", "KOIRA", "KARIT"]),
+        );
+
+        let previous_word = game.word();
+        type_word(&mut game, &previous_word);
+        game.submit_guess();
+
+        assert!(game.is_winner());
+        assert_eq!(game.streak(), 1);
+        assert_eq!(game.guesses_used(), Some(1));
+
+        game.next_word();
+
+        assert!(game.is_guessing());
+        assert_eq!(game.guesses_used(), None);
+        assert_eq!(game.current_guess, 1);
+
+        assert_eq!(game.chain, vec![Some(1)]);
+
+        let carryover = &game.guesses[0];
+        assert_eq!(
+            carryover.iter().map(|(c, _)| *c).collect::<Vec<_>>(),
+            previous_word
+        );
+
+        // The carried over row is colored against the new word
+        for (index, (character, state)) in carryover.iter().enumerate() {
+            if game.word[index] == *character {
+                assert_eq!(*state, TileState::Correct);
+            } else {
+                assert!(*state != TileState::Correct && *state != TileState::Unknown);
+            }
+        }
+    }
+
+    #[test]
+    fn previous_guesses_fit_the_next_word_length() {
+        let mut game = Sanuli::with_word(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            "KISSA".chars().collect(),
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["KISSA", "KOIRA", "SAUNAT", "KISSAT"]),
+        );
+        let empty = (' ', TileState::Unknown);
+
+        type_word(&mut game, &"KOIRA".chars().collect::<Vec<_>>());
+        game.submit_guess();
+        game.word_length = 6;
+        game.next_word();
+
+        assert_eq!(game.previous_guesses.len(), 2);
+        assert_eq!(
+            game.previous_guesses[0],
+            vec![
+                ('K', TileState::Correct),
+                ('O', TileState::Absent),
+                ('I', TileState::Present),
+                ('R', TileState::Absent),
+                ('A', TileState::Correct),
+                empty,
+            ]
+        );
+        assert_eq!(game.previous_guesses[1], vec![empty; 6]);
+
+        game.word = "SAUNAT".chars().collect();
+        type_word(&mut game, &"KISSAT".chars().collect::<Vec<_>>());
+        game.submit_guess();
+        game.word_length = 5;
+        game.next_word();
+
+        assert_eq!(game.previous_guesses.len(), 1);
+        assert_eq!(
+            game.previous_guesses[0],
+            vec![
+                ('K', TileState::Absent),
+                ('I', TileState::Absent),
+                ('S', TileState::Present),
+                ('S', TileState::Absent),
+                ('A', TileState::Correct),
+            ]
+        );
+    }
+
+    #[test]
+    fn hint_colors_can_be_hidden_while_typing() {
+        let mut game = Sanuli::with_word(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            "KISSA".chars().collect(),
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["KISSA", "KOIRA", "KASSI"]),
+        );
+
+        type_word(&mut game, &"KOIRA".chars().collect::<Vec<_>>());
+        game.submit_guess();
+        assert!(!game.is_hint_assisted());
+
+        game.push_character('K');
+        assert_eq!(game.guesses[1][0].1, TileState::Correct);
+        assert!(game.is_hint_assisted());
+
+        game.set_hide_typing_hints(true);
+        assert_eq!(game.guesses[1][0].1, TileState::Unknown);
+        assert!(!game.is_hint_assisted());
+        type_word(&mut game, &"ASSI".chars().collect::<Vec<_>>());
+        assert!(game.guesses[1]
+            .iter()
+            .all(|(_, state)| *state == TileState::Unknown));
+        game.submit_guess();
+        assert!(!game.is_hint_assisted());
+
+        game.set_hide_typing_hints(false);
+        type_word(&mut game, &"KISSA".chars().collect::<Vec<_>>());
+        game.submit_guess();
+        assert!(game.is_winner());
+        assert!(game.is_hint_assisted());
+    }
+
+    #[test]
+    fn full_list_words_are_tagged_by_the_narrowest_list() {
+        let mut lists = (*word_lists(&["KISSA"])).clone();
+        lists
+            .get_mut(&(WordLanguage::Finnish, WordList::Full, 5))
+            .unwrap()
+            .insert("KOIRA".chars().collect());
+        let lists = Rc::new(lists);
+
+        let source = |game_mode: GameMode, word_list: WordList, word: &str| {
+            Sanuli::with_word(
+                WordLanguage::Finnish,
+                game_mode,
+                word_list,
+                word.chars().collect(),
+                DEFAULT_MAX_GUESSES,
+                true,
+                lists.clone(),
+            )
+            .word_source()
+        };
+
+        assert_eq!(
+            source(GameMode::Classic, WordList::Full, "KISSA"),
+            Some(WordList::Common)
+        );
+        assert_eq!(
+            source(GameMode::Classic, WordList::Full, "KOIRA"),
+            Some(WordList::Full)
+        );
+        assert_eq!(
+            source(GameMode::Classic, WordList::Common, "KISSA"),
+            Some(WordList::Common)
+        );
+        assert!(source(GameMode::Hotseat, WordList::Full, "KOIRA").is_none());
+
+        let daily = GameMode::DailyWord(NaiveDate::from_ymd_opt(2022, 1, 7).unwrap());
+        assert_eq!(
+            source(daily, WordList::Daily, "KISSA"),
+            Some(WordList::Daily)
+        );
+    }
+
+    #[test]
+    fn markers_stay_out_of_the_game() {
+        let mut game = Sanuli::with_word(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            "KISSA".chars().collect(),
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["KISSA", "KOIRA"]),
+        );
+
+        type_word(&mut game, &"KOIRA".chars().collect::<Vec<_>>());
+        game.submit_guess();
+        type_word(&mut game, &"KI".chars().collect::<Vec<_>>());
+
+        // Only tiles of submitted guesses can be marked
+        assert!(!game.cycle_tile_marker(0, 1, 0));
+        assert!(!game.cycle_tile_marker(0, 0, 5));
+        let share = game.share_emojis(Theme::Dark, ShareOptions::default());
+
+        assert!(game.cycle_tile_marker(0, 0, 1));
+        assert!(game.cycle_tile_marker(0, 0, 2));
+        assert!(game.cycle_tile_marker(0, 0, 2));
+        assert_eq!(game.markers[&(0, 1)], TileMarker::Star);
+        assert_eq!(game.markers[&(0, 2)], TileMarker::Strike);
+        assert_eq!(
+            game.share_emojis(Theme::Dark, ShareOptions::default()),
+            share
+        );
+        assert_eq!(
+            game.keyboard_state()[&'O'],
+            KeyState::Single(TileState::Absent)
+        );
+
+        let stored: Sanuli = serde_json::from_value(game.snapshot().unwrap()).unwrap();
+        assert_eq!(stored.markers, game.markers);
+
+        assert!(game.cycle_tile_marker(0, 0, 2));
+        assert!(!game.markers.contains_key(&(0, 2)));
+
+        type_word(&mut game, &"SSA".chars().collect::<Vec<_>>());
+        game.submit_guess();
+        game.next_word();
+        assert!(game.markers.is_empty());
+    }
+
+    #[test]
+    fn finishing_guess_returns_the_result() {
+        let words = ["KISSA", "KOIRA", "TALVI"];
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&words),
+        );
+
+        let word = game.word();
+        let guess = other_word(&game, &words);
+        type_word(&mut game, &guess);
+        assert!(game.submit_guess().is_none());
+        type_word(&mut game, &word);
+        let result = game.submit_guess();
+        assert_eq!(
+            result,
+            Some(GameResult {
+                game_mode: GameMode::Classic,
+                words: vec![word],
+                is_winner: true,
+                guesses_used: 2,
+                streak: 1,
+                is_late: false,
+            })
+        );
+
+        game.next_word();
+        let word = game.word();
+        let guess = other_word(&game, &words);
+        let mut result = None;
+        for _ in 0..DEFAULT_MAX_GUESSES {
+            type_word(&mut game, &guess);
+            result = game.submit_guess();
+        }
+        assert_eq!(
+            result,
+            Some(GameResult {
+                game_mode: GameMode::Classic,
+                words: vec![word],
+                is_winner: false,
+                guesses_used: DEFAULT_MAX_GUESSES,
+                streak: 0,
+                is_late: false,
+            })
+        );
+    }
+
+    #[test]
+    fn daily_result_has_no_streak() {
+        let daily = GameMode::DailyWord(NaiveDate::from_ymd_opt(2022, 1, 7).unwrap());
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            daily,
+            WordList::Daily,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["KISSA", "KOIRA"]),
+        );
+        game.mark_late();
+
+        let word = game.word();
+        type_word(&mut game, &word);
+        let result = game.submit_guess();
+        assert_eq!(
+            result,
+            Some(GameResult {
+                game_mode: daily,
+                words: vec![word],
+                is_winner: true,
+                guesses_used: 1,
+                streak: 0,
+                is_late: true,
+            })
+        );
+    }
+
+    #[test]
+    fn completed_daily_word_cannot_be_reset() {
+        let words = ["KISSA", "KOIRA", "TALVI"];
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&words),
+        );
+        game.game_mode = GameMode::DailyWord(NaiveDate::from_ymd_opt(2022, 1, 7).unwrap());
+
+        let word = game.word();
+        type_word(&mut game, &word);
+        game.submit_guess();
+        assert!(game.is_completed);
+
+        let snapshot = serde_json::to_value(&game).unwrap();
+        let mut rehydrated = Sanuli::rehydrate(snapshot, true, word_lists(&words)).unwrap();
+
+        for game in [&mut game, &mut rehydrated] {
+            game.reset();
+            assert!(game.is_winner());
+            assert!(!game.is_guessing());
+            assert_eq!(game.message(), "Päivän sanuli on jo pelattu");
+        }
+
+        // A shared copy of the same daily is just a puzzle to retry
+        rehydrated.game_mode = GameMode::Shared;
+        rehydrated.reset();
+        assert!(rehydrated.is_guessing());
+    }
+
+    #[test]
+    fn rejected_guess_is_kept_until_the_message_clears() {
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["KISSA", "KOIRA"]),
+        );
+
+        type_word(&mut game, &['K', 'I', 'S', 'S', 'Ä']);
+        game.submit_guess();
+        assert!(game.is_unknown());
+        assert_eq!(game.last_guess(), "KISSÄ");
+
+        game.pop_character();
+        assert!(!game.is_unknown());
+        assert_eq!(game.last_guess(), "KISS");
+    }
+
+    #[test]
+    fn weighted_selection_favors_common_words() {
+        let mut word_lists: WordLists = HashMap::new();
+        let rare_words = [
+            "KARIT", "SIKSI", "LAITA", "KESÄT", "PUTKI", "SANUL", "VÄRIT",
+        ];
+        for word in rare_words.iter().chain(&["KISSA"]) {
+            word_lists
+                .entry((WordLanguage::Finnish, WordList::Full, 5))
+                .or_default()
+                .insert(word.chars().collect());
+        }
+        word_lists
+            .entry((WordLanguage::Finnish, WordList::Common, 5))
+            .or_default()
+            .insert("KISSA".chars().collect());
+        let word_lists = Rc::new(word_lists);
+
+        let common_draws = (0..200)
+            .filter_map(|_| {
+                Sanuli::get_random_word(
+                    WordLanguage::Finnish,
+                    WordList::Full,
+                    5,
+                    true,
+                    true,
+                    &word_lists,
+                    |_| true,
+                )
+            })
+            .filter(|word| *word == vec!['K', 'I', 'S', 'S', 'A'])
+            .count();
+
+        // Around 140 when weighted, 25 when drawn evenly
+        assert!(common_draws > 100, "only {} common draws", common_draws);
+
+        let rare_draw = Sanuli::get_random_word(
+            WordLanguage::Finnish,
+            WordList::Full,
+            5,
+            true,
+            true,
+            &word_lists,
+            |word| word[0] == 'P',
+        );
+        assert_eq!(rare_draw, Some(vec!['P', 'U', 'T', 'K', 'I']));
+    }
+
+    #[test]
+    fn solve_time_survives_a_paused_refresh() {
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::DailyWord(NaiveDate::from_ymd_opt(2022, 1, 7).unwrap()),
+            WordList::Daily,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["KISSA", "KOIRA", "TALVI", "SANUL"]),
+        );
+        let word = game.word();
+
+        game.push_character(word[0]);
+        assert!(game.timer_started_at.is_some());
+        game.pause_timer();
+        game.elapsed_ms += 94_000;
+
+        let mut game = Sanuli::rehydrate(
+            game.snapshot().unwrap(),
+            true,
+            word_lists(&["KISSA", "KOIRA", "TALVI", "SANUL"]),
+        )
+        .unwrap();
+        assert!(game.timer_started_at.is_none());
+
+        type_word(&mut game, &word[1..]);
+        game.submit_guess();
+
+        assert!(game.message().ends_with("Ratkaistu 1 min 34 s"));
+        let options = ShareOptions {
+            show_solve_time: true,
+            ..ShareOptions::default()
+        };
+        let header = game.share_emojis(Theme::Dark, options).unwrap();
+        assert!(header.lines().next().unwrap().ends_with("⏱️ 1 min 34 s"));
+        let header = game
+            .share_emojis(Theme::Dark, ShareOptions::default())
+            .unwrap();
+        assert!(!header.contains("⏱️"));
+    }
+
+    #[test]
+    fn streak_milestone_is_celebrated() {
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["KISSA", "KOIRA"]),
+        );
+        game.streak = 9;
+
+        let word = game.word();
+        type_word(&mut game, &word);
+        game.submit_guess();
+
+        assert_eq!(game.streak(), 10);
+        assert_eq!(game.message(), "Putki 10! 🔥");
+    }
+
+    #[test]
+    fn keyboard_state_follows_submitted_guesses() {
+        let words = ["KISSA", "KOIRA", "TALVI"];
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&words),
+        );
+
+        let guess = other_word(&game, &words);
+        type_word(&mut game, &guess);
+        assert_eq!(
+            game.keyboard_state()[&guess[0]],
+            KeyState::Single(TileState::Unknown)
+        );
+
+        game.submit_guess();
+        for key in ALLOWED_KEYS {
+            let expected = game::keyboard_tile_state(
+                &key,
+                game.current_guess,
+                &game.known_states,
+                &game.known_counts,
             );
+            assert_eq!(game.keyboard_state()[&key], KeyState::Single(expected));
+        }
+        assert_ne!(
+            game.keyboard_state()[&guess[0]],
+            KeyState::Single(TileState::Unknown)
+        );
+
+        game.reset();
+        assert!(game
+            .keyboard_state()
+            .values()
+            .all(|state| *state == KeyState::Single(TileState::Unknown)));
+    }
+
+    #[test]
+    fn carried_row_is_stored_colored() {
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Relay,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["KISSA", "KOIRA"]),
+        );
+
+        let word = game.word();
+        type_word(&mut game, &word);
+        game.submit_guess();
+        game.next_word();
+
+        let snapshot = game.snapshot().unwrap();
+        let stored: Sanuli = serde_json::from_value(snapshot).unwrap();
+        assert_eq!(stored.guesses[0].len(), word.len());
+        assert!(stored.guesses[0]
+            .iter()
+            .all(|(_, state)| *state != TileState::Unknown));
+    }
+
+    #[test]
+    fn relay_loss_starts_from_an_empty_board() {
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Relay,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["KISSA", "KOIRA"]),
+        );
+
+        let wrong_word: Vec<char> = if game.word() == "KISSA".chars().collect::<Vec<_>>() {
+            "KOIRA".chars().collect()
+        } else {
+            "KISSA".chars().collect()
+        };
+
+        for _ in 0..DEFAULT_MAX_GUESSES {
+            type_word(&mut game, &wrong_word);
+            game.submit_guess();
         }
+
+        assert!(!game.is_guessing());
+        assert!(!game.is_winner());
+        assert_eq!(game.streak(), 0);
+
+        game.next_word();
+
+        assert_eq!(game.current_guess, 0);
+        assert!(game.guesses.iter().all(|guess| guess.is_empty()));
     }
 
-    fn persist(&self) -> Result<(), StorageError> {
-        if matches!(self.game_mode, GameMode::Shared | GameMode::Quadruple) {
-            // Never persist shared or subgames within quadruple mode
-            // The quadruple subgames should never call this persist anyways.
-            return Ok(());
+    #[test]
+    fn typing_reports_whether_the_row_changed() {
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["KISSA", "KOIRA"]),
+        );
+
+        assert!(!game.pop_character());
+        for character in "KISSA".chars() {
+            assert!(game.push_character(character));
         }
+        assert!(!game.push_character('K'));
+        assert!(!game.push_character('1'));
+        assert!(game.pop_character());
+    }
 
-        let game_key = &format!(
-            "game|{}|{}|{}",
-            serde_json::to_string(&self.game_mode).unwrap(),
-            serde_json::to_string(&self.word_list).unwrap(),
-            self.word_length
+    #[test]
+    fn prepared_word_is_dropped_when_the_settings_change() {
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&["KISSA", "KOIRA", "TALVI"]),
         );
 
-        LocalStorage::set(game_key, self)
+        assert!(!game.is_next_word_prepared());
+        game.prepare_next_word();
+        assert!(game.is_next_word_prepared());
+
+        let prepared = game.prepared_word.clone().unwrap().1;
+        game.next_word();
+        assert_eq!(game.word, prepared);
+        assert!(!game.is_next_word_prepared());
+
+        game.prepare_next_word();
+        game.set_allow_profanities(false);
+        assert!(!game.is_next_word_prepared());
+    }
+
+    #[test]
+    fn relay_continues_after_a_win_on_the_last_row() {
+        let words = ["KISSA", "KOIRA"];
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Relay,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&words),
+        );
+
+        let word = game.word();
+        let wrong_word = other_word(&game, &words);
+        for _ in 1..DEFAULT_MAX_GUESSES {
+            type_word(&mut game, &wrong_word);
+            game.submit_guess();
+        }
+        type_word(&mut game, &word);
+        game.submit_guess();
+
+        assert!(game.is_winner());
+        assert_eq!(game.guesses_used(), Some(DEFAULT_MAX_GUESSES));
+
+        game.next_word();
+        assert_eq!(game.current_guess, 1);
+
+        let wrong_word = other_word(&game, &words);
+        for _ in 1..DEFAULT_MAX_GUESSES {
+            type_word(&mut game, &wrong_word);
+            game.submit_guess();
+        }
+
+        assert!(!game.is_guessing());
+        assert!(!game.is_winner());
+        game.refresh();
+        assert_eq!(game.known_states.len(), DEFAULT_MAX_GUESSES);
+    }
+
+    #[test]
+    fn share_payload_round_trip() {
+        let words = ["KISSA", "KOIRA", "KÄÄPÄ", "KISSAT", "KOIRAT", "HÄÄYÖT"];
+
+        for word_length in [5, 6] {
+            for guess_count in 1..=DEFAULT_MAX_GUESSES {
+                let mut game = Sanuli::new(
+                    WordLanguage::Finnish,
+                    GameMode::Classic,
+                    WordList::Common,
+                    word_length,
+                    DEFAULT_MAX_GUESSES,
+                    true,
+                    word_lists(&words),
+                );
+
+                let wrong_word = other_word(&game, &words);
+                for _ in 1..guess_count {
+                    type_word(&mut game, &wrong_word);
+                    game.submit_guess();
+                }
+                let word = game.word();
+                type_word(&mut game, &word);
+                game.submit_guess();
+
+                assert!(game.is_winner());
+
+                let payload = share::decode_payload(&game.encode_share_payload(None)).unwrap();
+                let shared = Sanuli::from_shared_link(&payload, word_lists(&words)).unwrap();
+
+                assert_eq!(shared.game_mode, GameMode::Shared);
+                assert_eq!(shared.word, game.word);
+                assert_eq!(shared.current_guess, guess_count - 1);
+                assert_eq!(shared.guesses, game.guesses);
+            }
+        }
+    }
+
+    #[test]
+    fn progress_link_shows_guesses_without_the_word() {
+        let words = ["KISSA", "KOIRA", "TALVI"];
+        let mut game = Sanuli::with_word(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            "KISSA".chars().collect(),
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&words),
+        );
+        assert!(game.progress_payload(None).is_none());
+
+        type_word(&mut game, &"KOIRA".chars().collect::<Vec<_>>());
+        game.submit_guess();
+        type_word(&mut game, &"TA".chars().collect::<Vec<_>>());
+
+        let payload = share::decode_payload(&game.progress_payload(None).unwrap()).unwrap();
+        assert!(!payload.contains("KISSA"));
+
+        let mut shared = Sanuli::from_shared_link(&payload, word_lists(&words)).unwrap();
+        assert!(shared.is_spectating());
+        assert!(!shared.is_hidden());
+        assert!(shared.word.is_empty());
+        assert_eq!(shared.current_guess, 0);
+        assert_eq!(shared.guesses[0], game.guesses[0]);
+        assert!(shared.guesses[1].is_empty());
+        assert_eq!(
+            shared.keyboard_state()[&'K'],
+            KeyState::Single(TileState::Correct)
+        );
+        assert_eq!(
+            shared.keyboard_state()[&'O'],
+            KeyState::Single(TileState::Absent)
+        );
+        assert!(shared.share_payload(None).is_none());
+
+        assert!(!shared.push_character('K'));
+        shared.reveal_hidden_tiles();
+        assert_eq!(shared.message(), "Peli kesken");
+    }
+
+    #[test]
+    fn rehydrate_rejects_inconsistent_games() {
+        let words = ["KISSA", "KOIRA", "TALVI"];
+        let mut game = Sanuli::new(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            5,
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&words),
+        );
+        let guess = other_word(&game, &words);
+        type_word(&mut game, &guess);
+        game.submit_guess();
+
+        let snapshot = game.snapshot().unwrap();
+        let rehydrate = |snapshot: Value| Sanuli::rehydrate(snapshot, true, word_lists(&words));
+
+        assert!(rehydrate(snapshot.clone()).is_ok());
+
+        let mut corrupt = snapshot.clone();
+        corrupt["current_guess"] = Value::from(DEFAULT_MAX_GUESSES);
+        assert!(rehydrate(corrupt).is_err());
+
+        let mut corrupt = snapshot.clone();
+        corrupt["word"] = serde_json::json!(["K", "I", "S", "S"]);
+        assert!(rehydrate(corrupt).is_err());
+
+        let mut corrupt = snapshot.clone();
+        corrupt["guesses"][0]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!(["A", "Unknown"]));
+        assert!(rehydrate(corrupt).is_err());
+
+        // Missing empty rows are filled in
+        let mut truncated = snapshot;
+        truncated["guesses"].as_array_mut().unwrap().truncate(2);
+        let rehydrated = rehydrate(truncated).unwrap();
+        assert_eq!(rehydrated.guesses.len(), DEFAULT_MAX_GUESSES);
+    }
+
+    #[test]
+    fn invalid_share_links_are_ignored() {
+        let words = ["KISSA", "KOIRA"];
+
+        assert!(Sanuli::from_shared_link("KISSA|", word_lists(&words)).is_none());
+        assert!(Sanuli::from_shared_link("|KISSA", word_lists(&words)).is_none());
+        assert!(Sanuli::from_shared_link("KISSA|KOIRAKIS", word_lists(&words)).is_none());
+        assert!(Sanuli::from_shared_link(
+            &format!("KISSA|{}", "KOIRA".repeat(DEFAULT_MAX_GUESSES + 1)),
+            word_lists(&words)
+        )
+        .is_none());
+        assert!(Sanuli::from_shared_link("KISSA|KOIRAKISSA", word_lists(&words)).is_some());
+        assert!(Sanuli::from_shared_link("KISSA|KOIRAKISSA|xx", word_lists(&words)).is_none());
+    }
+
+    #[test]
+    fn crafted_share_links_tell_a_coherent_story() {
+        let words = ["KISSA", "KOIRA", "TALVI"];
+        let open = |payload: &str| Sanuli::from_shared_link(payload, word_lists(&words)).unwrap();
+
+        // Guesses after finding the word are dropped
+        let game = open("KISSA|KOIRAKISSATALVIKOIRA");
+        assert!(game.is_winner());
+        assert_eq!(game.current_guess, 1);
+        assert!(game.guesses[2].is_empty() && game.guesses[3].is_empty());
+
+        let game = open("KISSA|KISSAKISSA");
+        assert!(game.is_winner());
+        assert_eq!(game.current_guess, 0);
+
+        let mut game = open(&format!("KISSA|{}", "KOIRA".repeat(DEFAULT_MAX_GUESSES)));
+        assert!(!game.is_winner());
+        assert_eq!(
+            game.message(),
+            Text::SharedGuessRepeated.get(Language::Finnish)
+        );
+        game.reveal_hidden_tiles();
+        assert!(game
+            .message()
+            .ends_with(Text::SharedGuessRepeated.get(Language::Finnish)));
+
+        let game = open("KISSA|KOIRATALVI");
+        assert!(!game.is_winner());
+        assert_eq!(game.current_guess, 1);
+        assert!(game.message().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "swedish")]
+    fn share_link_keeps_the_word_language() {
+        let mut game = Sanuli::with_word(
+            WordLanguage::Swedish,
+            GameMode::Classic,
+            WordList::Common,
+            "FÅGEL".chars().collect(),
+            DEFAULT_MAX_GUESSES,
+            false,
+            word_lists(&[]),
+        );
+        for character in "FÅGEL".chars() {
+            game.push_character(character);
+        }
+        game.submit_guess();
+
+        let game_str = share::decode_payload(&game.encode_share_payload(None)).unwrap();
+        let shared = Sanuli::from_shared_link(&game_str, word_lists(&[])).unwrap();
+        assert_eq!(shared.word_language(), WordLanguage::Swedish);
+        assert_eq!(shared.word(), game.word());
+    }
+
+    #[test]
+    fn share_link_carries_the_sharer_tag() {
+        let words = ["KISSA", "KOIRA"];
+        let mut game = Sanuli::with_word(
+            WordLanguage::Finnish,
+            GameMode::Classic,
+            WordList::Common,
+            "KISSA".chars().collect(),
+            DEFAULT_MAX_GUESSES,
+            true,
+            word_lists(&words),
+        );
+        type_word(&mut game, &"KOIRA".chars().collect::<Vec<_>>());
+        game.submit_guess();
+
+        let payload = share::decode_payload(&game.progress_payload(Some("MTK")).unwrap()).unwrap();
+        let spectated = Sanuli::from_shared_link(&payload, word_lists(&words)).unwrap();
+        assert_eq!(spectated.title(), "Jaettu sanuli (MTK)");
+
+        type_word(&mut game, &"KISSA".chars().collect::<Vec<_>>());
+        game.submit_guess();
+
+        let payload = share::decode_payload(&game.share_payload(Some("MTK")).unwrap()).unwrap();
+        let shared = Sanuli::from_shared_link(&payload, word_lists(&words)).unwrap();
+        assert_eq!(shared.title(), "Jaettu sanuli (MTK)");
+        assert_eq!(shared.guesses, game.guesses);
+
+        // Tags that don't fit the rules are left out, the game still opens
+        let shared =
+            Sanuli::from_shared_link("KISSA|KOIRAKISSA|fi|M!", word_lists(&words)).unwrap();
+        assert_eq!(shared.title(), "Jaettu sanuli");
     }
 }
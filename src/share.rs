@@ -0,0 +1,278 @@
+// Shared games are encoded the same way as the browser `btoa` would do it: every character
+// of the payload is a single Latin-1 byte, which covers the whole finnish alphabet.
+
+use rand::Rng;
+
+use crate::game::Letter;
+use crate::manager::{Theme, TileState};
+use crate::strings::{self, Language, Text};
+
+// Sharer tags tell the links of a family apart, a few letters is plenty
+const SHARER_TAG_MIN_LEN: usize = 3;
+const SHARER_TAG_MAX_LEN: usize = 4;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn to_base64(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+
+    output
+}
+
+fn from_base64(input: &str) -> Option<Vec<u8>> {
+    let input = input.trim_end_matches('=');
+    let mut output = Vec::with_capacity(input.len() * 3 / 4);
+
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in input.bytes() {
+        let value = ALPHABET.iter().position(|a| *a == c)? as u32;
+        buffer = buffer << 6 | value;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits & 0xff) as u8);
+        }
+    }
+
+    Some(output)
+}
+
+pub fn encode_payload(payload: &str) -> String {
+    let bytes = payload
+        .chars()
+        .map(|c| u8::try_from(c as u32).unwrap_or(b'?'))
+        .collect::<Vec<_>>();
+
+    // Replace +/= at the base64 with URL safe characters
+    to_base64(&bytes)
+        .replace('+', "-")
+        .replace('/', ".")
+        .replace('=', "_")
+}
+
+pub fn decode_payload(encoded: &str) -> Option<String> {
+    // Replace URL safe characters back to +/=
+    let base64 = encoded
+        .replace('-', "+")
+        .replace('.', "/")
+        .replace('_', "=");
+
+    let bytes = from_base64(&base64)?;
+    Some(bytes.into_iter().map(char::from).collect())
+}
+
+// Progress links carry the colors of the guesses, one character for each tile
+pub fn tile_state_code(state: TileState) -> char {
+    match state {
+        TileState::Correct => 'c',
+        TileState::Present => 'p',
+        TileState::Absent => 'a',
+        TileState::Unknown => 'u',
+    }
+}
+
+pub fn tile_state_from_code(code: char) -> Option<TileState> {
+    match code {
+        'c' => Some(TileState::Correct),
+        'p' => Some(TileState::Present),
+        'a' => Some(TileState::Absent),
+        'u' => Some(TileState::Unknown),
+        _ => None,
+    }
+}
+
+/// The tag as it goes into a share link: letters of the game only, in upper case. Anything
+/// too short to tell sharers apart is no tag at all.
+pub fn sanitize_sharer_tag(tag: &str) -> Option<String> {
+    let tag = tag
+        .chars()
+        .flat_map(char::to_uppercase)
+        .filter(|c| Letter::try_from(*c).is_ok())
+        .take(SHARER_TAG_MAX_LEN)
+        .collect::<String>();
+
+    (tag.chars().count() >= SHARER_TAG_MIN_LEN).then_some(tag)
+}
+
+pub fn random_sharer_tag() -> String {
+    let mut rng = rand::thread_rng();
+    (0..SHARER_TAG_MIN_LEN)
+        .map(|_| char::from(rng.gen_range(b'A'..=b'Z')))
+        .collect()
+}
+
+pub fn tile_emoji(state: TileState, theme: Theme) -> &'static str {
+    match state {
+        TileState::Correct => match theme {
+            Theme::Colorblind => "🟧",
+            _ => "🟩",
+        },
+        TileState::Present => match theme {
+            Theme::Colorblind => "🟦",
+            _ => "🟨",
+        },
+        TileState::Absent => "⬛",
+        TileState::Unknown => "⬜",
+    }
+}
+
+/// Counts of the colored tiles of a guess, like "🟩x2 🟨x1", which don't give away their positions.
+pub fn count_emojis(guess: &[(char, TileState)], theme: Theme) -> String {
+    let counts = [TileState::Correct, TileState::Present]
+        .iter()
+        .map(|tile| {
+            let count = guess.iter().filter(|(_, state)| state == tile).count();
+            (tile_emoji(*tile, theme), count)
+        })
+        .filter(|(_, count)| *count > 0)
+        .map(|(emoji, count)| format!("{}x{}", emoji, count))
+        .collect::<Vec<_>>();
+
+    if counts.is_empty() {
+        format!("{}x{}", tile_emoji(TileState::Absent, theme), guess.len())
+    } else {
+        counts.join(" ")
+    }
+}
+
+/// The guess counts of a relay chain in order, like "Sanuliketju: 3 sanaa — 4,3,X".
+pub fn relay_chain(language: Language, chain: &[Option<usize>]) -> String {
+    let words = strings::word_count(language, chain.len());
+    let guesses = chain
+        .iter()
+        .map(|guesses| guesses.map_or("X".to_owned(), |guesses| guesses.to_string()))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!("{}: {} — {}", Text::ModeRelay.get(language), words, guesses)
+}
+
+/// Spells out the colors of each guessed row for screen readers, one line per row.
+pub fn describe_rows(language: Language, guesses: &[Vec<(char, TileState)>]) -> String {
+    guesses
+        .iter()
+        .filter(|guess| !guess.is_empty())
+        .enumerate()
+        .map(|(index, guess)| {
+            let count = |tile: TileState| guess.iter().filter(|(_, state)| *state == tile).count();
+            let correct = count(TileState::Correct);
+            let present = count(TileState::Present);
+
+            let description = if correct == guess.len() {
+                Text::AllCorrect.get(language).to_owned()
+            } else if correct == 0 && present == 0 {
+                Text::NoHits.get(language).to_owned()
+            } else {
+                strings::row_hits(language, correct, present)
+            };
+
+            strings::row_description(language, index + 1, &description)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_browser_btoa() {
+        // btoa("KISSA|KOIRA")
+        assert_eq!(encode_payload("KISSA|KOIRA"), "S0lTU0F8S09JUkE_");
+        // btoa("ÄÖ") encodes the characters as single Latin-1 bytes
+        assert_eq!(encode_payload("ÄÖ"), "xNY_");
+    }
+
+    #[test]
+    fn rows_are_described() {
+        let row = |states: &[TileState]| states.iter().map(|state| ('A', *state)).collect();
+        let guesses = vec![
+            row(&[
+                TileState::Correct,
+                TileState::Present,
+                TileState::Absent,
+                TileState::Correct,
+                TileState::Absent,
+            ]),
+            row(&[TileState::Absent; 5]),
+            row(&[TileState::Correct; 5]),
+            Vec::new(),
+        ];
+
+        assert_eq!(
+            describe_rows(Language::Finnish, &guesses),
+            "Rivi 1: 2 oikein, 1 väärässä paikassa\nRivi 2: ei osumia\nRivi 3: kaikki oikein"
+        );
+    }
+
+    #[test]
+    fn counts_hide_the_positions() {
+        let guess = [
+            ('K', TileState::Present),
+            ('O', TileState::Absent),
+            ('I', TileState::Correct),
+            ('R', TileState::Absent),
+            ('A', TileState::Correct),
+        ];
+        assert_eq!(count_emojis(&guess, Theme::Dark), "🟩x2 🟨x1");
+        assert_eq!(count_emojis(&guess, Theme::Colorblind), "🟧x2 🟦x1");
+        assert_eq!(count_emojis(&guess[1..2], Theme::Dark), "⬛x1");
+    }
+
+    #[test]
+    fn relay_chain_lists_the_guesses() {
+        assert_eq!(
+            relay_chain(Language::Finnish, &[Some(4), Some(3), Some(5), None]),
+            "Sanuliketju: 4 sanaa — 4,3,5,X"
+        );
+        assert_eq!(
+            relay_chain(Language::Finnish, &[None]),
+            "Sanuliketju: 1 sana — X"
+        );
+        assert_eq!(
+            relay_chain(Language::English, &[Some(2), None]),
+            "Relay: 2 words — 2,X"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for payload in ["", "A", "AB", "ABC", "KÄÄPÄ|KÖÖRI", "SANULI|SANULISANULI"] {
+            assert_eq!(
+                decode_payload(&encode_payload(payload)).as_deref(),
+                Some(payload)
+            );
+        }
+    }
+
+    #[test]
+    fn sharer_tags_are_sanitized() {
+        assert_eq!(sanitize_sharer_tag("mtk").as_deref(), Some("MTK"));
+        assert_eq!(sanitize_sharer_tag("ä-ö k!").as_deref(), Some("ÄÖK"));
+        assert_eq!(sanitize_sharer_tag("ABCDEF").as_deref(), Some("ABCD"));
+        assert_eq!(sanitize_sharer_tag("M|K"), None);
+        assert_eq!(sanitize_sharer_tag(""), None);
+        assert!(sanitize_sharer_tag(&random_sharer_tag()).is_some());
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::game::{self, KnownCounts, KnownStates};
+use crate::manager::{CharacterCount, CharacterState, TileState};
+
+// Candidate elimination from board feedback, using the same representation as the game
+
+pub fn parse_feedback(guess: &str, feedback: &str) -> Option<Vec<(char, TileState)>> {
+    let guess = guess.to_uppercase().chars().collect::<Vec<_>>();
+    let feedback = feedback.to_lowercase().chars().collect::<Vec<_>>();
+
+    if guess.len() != feedback.len() {
+        return None;
+    }
+
+    guess
+        .into_iter()
+        .zip(feedback)
+        .map(|(character, tile)| {
+            let state = match tile {
+                'c' => TileState::Correct,
+                'p' => TileState::Present,
+                'a' => TileState::Absent,
+                _ => return None,
+            };
+            Some((character, state))
+        })
+        .collect()
+}
+
+/// Records what a colored guess row reveals about the hidden word. Like `game::known_count`,
+/// an absent copy of a character means the highlighted copies were all there is.
+pub fn learn(states: &mut KnownStates, counts: &mut KnownCounts, guess: &[(char, TileState)]) {
+    for (index, (character, tile_state)) in guess.iter().enumerate() {
+        let state = if *tile_state == TileState::Correct {
+            CharacterState::Correct
+        } else {
+            CharacterState::Absent
+        };
+        states.insert(*character, index, state);
+    }
+
+    let characters = guess.iter().map(|(c, _)| *c).collect::<HashSet<_>>();
+    for character in characters {
+        let found = guess
+            .iter()
+            .filter(|(c, state)| *c == character && *state != TileState::Absent)
+            .count();
+        let is_exact = guess
+            .iter()
+            .any(|(c, state)| *c == character && *state == TileState::Absent);
+
+        let count = counts
+            .entry(character)
+            .or_insert(CharacterCount::AtLeast(0));
+
+        // Exact count should never change
+        if let CharacterCount::AtLeast(known) = count {
+            if is_exact {
+                *count = CharacterCount::Exactly(found);
+            } else if found > *known {
+                *count = CharacterCount::AtLeast(found);
+            }
+        }
+    }
+}
+
+pub fn is_candidate(word: &[char], states: &KnownStates, counts: &KnownCounts) -> bool {
+    fits(word, states.iter(), counts)
+}
+
+// The counts are fewer and cheaper to check, so a word is ruled out by them first
+fn fits(
+    word: &[char],
+    mut positions: impl Iterator<Item = (char, usize, CharacterState)>,
+    counts: &KnownCounts,
+) -> bool {
+    counts.iter().all(|(character, count)| {
+        let count_in_word = word.iter().filter(|c| *c == character).count();
+        match count {
+            CharacterCount::AtLeast(count) => count_in_word >= *count,
+            CharacterCount::Exactly(count) => count_in_word == *count,
+        }
+    }) && positions.all(|(character, index, state)| match state {
+        CharacterState::Correct => word.get(index) == Some(&character),
+        CharacterState::Absent => word.get(index) != Some(&character),
+        CharacterState::Unknown => true,
+    })
+}
+
+/// Drops the words that no longer fit, so each guess only narrows the survivors of the last.
+/// The known positions are looked up once instead of for every word.
+pub fn retain_candidates(words: &mut Vec<Vec<char>>, states: &KnownStates, counts: &KnownCounts) {
+    let positions = states.iter().collect::<Vec<_>>();
+    words.retain(|word| fits(word, positions.iter().cloned(), counts));
+}
+
+pub fn candidates<'a>(
+    words: impl IntoIterator<Item = &'a Vec<char>>,
+    states: &KnownStates,
+    counts: &KnownCounts,
+) -> Vec<Vec<char>> {
+    let mut candidates = words
+        .into_iter()
+        .filter(|word| is_candidate(word, states, counts))
+        .cloned()
+        .collect::<Vec<_>>();
+    candidates.sort();
+    candidates
+}
+
+/// The tile colors the game would show for `guess` as the first guess against `word`.
+pub fn feedback(guess: &[char], word: &[char]) -> Vec<TileState> {
+    let mut states = vec![KnownStates::default()];
+    let mut counts = vec![HashMap::new()];
+    let mut guess = guess
+        .iter()
+        .map(|c| (*c, TileState::Unknown))
+        .collect::<Vec<_>>();
+
+    game::update_known_information(&mut states, &mut counts, &mut guess, 0, word);
+
+    guess.into_iter().map(|(_, state)| state).collect()
+}
+
+/// Scores each candidate by how many candidates share its distinct letters.
+pub fn rank_by_letter_frequency(candidates: &[Vec<char>]) -> Vec<(Vec<char>, f64)> {
+    let mut frequencies: HashMap<char, usize> = HashMap::new();
+    for word in candidates {
+        for character in word.iter().collect::<HashSet<_>>() {
+            *frequencies.entry(*character).or_default() += 1;
+        }
+    }
+
+    let scored = candidates
+        .iter()
+        .map(|word| {
+            let score = word
+                .iter()
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .map(|c| frequencies[c])
+                .sum::<usize>();
+            (word.clone(), score as f64)
+        })
+        .collect();
+
+    sorted_by_score(scored)
+}
+
+/// Scores each candidate by the expected information, in bits, its feedback gives about
+/// the remaining candidates.
+pub fn rank_by_expected_information(candidates: &[Vec<char>]) -> Vec<(Vec<char>, f64)> {
+    let total = candidates.len() as f64;
+
+    let scored = candidates
+        .iter()
+        .map(|guess| {
+            let mut patterns: HashMap<Vec<TileState>, usize> = HashMap::new();
+            for word in candidates {
+                *patterns.entry(feedback(guess, word)).or_default() += 1;
+            }
+
+            let entropy = patterns
+                .values()
+                .map(|count| {
+                    let p = *count as f64 / total;
+                    -p * p.log2()
+                })
+                .sum::<f64>();
+            (guess.clone(), entropy)
+        })
+        .collect();
+
+    sorted_by_score(scored)
+}
+
+fn sorted_by_score(mut scored: Vec<(Vec<char>, f64)>) -> Vec<(Vec<char>, f64)> {
+    scored.sort_by(|(a_word, a), (b_word, b)| b.total_cmp(a).then_with(|| a_word.cmp(b_word)));
+    scored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(word: &str) -> Vec<char> {
+        word.chars().collect()
+    }
+
+    fn remaining(words: &[&str], rows: &[(&str, &str)]) -> Vec<String> {
+        let words = words.iter().map(|w| chars(w)).collect::<Vec<_>>();
+        let mut states = KnownStates::default();
+        let mut counts = HashMap::new();
+
+        for (guess, tiles) in rows {
+            learn(
+                &mut states,
+                &mut counts,
+                &parse_feedback(guess, tiles).unwrap(),
+            );
+        }
+
+        candidates(&words, &states, &counts)
+            .into_iter()
+            .map(|w| w.into_iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn parse_feedback_rejects_bad_input() {
+        assert!(parse_feedback("KOIRA", "cppa").is_none());
+        assert!(parse_feedback("KOIRA", "cppax").is_none());
+        assert!(parse_feedback("koira", "CPPAA").is_some());
+    }
+
+    #[test]
+    fn absent_duplicate_reveals_exact_count() {
+        let words = ["KISSA", "ISSSA", "SIKSI", "KALSA"];
+
+        // SASSA against KISSA: the first S is absent, so ISSSA has one S too many
+        assert_eq!(
+            remaining(&words, &[("SASSA", "aaccc")]),
+            vec!["KISSA".to_owned()]
+        );
+        assert_eq!(
+            remaining(&words, &[("SIKSI", "pcpca")]),
+            vec!["KISSA".to_owned()]
+        );
+    }
+
+    #[test]
+    fn feedback_matches_game_coloring() {
+        let states = feedback(&chars("SIKSI"), &chars("KISSA"));
+        assert!(
+            states
+                == vec![
+                    TileState::Present,
+                    TileState::Correct,
+                    TileState::Present,
+                    TileState::Correct,
+                    TileState::Absent,
+                ]
+        );
+    }
+
+    #[test]
+    fn hidden_word_survives_its_own_feedback() {
+        let words = ["KISSA", "SIKSI", "KOIRA", "KARIT", "AAAAA"]
+            .iter()
+            .map(|w| chars(w))
+            .collect::<Vec<_>>();
+
+        for word in &words {
+            for guess in &words {
+                let row = guess
+                    .iter()
+                    .copied()
+                    .zip(feedback(guess, word))
+                    .collect::<Vec<_>>();
+                let mut states = KnownStates::default();
+                let mut counts = HashMap::new();
+                learn(&mut states, &mut counts, &row);
+
+                assert!(is_candidate(word, &states, &counts));
+            }
+        }
+    }
+
+    #[test]
+    fn retained_candidates_match_a_fresh_filter() {
+        let words = ["ISSSA", "KALSA", "KISSA", "KOIRA", "SIKSI"]
+            .iter()
+            .map(|w| chars(w))
+            .collect::<Vec<_>>();
+        let mut states = KnownStates::default();
+        let mut counts = HashMap::new();
+        let mut survivors = words.clone();
+
+        for (guess, tiles) in [("TUULI", "aaaap"), ("SASSA", "aaccc")] {
+            learn(
+                &mut states,
+                &mut counts,
+                &parse_feedback(guess, tiles).unwrap(),
+            );
+            retain_candidates(&mut survivors, &states, &counts);
+            assert!(survivors == candidates(&words, &states, &counts));
+        }
+        assert!(survivors == vec![chars("KISSA")]);
+    }
+}
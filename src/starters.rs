@@ -0,0 +1,18 @@
+// Good first guesses for the beginner help, picked by letter frequency over the common lists.
+// Generated by `cargo run --bin generate-starter-words`, run it again when the lists change.
+
+use crate::manager::WordLanguage;
+
+pub const STARTER_WORDS: &[(WordLanguage, usize, &[&str])] = &[
+    (WordLanguage::Finnish, 5, &["KOIRA", "TALVI"]),
+    (WordLanguage::Finnish, 6, &["KOIRAT"]),
+    (WordLanguage::Swedish, 5, &["BÅTAR", "FÅGEL", "SKOLA"]),
+    (WordLanguage::Swedish, 6, &["FÅGLAR", "HÄSTAR"]),
+];
+
+pub fn starter_words(word_language: WordLanguage, word_length: usize) -> &'static [&'static str] {
+    STARTER_WORDS
+        .iter()
+        .find(|(language, length, _)| *language == word_language && *length == word_length)
+        .map_or(&[], |(_, _, words)| words)
+}
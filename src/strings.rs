@@ -0,0 +1,628 @@
+// Texts shown to the player, in each of the languages of the user interface.
+// The words to guess are always Finnish, only the presentation is translated.
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::Contradiction;
+use crate::manager::{GameMode, WordList};
+
+pub const SUCCESS_EMOJIS: [&str; 9] = ["🥳", "🤩", "🤗", "🎉", "😊", "😺", "😎", "👏", ":3"];
+pub const ANIMAL_EMOJIS: [&str; 6] = ["🐱", "🐶", "🦊", "🐻", "🐼", "🦉"];
+pub const HEART_EMOJIS: [&str; 6] = ["❤️", "🧡", "💛", "💚", "💙", "💜"];
+
+#[derive(PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    Finnish,
+    English,
+}
+
+impl Language {
+    /// The language for a tag like `navigator.language` gives, English unless it's Finnish.
+    pub fn from_tag(tag: &str) -> Language {
+        let primary = tag.split(['-', '_']).next().unwrap_or_default();
+        if primary.eq_ignore_ascii_case("fi") {
+            Language::Finnish
+        } else {
+            Language::English
+        }
+    }
+}
+
+macro_rules! texts {
+    ( $( $name:ident => $finnish:expr, $english:expr; )* ) => {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        pub enum Text {
+            $( $name, )*
+        }
+
+        impl Text {
+            pub const ALL: &'static [Text] = &[ $( Text::$name, )* ];
+
+            pub fn get(self, language: Language) -> &'static str {
+                match self {
+                    $( Text::$name => match language {
+                        Language::Finnish => $finnish,
+                        Language::English => $english,
+                    }, )*
+                }
+            }
+        }
+    };
+}
+
+texts! {
+    // Messages of the games
+    TooFewLetters => "Liian vähän kirjaimia!", "Not enough letters!";
+    NotInWordList => "Ei sanulistalla.", "Not in the word list.";
+    HotseatEntry =>
+        "Kirjoita salainen sana ja anna laite arvaajalle",
+        "Type in the secret word and hand the device to the guesser";
+    GameEnded => "Peli päättyi!", "Game over!";
+    GameReset => "Peli nollattu, arvaa sanuli!", "Game reset, guess the word!";
+    DailyAlreadyPlayed => "Päivän sanuli on jo pelattu", "Today's word has already been played";
+    WordIsFixed => "Tämän pelin sana on jo valittu", "The word of this game is already chosen";
+    NotAllWordsFound => "Kaikkia sanuleita ei löytynyt.", "Not all of the words were found.";
+    GameNotRestored =>
+        "Keskeneräistä peliä ei voitu palauttaa, putki jatkuu.",
+        "The unfinished game couldn't be restored, the streak continues.";
+    Win => "Löysit sanan!", "You found the word!";
+    WinDaily => "Löysit päivän sanulin!", "You found today's word!";
+    WinQuadruple => "Löysit sanulit!", "You found the words!";
+    Late => "(myöhässä)", "(late)";
+    Streak => "Putki", "Streak";
+
+    // Names of the game modes and word lists
+    ModeClassic => "Peruspeli", "Classic";
+    ModeRelay => "Sanuliketju", "Relay";
+    ModeDaily => "Päivän sanuli", "Daily word";
+    ModeShared => "Jaettu sanuli", "Shared word";
+    ModeQuadruple => "Neluli", "Neluli";
+    ModeHotseat => "Kaksinpeli", "Two players";
+    ModePractice => "Harjoittelu", "Practice";
+    ListFull => "Vaikea", "Hard";
+    ListCommon => "Tavallinen", "Common";
+    ListEasy => "Helppo", "Easy";
+    ListProfanities => "Rumat", "Profanities";
+    ListDaily => "Päivän sanulit", "Daily words";
+    ListAnimals => "Eläimet", "Animals";
+    ListFood => "Ruoka", "Food";
+    ListMixed => "Sekalista", "Mixed";
+
+    // Sharing the result
+    AllCorrect => "kaikki oikein", "all correct";
+    NoHits => "ei osumia", "no hits";
+
+    // Keyboard and the links under the message
+    GuessButton => "ARVAA", "GUESS";
+    BackButton => "TAKAISIN", "BACK";
+    NewButton => "UUSI?", "NEW?";
+    TryToSolve => "Kokeile ratkaista", "Try to solve it";
+    CopyResult => "Kopioi tulos", "Copy result";
+    CopyCounts => "Sumea jako", "Fuzzy share";
+    CopyLink => "Kopioi linkki", "Copy link";
+    CopyChain => "Kopioi ketju", "Copy chain";
+    Copied => "Kopioitu!", "Copied!";
+    ShareProgress => "Jaa tilanne", "Share progress";
+    GameInProgress => "Peli kesken", "Game in progress";
+    SharedGuessRepeated =>
+        "Jaetussa pelissä on sama arvaus monta kertaa peräkkäin",
+        "The shared game has the same guess several times in a row";
+    Reveal => "Paljasta", "Reveal";
+    Dictionary => "Sanakirja", "Dictionary";
+    HintsUsed => "vihjeet käytössä", "hints used";
+    Replay => "Toisto", "Replay";
+    AnalysisTitle => "Analyysi", "Analysis";
+    AnalysisGuess => "Arvaus", "Guess";
+    AnalysisBefore => "Sanoja ennen", "Words before";
+    AnalysisAfter => "Sanoja jälkeen", "Words after";
+    AnalysisWeak => "karsi vähän", "ruled out little";
+    PlayYourself => "Pelaa itse", "Play yourself";
+    SuggestAddition => "Ehdota lisäystä?", "Suggest adding it?";
+    SuggestRemoval => "Ehdota poistoa?", "Suggest removing it?";
+    WordsMissed => "Löytämättä jäi: ", "Not found: ";
+    WordsFound => "Löysit: ", "Found: ";
+    AreYouSure => "Oletko varma?", "Are you sure?";
+
+    // Banners
+    NewDailyAvailable => "Uusi päivän sanuli saatavilla", "A new daily word is available";
+    Play => "Pelaa", "Play";
+    DailyUnfinished =>
+        "Päivän sanuli on kesken — vaihdetaanko silti?",
+        "Today's word is unfinished — switch anyway?";
+    Switch => "Vaihda", "Switch";
+    StorageFull =>
+        "Tallennustila on täynnä — edistymistäsi ei tallenneta",
+        "Storage is full — your progress isn't being saved";
+    CleanupStorage => "Siivoa vanhat pelit", "Clean up old games";
+    Offline =>
+        "Ei verkkoyhteyttä — peli toimii, mutta jakolinkit ja sanakirja eivät",
+        "Offline — the game works, but share links and the dictionary don't";
+    StorageFailed =>
+        "Tallennus epäonnistui — edistymistäsi ei tallenneta",
+        "Saving failed — your progress isn't being saved";
+    RetryStorage => "Yritä uudelleen", "Try again";
+
+    // Settings
+    Settings => "Asetukset", "Settings";
+    Statistics => "Tilastot", "Statistics";
+    Yes => "Kyllä", "Yes";
+    No => "Ei", "No";
+    WordLengthLabel => "Sanulien pituus:", "Word length:";
+    WordListLabel => "Sanulista:", "Word list:";
+    ProfanitiesLabel => "Rumat sanulit:", "Profanities:";
+    WeightedSelectionLabel => "Painotettu arvonta:", "Weighted draw:";
+    GameModeLabel => "Pelimuoto:", "Game mode:";
+    NewGame => "Uusi peli", "New game";
+    YesterdaysWord => "Eilinen sanuli", "Yesterday's word";
+    PracticeLetterLabel => "Sanulissa on kirjain:", "The word has the letter:";
+    AnyLetter => "Mikä tahansa", "Any";
+    SuspendedGamesLabel => "Kesken olevat pelit:", "Unfinished games:";
+    Finished => "Päättynyt", "Finished";
+    Continue => "Jatka", "Continue";
+    ThemeLabel => "Teema:", "Theme:";
+    ThemeDark => "Oletus", "Default";
+    ThemeColorblind => "Värisokeille", "Colorblind";
+    UnusedLettersLabel => "Käyttämättömät kirjaimet:", "Unused letters:";
+    Hide => "Piilota", "Hide";
+    Show => "Näytä", "Show";
+    NewInformationLabel => "Uusi tieto:", "New information:";
+    NoHighlight => "Ei korostusta", "No highlight";
+    Highlight => "Korosta", "Highlight";
+    TypingHintsLabel => "Vihjevärit kirjoittaessa:", "Hint colors while typing:";
+    ContradictionWarningLabel =>
+        "Varoita tietoa hukkaavista arvauksista:",
+        "Warn about guesses that waste information:";
+    AnalysisLabel =>
+        "Analyysi, jäljellä olevien sanojen määrä:",
+        "Analysis, the number of words left:";
+    BeginnerHelpLabel => "Aloittelijan apu, ehdotuksia ensimmäiseksi arvaukseksi:", "Beginner help, suggested first guesses:";
+    CelebrationLabel => "Onnittelut:", "Celebration:";
+    NoCelebration => "Ei mitään", "None";
+    ShareSolveTimeLabel => "Ratkaisuaika jaettavaan tulokseen:", "Solve time in the shared result:";
+    AccessibleShareLabel => "Saavutettava jako:", "Accessible share:";
+    SharerTagLabel => "Tunnuksesi jaetuissa linkeissä:", "Your tag in shared links:";
+    AutoSubmitLabel => "Arvaa heti kun rivi on täynnä:", "Guess as soon as the row is full:";
+    HideFinishedWordLabel => "Piilota sana pelin päätyttyä:", "Hide the word when the game ends:";
+    LanguageLabel => "Kieli:", "Language:";
+    WordLanguageLabel => "Sanulien kieli:", "Word language:";
+    KeyboardLayoutLabel => "Näppäimistö:", "Keyboard:";
+    CompactKeyboard => "Kaksi riviä", "Two rows";
+    KeyColorsLabel => "Näppäinten värit nelulissa:", "Key colors with four boards:";
+    KeyColorsBest => "Paras tieto", "Best state";
+    KeyColorsDetailed => "Laudoittain", "Per board";
+    AppLabel => "Sovellus:", "App:";
+    InstallApp => "Asenna sovellus", "Install the app";
+    StoredDataLabel => "Tallennetut tiedot:", "Stored data:";
+    ClearAllData => "Poista kaikki tiedot", "Delete all data";
+    DrawnWordsLabel => "Arvotut sanat (debug):", "Drawn words (debug):";
+    CopyDrawnWords => "Kopioi vikailmoitukseen", "Copy for a bug report";
+    ResetSettings => "Palauta oletusasetukset", "Restore default settings";
+    ChangedFromDefault => "Muutettu oletuksesta", "Changed from the default";
+
+    // Statistics
+    OwnStatisticsLabel => "Omat tilastosi:", "Your statistics:";
+    LongestStreak => "Pisin putki", "Longest streak";
+    WordsPlayed => "Pelatut sanulit", "Words played";
+    WordsSolved => "Ratkaistut sanulit", "Words solved";
+    DailyWordsLabel => "Päivän sanulit:", "Daily words:";
+    Freezes => "Jäädytykset", "Freezes";
+    FrozenDaysLabel => "Jäädytetyt päivät:", "Frozen days:";
+    BrokenStreaksLabel => "Katkenneet putket:", "Broken streaks:";
+    MilestonesLabel => "Putkimerkit:", "Streak badges:";
+    GuessDistributionLabel => "Ratkaisut arvauksittain:", "Solves by guess:";
+
+    // Help
+    HelpDaily =>
+        "Päivän sanulit tulevat omalta listaltaan, joka on jotain tavallisen ja vaikean listan väliltä. Sanuli on aina sama kaikille pelaajille tiettynä päivänä.",
+        "The daily words come from a list of their own, somewhere between the common and the hard list. The word of a day is the same for every player.";
+    HelpRelay =>
+        "Sanuliketjussa jos arvaat sanulin, on se suoraan ensimmäinen arvaus seuraavaan peliin. Näin joudut sopeutumaan vaihtuviin alkuarvauksiin, ja peli on hieman vaikeampi.",
+        "In a relay the word you found is the first guess of the next game. You'll have to adapt to a different starting guess every time, which makes the game a bit harder.";
+    HelpQuadruple =>
+        "Nelulissa ratkaiset samalla kertaa neljää eri sanulia samoilla arvauksilla. Tavoite on saada kaikki neljä sanulia ratkaistua yhdeksällä arvauksella.",
+        "In Neluli you solve four words at once with the same guesses. The goal is to solve all four words in nine guesses.";
+    HelpHotseat =>
+        "Kaksinpelissä toinen pelaaja kirjoittaa salaisen sanulin ja toinen yrittää arvata sen. Sanulin pitää löytyä vaikealta listalta.",
+        "In a two player game one player types in a secret word and the other one tries to guess it. The word has to be on the hard list.";
+    HelpPractice =>
+        "Harjoittelussa voit vaatia, että arvattavassa sanulissa on tietty kirjain. Harjoittelu ei vaikuta putkiin eikä tilastoihin.",
+        "In practice you can require the word to have a certain letter. Practice doesn't count towards streaks or statistics.";
+    HelpShared =>
+        "Jaetusta linkistä näet kaverisi arvaukset ilman kirjaimia. Voit paljastaa ne tai yrittää ratkaista saman sanulin itse. Jaetut pelit eivät vaikuta tilastoihisi.",
+        "A shared link shows the guesses of your friend without the letters. You can reveal them or try to solve the same word yourself. Shared games don't count towards your statistics.";
+    HelpGuessStart => "Arvaa kätketty ", "Guess the hidden ";
+    HelpGuessEnd => " kuudella yrityksellä.", " in six tries. The words are Finnish.";
+    HelpColors =>
+        "Jokaisen yrityksen jälkeen arvatut kirjaimet vaihtavat väriään.",
+        "After each guess the color of the letters shows how close the guess was.";
+    Yellow => "Keltainen", "Yellow";
+    Blue => "Sininen", "Blue";
+    Green => "Vihreä", "Green";
+    Orange => "Oranssi", "Orange";
+    Gray => "Harmaa", "Gray";
+    HelpPresent =>
+        ": kirjain löytyy kätketystä sanasta, mutta on arvauksessa väärällä paikalla.",
+        ": the letter is in the hidden word, but in a different spot.";
+    HelpCorrect => ": kirjain on arvauksessa oikealla paikalla.", ": the letter is in the right spot.";
+    HelpAbsent => ": kirjain ei löydy sanasta.", ": the letter isn't in the word.";
+    HelpExampleStart =>
+        "Kokeile: paina nappia ja katso miten arvaukset etenevät.",
+        "Try it: press the button to see how the guesses play out.";
+    HelpExampleFirst =>
+        "I ja A löytyvät sanasta, mutta eri paikoilta. K, O ja R eivät ole sanassa.",
+        "I and A are in the word, but in other spots. K, O and R aren't in the word.";
+    HelpExampleSecond =>
+        "A on nyt oikealla paikalla. Sanassa on vain yksi A, joten toinen jää harmaaksi.",
+        "A is now in the right spot. The word has only one A, so the other one stays gray.";
+    HelpExampleSolved =>
+        "Kaikki kirjaimet oikein, sanuli löytyi!",
+        "All the letters are right, the word was found!";
+    NextGuess => "Seuraava arvaus", "Next guess";
+    StartOver => "Alusta", "Start over";
+    HelpWordListsStart =>
+        "Arvattaviin sanoihin käytetyn sanulistan vaikeusasteen voi valita asetuksista. Sanulistojen pohjana on käytetty Kotimaisten kielten keskuksen (Kotus) julkaiseman ",
+        "The difficulty of the word list the hidden words are drawn from can be chosen in the settings. The word lists are based on the ";
+    HelpWordListsLicense => "\"CC Nimeä 3.0 Muokkaamaton\"", "\"CC Attribution 3.0 Unported\"";
+    HelpWordListsEnd =>
+        "-lisensoidun nykysuomen sanalistan sanoja.",
+        " licensed list of contemporary Finnish words by the Institute for the Languages of Finland (Kotus).";
+    HelpCommonList =>
+        " lista sisältää täydestä listasta poimitut yleisimmät sanat ilman harvinaisempia laina- ja murresanoja tai muita erikoisuuksia.",
+        " list has the most common words of the full list, without rarer loanwords, dialect words or other oddities.";
+    HelpEasyList =>
+        " lista on tavallisesta vielä hieman helpotettu versio, jossa jäljellä ovat vain yleiset arkikielen sanat ilman vanhahtavia sanoja, puhekieltä tai rumia sanuleja. Näin lista sopii kaikenikäisille. \"Helppo\" kuusikirjaimisten sanulien lista on kuitenkin vielä kesken.",
+        " list is an even easier version of the common list with only everyday words, without old-fashioned words, slang or profanities, so it suits players of all ages. The easy list of six letter words is still a work in progress though.";
+    HelpFullList =>
+        " lista on täysi lista pelin hyväksymiä sanoja. Tälle listalle on myös lisätty jonkin verran käyttäjien uusia ehdotuksia, puhekielisyyksiä, murresanoja sekä muita erikoisuuksia, eikä poistoja ole tehty kuin vain jos sanulit eivät selvästi ole oikeita sanoja.",
+        " list is the full list of the words the game accepts. It also has some words suggested by players, slang, dialect words and other oddities, and words have only been removed if they clearly aren't real words.";
+    HelpWordForms =>
+        "Sanulit ovat yleensä perusmuodossa, mutta eivät välttämättä täysin pelkkää kirjakieltä. Yhdyssanojakin on seassa.",
+        "The words are usually in their base form, but not necessarily all standard language. There are compound words in there too.";
+    HelpSuggestionsStart =>
+        "Sanulistoja muokkailen aina välillä käyttäjien ehdotusten perusteella, ja voit jättää omat ehdotuksesi sanuleihin ",
+        "I edit the word lists every now and then based on the suggestions of players, and you can leave your own suggestions ";
+    HelpSuggestionsLink => "täällä", "here";
+    HelpSuggestionsEnd =>
+        ". Kiitos kaikille ehdotuksia jättäneille ja sanulistojen kasaamisessa auttaneille henkilöille!",
+        ". Thanks to everyone who has left suggestions or helped put the word lists together!";
+}
+
+pub fn game_mode_name(language: Language, game_mode: GameMode) -> String {
+    let name = match game_mode {
+        GameMode::Classic => Text::ModeClassic,
+        GameMode::Relay => Text::ModeRelay,
+        GameMode::DailyWord(_) => Text::ModeDaily,
+        GameMode::Shared => Text::ModeShared,
+        GameMode::Quadruple => Text::ModeQuadruple,
+        GameMode::Hotseat => Text::ModeHotseat,
+        GameMode::Practice(_) => Text::ModePractice,
+    }
+    .get(language);
+
+    match game_mode {
+        GameMode::Practice(Some(letter)) => format!("{} · {}", name, letter),
+        _ => name.to_owned(),
+    }
+}
+
+pub fn word_list_name(language: Language, word_list: WordList) -> &'static str {
+    match word_list {
+        WordList::Full => Text::ListFull,
+        WordList::Common => Text::ListCommon,
+        WordList::Easy => Text::ListEasy,
+        WordList::Profanities => Text::ListProfanities,
+        WordList::Daily => Text::ListDaily,
+        WordList::Animals => Text::ListAnimals,
+        WordList::Food => Text::ListFood,
+        WordList::Mixed => Text::ListMixed,
+    }
+    .get(language)
+}
+
+/// The message of a won game, followed by the celebration emoji if there is one.
+pub fn msg_win(language: Language, game_mode: GameMode, celebration: &str) -> String {
+    let message = match game_mode {
+        GameMode::DailyWord(_) => Text::WinDaily,
+        GameMode::Quadruple => Text::WinQuadruple,
+        _ => Text::Win,
+    };
+    format!("{} {}", message.get(language), celebration)
+        .trim_end()
+        .to_owned()
+}
+
+pub fn msg_streak_milestone(language: Language, streak: usize) -> String {
+    format!("{} {}! 🔥", Text::Streak.get(language), streak)
+}
+
+pub fn msg_solve_time(language: Language, message: &str, solve_time: &str) -> String {
+    match language {
+        Language::Finnish => format!("{} Ratkaistu {}", message, solve_time),
+        Language::English => format!("{} Solved in {}", message, solve_time),
+    }
+}
+
+pub fn msg_board_progress(
+    language: Language,
+    solved: usize,
+    boards: usize,
+    guesses_left: usize,
+) -> String {
+    match language {
+        Language::Finnish => format!(
+            "Ratkaistu {}/{} · Arvauksia jäljellä {}",
+            solved, boards, guesses_left
+        ),
+        Language::English => format!(
+            "Solved {}/{} · Guesses left {}",
+            solved, boards, guesses_left
+        ),
+    }
+}
+
+pub fn msg_contradiction(language: Language, contradiction: Contradiction) -> String {
+    let reason = match (language, contradiction) {
+        (Language::Finnish, Contradiction::AbsentLetter(character)) => {
+            format!("Kirjainta {} ei ole sanassa.", character)
+        }
+        (Language::Finnish, Contradiction::MissingCorrect(character, index)) => {
+            format!("Kirjain {} kuuluu kohtaan {}.", character, index + 1)
+        }
+        (Language::English, Contradiction::AbsentLetter(character)) => {
+            format!("The letter {} isn't in the word.", character)
+        }
+        (Language::English, Contradiction::MissingCorrect(character, index)) => {
+            format!(
+                "The letter {} belongs in position {}.",
+                character,
+                index + 1
+            )
+        }
+    };
+    match language {
+        Language::Finnish => format!(
+            "{} Vahvista painamalla {} uudelleen.",
+            reason,
+            Text::GuessButton.get(language)
+        ),
+        Language::English => format!(
+            "{} Press {} again to confirm.",
+            reason,
+            Text::GuessButton.get(language)
+        ),
+    }
+}
+
+pub fn msg_broken_streak(language: Language, streak: usize) -> String {
+    match language {
+        Language::Finnish => format!("Putki {} katkesi sanaan", streak),
+        Language::English => format!("Streak of {} ended on", streak),
+    }
+}
+
+pub fn msg_remaining_candidates(language: Language, count: usize) -> String {
+    match language {
+        Language::Finnish => format!("Mahdollisia sanoja {}", format_count(language, count)),
+        Language::English => format!("Possible words {}", format_count(language, count)),
+    }
+}
+
+pub fn msg_hardest_board(language: Language, board: usize) -> String {
+    match language {
+        Language::Finnish => format!("Vaikein sanuli oli laudalla {}.", board),
+        Language::English => format!("The hardest word was on board {}.", board),
+    }
+}
+
+pub fn msg_word_was(language: Language, word: &[char]) -> String {
+    let word = word.iter().collect::<String>();
+    match language {
+        Language::Finnish => format!("Sana oli \"{}\"", word),
+        Language::English => format!("The word was \"{}\"", word),
+    }
+}
+
+pub fn msg_daily_percentile(language: Language, percentile: usize) -> String {
+    match language {
+        Language::Finnish => format!("Parempi kuin {} % omista päivän sanuleistasi", percentile),
+        Language::English => format!("Better than {}% of your daily words", percentile),
+    }
+}
+
+/// Which list the word came from, a word drawn from the full list is tagged by the narrowest list it's on.
+pub fn msg_word_source(language: Language, word_list: WordList) -> String {
+    match (language, word_list) {
+        (Language::Finnish, WordList::Full) => "vain vaikealla listalla".to_owned(),
+        (Language::English, WordList::Full) => "only on the hard list".to_owned(),
+        (Language::Finnish, WordList::Daily) => "sana on päivän sanuleista".to_owned(),
+        (Language::English, WordList::Daily) => "one of the daily words".to_owned(),
+        (Language::Finnish, _) => {
+            format!("sana on {}-listalta", word_list_name(language, word_list))
+        }
+        (Language::English, _) => format!(
+            "from the {} list",
+            word_list_name(language, word_list).to_lowercase()
+        ),
+    }
+}
+
+pub fn msg_welcome_back(language: Language, streak: usize, is_daily_missed: bool) -> String {
+    let (welcome, missed) = match language {
+        Language::Finnish => ("Tervetuloa takaisin!", "Eilinen päivän sanuli jäi väliin"),
+        Language::English => ("Welcome back!", "You missed yesterday's daily word"),
+    };
+    let welcome = format!("{} {}: {}", welcome, Text::Streak.get(language), streak);
+
+    if is_daily_missed {
+        format!("{} · {}", welcome, missed)
+    } else {
+        welcome
+    }
+}
+
+pub fn msg_practice_letter_missing(language: Language, letter: char) -> String {
+    match language {
+        Language::Finnish => format!("Yhdessäkään sanulissa ei ole kirjainta {}", letter),
+        Language::English => format!("None of the words have the letter {}", letter),
+    }
+}
+
+pub fn daily_title(language: Language, number: usize) -> String {
+    match language {
+        Language::Finnish => format!("Päivän sanuli #{}", number),
+        Language::English => format!("Daily sanuli #{}", number),
+    }
+}
+
+pub fn word_length(language: Language, word_length: usize) -> String {
+    match language {
+        Language::Finnish => format!("{} merkkiä", word_length),
+        Language::English => format!("{} letters", word_length),
+    }
+}
+
+// Counts with thousands separated, "2 413" in Finnish and "2,413" in English
+pub fn format_count(language: Language, count: usize) -> String {
+    let separator = match language {
+        Language::Finnish => '\u{a0}',
+        Language::English => ',',
+    };
+
+    let digits = count.to_string().chars().collect::<Vec<_>>();
+    let mut formatted = String::new();
+    for (index, digit) in digits.iter().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            formatted.push(separator);
+        }
+        formatted.push(*digit);
+    }
+    formatted
+}
+
+pub fn msg_word_list_count(language: Language, count: usize, word_length: usize) -> String {
+    let count = format_count(language, count);
+    match (language, word_length) {
+        (Language::Finnish, 5) => format!("{} viisikirjaimista sanaa", count),
+        (Language::Finnish, 6) => format!("{} kuusikirjaimista sanaa", count),
+        (Language::Finnish, _) => format!("{} {}-kirjaimista sanaa", count, word_length),
+        (Language::English, 5) => format!("{} five-letter words", count),
+        (Language::English, 6) => format!("{} six-letter words", count),
+        (Language::English, _) => format!("{} {}-letter words", count, word_length),
+    }
+}
+
+pub fn guess_number(language: Language, number: usize) -> String {
+    match language {
+        Language::Finnish => format!("{}. arvaus", number),
+        Language::English => format!("Guess {}", number),
+    }
+}
+
+pub fn word_count(language: Language, count: usize) -> String {
+    match (language, count) {
+        (Language::Finnish, 1) => "1 sana".to_owned(),
+        (Language::Finnish, count) => format!("{} sanaa", count),
+        (Language::English, 1) => "1 word".to_owned(),
+        (Language::English, count) => format!("{} words", count),
+    }
+}
+
+pub fn row_description(language: Language, row: usize, description: &str) -> String {
+    match language {
+        Language::Finnish => format!("Rivi {}: {}", row, description),
+        Language::English => format!("Row {}: {}", row, description),
+    }
+}
+
+pub fn row_hits(language: Language, correct: usize, present: usize) -> String {
+    match language {
+        Language::Finnish => format!("{} oikein, {} väärässä paikassa", correct, present),
+        Language::English => format!("{} correct, {} in the wrong spot", correct, present),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LANGUAGES: [Language; 2] = [Language::Finnish, Language::English];
+
+    // What UTF-8 text decoded with the wrong encoding turns into, like "L√∂ysit" or "LÃ¶ysit"
+    fn is_mangled(text: &str) -> bool {
+        text.contains(['\u{FFFD}', '√', 'Ã', 'Â'])
+    }
+
+    #[test]
+    fn strings_are_not_mangled() {
+        let emojis = SUCCESS_EMOJIS
+            .iter()
+            .chain(ANIMAL_EMOJIS.iter())
+            .chain(HEART_EMOJIS.iter())
+            .map(|emoji| emoji.to_string());
+
+        for text in emojis {
+            assert!(!is_mangled(&text), "{} is mangled", text);
+        }
+
+        for language in LANGUAGES {
+            let texts = Text::ALL.iter().map(|text| text.get(language).to_owned());
+            let formatted = [
+                msg_win(language, GameMode::DailyWord(Default::default()), "🥳"),
+                msg_streak_milestone(language, 10),
+                msg_solve_time(language, "", "1 min 2 s"),
+                msg_word_was(language, &['Ä', 'I', 'T', 'I', 'Ä']),
+                msg_practice_letter_missing(language, 'Ö'),
+                msg_daily_percentile(language, 72),
+                msg_welcome_back(language, 4, true),
+                daily_title(language, 1),
+                word_length(language, 5),
+                guess_number(language, 1),
+                word_count(language, 2),
+                row_description(language, 1, ""),
+                row_hits(language, 1, 2),
+            ];
+
+            for text in texts.chain(formatted) {
+                assert!(!text.trim().is_empty());
+                assert!(!is_mangled(&text), "{} is mangled", text);
+            }
+        }
+        assert!(is_mangled("L√∂ysit"));
+    }
+
+    #[test]
+    fn win_message_without_celebration_has_no_trailing_space() {
+        let language = Language::Finnish;
+        assert_eq!(msg_win(language, GameMode::Relay, ""), "Löysit sanan!");
+        assert_eq!(
+            msg_win(language, GameMode::Quadruple, "🐱"),
+            "Löysit sanulit! 🐱"
+        );
+    }
+
+    #[test]
+    fn language_follows_the_browser() {
+        assert!(Language::from_tag("fi") == Language::Finnish);
+        assert!(Language::from_tag("fi-FI") == Language::Finnish);
+        assert!(Language::from_tag("en-US") == Language::English);
+        assert!(Language::from_tag("sv") == Language::English);
+        assert!(Language::from_tag("") == Language::English);
+    }
+
+    #[test]
+    fn word_counts_separate_thousands() {
+        assert_eq!(
+            msg_word_list_count(Language::Finnish, 2413, 5),
+            "2\u{a0}413 viisikirjaimista sanaa"
+        );
+        assert_eq!(
+            msg_word_list_count(Language::English, 1234567, 6),
+            "1,234,567 six-letter words"
+        );
+        assert_eq!(
+            msg_word_list_count(Language::English, 413, 7),
+            "413 7-letter words"
+        );
+    }
+}
@@ -0,0 +1,128 @@
+// Helpers for the word list tools in src/bin, not part of the web app
+
+use std::collections::HashSet;
+
+use crate::solver;
+
+// Lengths of the words picked from the Kotus word list
+const KOTUS_WORD_LENGTHS: [usize; 2] = [5, 6];
+
+pub fn parse_words(words: &str, word_length: usize) -> Vec<String> {
+    words
+        .lines()
+        .filter(|word| word.chars().count() == word_length)
+        .map(|word| word.to_owned())
+        .collect()
+}
+
+/// Words of the Kotus XML word list that fit on the board and are made of only
+/// `allowed_characters`, in uppercase.
+pub fn parse_kotus_word_list(data: &str, allowed_characters: &[char]) -> Vec<String> {
+    let parts = data.split("<kotus-sanalista>\n").collect::<Vec<&str>>();
+    let words = parts[1].split("</kotus-sanalista>").collect::<Vec<&str>>();
+
+    let mut word_list = Vec::new();
+
+    for line in words[0].lines() {
+        let (word, _): (String, String) = serde_scan::scan!("<st><s>{}</s>{}" <- line).unwrap();
+        let word = word.to_uppercase();
+
+        if KOTUS_WORD_LENGTHS.contains(&word.chars().count())
+            && word.chars().all(|c| allowed_characters.contains(&c))
+        {
+            word_list.push(word);
+        }
+    }
+
+    word_list
+}
+
+/// The line number and word of every repeated word.
+pub fn duplicates(words: &str) -> Vec<(usize, &str)> {
+    let mut seen = HashSet::new();
+
+    words
+        .lines()
+        .enumerate()
+        .filter(|(_, word)| !seen.insert(*word))
+        .collect()
+}
+
+/// The best scoring words by letter frequency, each one sharing as few letters as possible
+/// with the words picked before it. Words with repeated letters are left out.
+pub fn starter_words(words: &[Vec<char>], count: usize) -> Vec<String> {
+    let mut words = words
+        .iter()
+        .filter(|word| word.iter().collect::<HashSet<_>>().len() == word.len())
+        .cloned()
+        .collect::<Vec<_>>();
+    words.sort();
+
+    let ranked = solver::rank_by_letter_frequency(&words);
+    let mut picked: Vec<&Vec<char>> = Vec::new();
+    let mut used = HashSet::new();
+
+    while picked.len() < count {
+        let best = ranked
+            .iter()
+            .filter(|(word, _)| !picked.contains(&word))
+            .min_by_key(|(word, _)| word.iter().filter(|c| used.contains(*c)).count());
+
+        match best {
+            Some((word, _)) => {
+                used.extend(word.iter().copied());
+                picked.push(word);
+            }
+            None => break,
+        }
+    }
+
+    picked
+        .into_iter()
+        .map(|word| word.iter().collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::alphabet;
+    use crate::manager::WordLanguage;
+
+    #[test]
+    fn kotus_words_are_filtered_to_the_board() {
+        let data = "<kotus-sanalista>
+<st><s>kissa</s><t><tn>9</tn></t></st>
+<st><s>kissala</s><t><tn>12</tn></t></st>
+<st><s>koira</s><t><tn>10</tn></t></st>
+<st><s>façade</s><t><tn>8</tn></t></st>
+<st><s>åkeri</s><t><tn>6</tn></t></st>
+</kotus-sanalista>";
+        let finnish = alphabet(WordLanguage::Finnish).collect::<Vec<_>>();
+        let swedish = alphabet(WordLanguage::Swedish).collect::<Vec<_>>();
+
+        assert_eq!(
+            parse_kotus_word_list(data, &finnish),
+            vec!["KISSA", "KOIRA"]
+        );
+        assert_eq!(
+            parse_kotus_word_list(data, &swedish),
+            vec!["KISSA", "KOIRA", "ÅKERI"]
+        );
+        assert_eq!(duplicates("KISSA\nKOIRA\nKISSA"), vec![(2, "KISSA")]);
+    }
+
+    #[test]
+    fn starter_words_cover_different_letters() {
+        let words = ["KISSA", "SAUNA", "TALVI", "KOIRA", "HELMI"]
+            .iter()
+            .map(|word| word.chars().collect())
+            .collect::<Vec<_>>();
+
+        // KISSA and SAUNA repeat letters. TALVI shares the most letters with the others,
+        // HELMI and KOIRA score the same and share two letters each with it.
+        assert_eq!(starter_words(&words, 3), vec!["TALVI", "HELMI", "KOIRA"]);
+        assert_eq!(starter_words(&words, 1), vec!["TALVI"]);
+        assert_eq!(starter_words(&words, 5).len(), 3);
+    }
+}
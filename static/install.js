@@ -0,0 +1,33 @@
+// Keeps the install prompt the browser offers, so that the app can show it from its menu.
+// The app is told about changes with a "sanuli-installable" event on the window.
+(function () {
+  let installPrompt = null;
+
+  function notify() {
+    window.dispatchEvent(new Event("sanuli-installable"));
+  }
+
+  window.addEventListener("beforeinstallprompt", function (e) {
+    e.preventDefault();
+    installPrompt = e;
+    notify();
+  });
+
+  window.addEventListener("appinstalled", function () {
+    installPrompt = null;
+    notify();
+  });
+
+  window.sanuliIsInstallable = function () {
+    return installPrompt !== null;
+  };
+
+  // The prompt can only be shown once, the browser offers a new one if it's dismissed
+  window.sanuliPromptInstall = function () {
+    if (installPrompt !== null) {
+      installPrompt.prompt();
+      installPrompt = null;
+      notify();
+    }
+  };
+})();
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use sanuli::game::{update_known_information, KnownCounts, KnownStates, ALLOWED_KEYS};
+use sanuli::manager::{CharacterCount, TileState};
+use sanuli::solver;
+
+struct Round {
+    word: Vec<char>,
+    guesses: Vec<Vec<char>>,
+    max_guesses: usize,
+}
+
+impl std::fmt::Debug for Round {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let word = self.word.iter().collect::<String>();
+        let guesses = self
+            .guesses
+            .iter()
+            .map(|guess| guess.iter().collect::<String>())
+            .collect::<Vec<_>>();
+        write!(f, "{word} {guesses:?} (max {})", self.max_guesses)
+    }
+}
+
+// Small alphabets make duplicate letters common, which is where the logic gets subtle
+fn round() -> impl Strategy<Value = Round> {
+    (1..=ALLOWED_KEYS.len(), 1usize..=7, 1usize..=8).prop_flat_map(
+        |(alphabet, word_length, max_guesses)| {
+            let letter = select(ALLOWED_KEYS[..alphabet].to_vec());
+            let word = prop::collection::vec(letter.clone(), word_length);
+            let guesses =
+                prop::collection::vec(prop::collection::vec(letter, word_length), 1..=max_guesses);
+            (word, guesses).prop_map(move |(word, guesses)| Round {
+                word,
+                guesses,
+                max_guesses,
+            })
+        },
+    )
+}
+
+fn count_of(count: &CharacterCount) -> usize {
+    match count {
+        CharacterCount::AtLeast(count) | CharacterCount::Exactly(count) => *count,
+    }
+}
+
+fn check_counts(before: &KnownCounts, after: &KnownCounts) -> Result<(), TestCaseError> {
+    for (character, known) in before {
+        let updated = after.get(character);
+        prop_assert!(updated.is_some(), "{} was forgotten", character);
+        let updated = updated.unwrap();
+
+        prop_assert!(
+            count_of(updated) >= count_of(known),
+            "count of {} decreased",
+            character
+        );
+        if let CharacterCount::Exactly(_) = known {
+            prop_assert!(updated == known, "exact count of {} changed", character);
+        }
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn known_information_invariants(round in round()) {
+        let mut states = vec![KnownStates::default(); round.max_guesses];
+        let mut counts = vec![HashMap::new(); round.max_guesses];
+        let mut learned_states = KnownStates::default();
+        let mut learned_counts = HashMap::new();
+
+        for (guess_index, guess) in round.guesses.iter().enumerate() {
+            let before = counts[guess_index].clone();
+            let mut row = guess
+                .iter()
+                .map(|c| (*c, TileState::Unknown))
+                .collect::<Vec<_>>();
+
+            update_known_information(
+                &mut states,
+                &mut counts,
+                &mut row,
+                guess_index,
+                &round.word,
+            );
+
+            check_counts(&before, &counts[guess_index])?;
+
+            for (character, _) in &row {
+                let highlighted = row
+                    .iter()
+                    .filter(|(c, state)| {
+                        c == character
+                            && matches!(state, TileState::Correct | TileState::Present)
+                    })
+                    .count();
+                let count_in_word = round.word.iter().filter(|c| *c == character).count();
+                prop_assert!(highlighted <= count_in_word, "too many {} highlighted", character);
+            }
+
+            let colors = row.iter().map(|(_, state)| *state).collect::<Vec<_>>();
+            prop_assert!(
+                colors == solver::feedback(guess, &round.word),
+                "row {} colored differently than a fresh guess",
+                guess_index
+            );
+
+            solver::learn(&mut learned_states, &mut learned_counts, &row);
+            prop_assert!(
+                solver::is_candidate(&round.word, &learned_states, &learned_counts),
+                "the hidden word was eliminated by row {}",
+                guess_index
+            );
+        }
+    }
+
+    #[test]
+    fn any_guess_index_is_accepted(round in round(), index in any::<prop::sample::Index>()) {
+        let guess_index = index.index(round.max_guesses);
+        let mut states = vec![KnownStates::default(); round.max_guesses];
+        let mut counts = vec![HashMap::new(); round.max_guesses];
+        let mut row = round.guesses[0]
+            .iter()
+            .map(|c| (*c, TileState::Unknown))
+            .collect::<Vec<_>>();
+
+        update_known_information(
+            &mut states,
+            &mut counts,
+            &mut row,
+            guess_index,
+            &round.word,
+        );
+    }
+}